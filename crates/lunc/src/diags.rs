@@ -0,0 +1,95 @@
+//! Diagnostics that may be emitted by the driver.
+
+use std::path::PathBuf;
+
+use lunc_diag::{Diagnostic, ErrorCode, Label, ToDiagnostic};
+use lunc_utils::Span;
+
+#[derive(Debug, Clone)]
+pub struct ImportCycle {
+    /// the files of the cycle, the first one imports the second one and so on,
+    /// the last one imports the first one.
+    pub files: Vec<String>,
+    /// location of the import declarations of the cycle, in the same order as
+    /// the files importing them.
+    pub imports: Vec<Span>,
+}
+
+impl ToDiagnostic for ImportCycle {
+    fn into_diag(self) -> Diagnostic {
+        let first = &self.files[0];
+        let cycle = self
+            .files
+            .iter()
+            .chain([first])
+            .map(|file| format!("`{file}`"))
+            .collect::<Vec<_>>()
+            .join(" -> ");
+
+        let mut labels = Vec::new();
+
+        for (i, import) in self.imports.iter().enumerate() {
+            let importing = &self.files[i];
+            let imported = self.files.get(i + 1).unwrap_or(first);
+
+            labels.push(if i == self.imports.len() - 1 {
+                Label::primary(import.fid, import.clone()).with_message(format!(
+                    "`{importing}` imports `{imported}`, closing the cycle"
+                ))
+            } else {
+                Label::secondary(import.fid, import.clone())
+                    .with_message(format!("`{importing}` imports `{imported}` here"))
+            });
+        }
+
+        Diagnostic::error()
+            .with_code(ErrorCode::ImportCycle)
+            .with_message(format!("cycle detected when importing `{first}`"))
+            .with_labels(labels)
+            .with_note(format!("the import cycle is {cycle}"))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportedFileNotFound {
+    pub path: PathBuf,
+    pub loc: Span,
+}
+
+impl ToDiagnostic for ImportedFileNotFound {
+    fn into_diag(self) -> Diagnostic {
+        Diagnostic::error()
+            .with_code(ErrorCode::ImportedFileNotFound)
+            .with_message(format!("imported file `{}` not found", self.path.display()))
+            .with_label(Label::primary(self.loc.fid, self.loc))
+            .with_note("the path of an import is relative to the file of the import")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MissingPackageDeclaration {
+    pub path: PathBuf,
+    pub loc: Span,
+}
+
+impl ToDiagnostic for MissingPackageDeclaration {
+    fn into_diag(self) -> Diagnostic {
+        let name = self
+            .path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        Diagnostic::error()
+            .with_code(ErrorCode::MissingPackageDeclaration)
+            .with_message(format!(
+                "imported file `{}` doesn't declare its package",
+                self.path.display()
+            ))
+            .with_label(Label::primary(self.loc.fid, self.loc).with_message("imported here"))
+            .with_note(format!(
+                "help: add `package {name};` at the top of `{}`",
+                self.path.display()
+            ))
+    }
+}
@@ -0,0 +1,177 @@
+//! The `lunc fmt` command, formats lun source files.
+//!
+//! The source is parsed and printed back with the [`SourceFormatter`], the
+//! comments are not part of the AST so a file with comments is not formatted
+//! in place, its formatted source can still be printed with `-stdout`.
+//!
+//! [`SourceFormatter`]: crate::parser::format::SourceFormatter
+
+use std::{
+    fs::{read_to_string, write},
+    io::{Write, stdout},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use termcolor::ColorChoice;
+
+use crate::{
+    CliArgs, CliError, Result,
+    diag::DiagnosticSink,
+    lexer::Lexer,
+    parser::{
+        Parser,
+        format::{FormatConfig, ToSource},
+    },
+};
+
+pub const FMT_HELP_MESSAGE: &str = "\
+Formatter for the Lun Programming Language.
+
+Usage: lunc fmt [OPTIONS] INPUT...
+
+Options:
+    -h, -help                Display this help message
+    -check                   Don't write the files, fail if one of them is not
+                             formatted
+    -stdout                  Print the formatted source to the standard output
+                             instead of writing the files
+    -color <choice>          Coloring possible values: 'always', 'always-ansi',
+                             'never' and 'auto'\
+";
+
+/// Arguments of the `lunc fmt` command
+#[derive(Debug, Clone, Default)]
+pub struct FmtArgs {
+    /// print the help message?
+    help: bool,
+    /// files to format
+    inputs: Vec<PathBuf>,
+    /// only check that the files are formatted
+    check: bool,
+    /// print the formatted sources instead of writing them
+    stdout: bool,
+    /// color choice
+    color: ColorChoice,
+}
+
+impl FmtArgs {
+    /// Parse the arguments, after the `fmt` command
+    pub fn parse_args(mut args: impl Iterator<Item = String>) -> Result<FmtArgs> {
+        let mut fmt = FmtArgs {
+            color: ColorChoice::Auto,
+            ..Default::default()
+        };
+
+        while let Some(arg) = args.next() {
+            if arg == "-h" || arg == "-help" {
+                fmt.help = true;
+            } else if arg == "-check" {
+                fmt.check = true;
+            } else if arg == "-stdout" {
+                fmt.stdout = true;
+            } else if arg == "-color" {
+                let choice = CliArgs::next_arg(&mut args)?;
+
+                fmt.color = ColorChoice::from_str(&choice)
+                    .map_err(|_| CliError::UnreochizedOption { arg: choice })?;
+            } else if !arg.starts_with("-") {
+                fmt.inputs.push(PathBuf::from(arg));
+            } else {
+                return Err(CliError::UnreochizedOption { arg });
+            }
+        }
+
+        if fmt.inputs.is_empty() && !fmt.help {
+            return Err(CliError::NoInputFile);
+        }
+
+        Ok(fmt)
+    }
+}
+
+/// Runs the `lunc fmt` command, `args` are the arguments after `fmt`.
+pub fn run_fmt(args: impl Iterator<Item = String>) -> Result<()> {
+    let argv = FmtArgs::parse_args(args)?;
+
+    if argv.help {
+        eprintln!("{FMT_HELP_MESSAGE}");
+        return Ok(());
+    }
+
+    let mut not_formatted = 0;
+
+    for input in &argv.inputs {
+        let source_code = read_to_string(input).map_err(|err| CliError::FileIoError {
+            path: input.clone(),
+            err,
+        })?;
+
+        let (formatted, had_comments) = format_source(&argv, input, source_code.clone())?;
+
+        if argv.stdout {
+            print!("{formatted}");
+        } else if formatted == source_code {
+            continue;
+        } else if argv.check {
+            eprintln!("{}: not formatted", input.display());
+            not_formatted += 1;
+        } else if had_comments {
+            return Err(CliError::FormatDropsComments {
+                path: input.clone(),
+            });
+        } else {
+            write(input, formatted).map_err(|err| CliError::FileIoError {
+                path: input.clone(),
+                err,
+            })?;
+        }
+    }
+
+    stdout().flush().expect("can't flush stdout");
+
+    if not_formatted != 0 {
+        return Err(CliError::NotFormatted {
+            count: not_formatted,
+        });
+    }
+
+    Ok(())
+}
+
+/// Parses the file and prints it back, returns the formatted source and if the
+/// file had comments.
+fn format_source(argv: &FmtArgs, input: &Path, source_code: String) -> Result<(String, bool)> {
+    let input_str = input.display().to_string();
+    let sink = DiagnosticSink::new();
+    let fid = sink.register_file(input_str.clone(), source_code.clone());
+
+    let builderr = || {
+        let mut sink = sink.clone();
+        CliArgs {
+            orb_name: input_str.clone(),
+            color: argv.color,
+            ..Default::default()
+        }
+        .dump_sink(&mut sink);
+
+        CliError::BuildDiagnostics {
+            failed: sink.failed(),
+        }
+    };
+
+    let mut lexer = Lexer::new(sink.clone(), source_code, fid);
+    let tokenstream = lexer.produce().ok_or_else(builderr)?;
+
+    let mut parser = Parser::new(tokenstream, sink.clone(), fid);
+    let ast = parser.produce().ok_or_else(builderr)?;
+
+    if !sink.is_empty() {
+        return Err(builderr());
+    }
+
+    Ok((
+        ast.to_source(&FormatConfig::default()),
+        lexer.had_comments(),
+    ))
+}
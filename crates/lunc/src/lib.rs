@@ -23,6 +23,7 @@ use crate::{
     diag::{DiagnosticSink, FileId},
     dsir::Desugarrer,
     lexer::Lexer,
+    loader::ModuleLoader,
     parser::Parser,
     scir::SemaChecker,
     utils::{
@@ -32,6 +33,10 @@ use crate::{
     },
 };
 
+pub mod diags;
+pub mod fmt;
+pub mod loader;
+
 mod re_exports {
     #[doc(inline)]
     pub use lunc_codegen as codegen;
@@ -102,12 +107,21 @@ pub enum CliError {
     TargetParsingError(#[from] TargetParsingError),
     #[error("unsupported target: '{target}', type 'lunc -target help' for details")]
     UnsupportedTargetTriplet { target: TargetTriplet },
+    #[error("{count} file{} not formatted", pluralize(*count))]
+    NotFormatted { count: usize },
+    #[error("{path}: the formatter would drop the comments, print it with `-stdout` instead")]
+    FormatDropsComments { path: PathBuf },
 }
 
 pub const HELP_MESSAGE: &str = "\
 Compiler for the Lun Programming Language.
 
 Usage: lunc [OPTIONS] INPUT
+       lunc fmt [OPTIONS] INPUT...
+
+Commands:
+    fmt                      Format lun source files, type `lunc fmt -help`
+                             for details
 
 Options:
     -h, -help                Display this help message
@@ -138,6 +152,7 @@ Debug flags help:
                              * inputfile
                              * tokenstream
                              * ast
+                             * ast-source
                              * dsir-tree
                              * scir-tree
                              * fir
@@ -206,6 +221,7 @@ pub enum DebugPrint {
     InputFile,
     TokenStream,
     Ast,
+    AstSource,
     DsirTree,
     ScirTree,
     Fir,
@@ -222,6 +238,7 @@ impl FromStr for DebugPrint {
             "inputfile" => Ok(Dp::InputFile),
             "tokenstream" => Ok(Dp::TokenStream),
             "ast" => Ok(Dp::Ast),
+            "ast-source" => Ok(Dp::AstSource),
             "dsir-tree" => Ok(Dp::DsirTree),
             "scir-tree" => Ok(Dp::ScirTree),
             "fir" => Ok(Dp::Fir),
@@ -456,7 +473,13 @@ pub fn run() -> Result<()> {
         );
     }));
 
-    let argv = CliArgs::parse_args(env::args())?;
+    let args: Vec<String> = env::args().collect();
+
+    if args.get(1).is_some_and(|arg| arg == "fmt") {
+        return fmt::run_fmt(args.into_iter().skip(2));
+    }
+
+    let argv = CliArgs::parse_args(args.into_iter())?;
 
     // maybe print help message
     if argv.help {
@@ -549,7 +572,7 @@ pub fn run() -> Result<()> {
 
     // 4. parsing, token stream => AST
     let mut parser = Parser::new(tokenstream, sink.clone(), root_fid);
-    let ast = parser.produce().ok_or_else(builderr)?;
+    let mut ast = parser.produce().ok_or_else(builderr)?;
 
     //    maybe print the ast
     if argv.debug_print_at(DebugPrint::Ast) {
@@ -557,6 +580,9 @@ pub fn run() -> Result<()> {
         ast.dump();
         eprintln!();
     }
+    if argv.debug_print_at(DebugPrint::AstSource) {
+        eprint!("{ast}");
+    }
     if argv.debug_halt_at(DebugHalt::Parser) {
         if sink.is_empty() {
             return Ok(());
@@ -565,9 +591,16 @@ pub fn run() -> Result<()> {
         Err(builderr())?;
     }
 
-    // 5. desugarring, AST => DSIR
+    // 5. loading, import declarations => AST of the imported files
+    let packages = ModuleLoader::new(sink.clone()).load(&mut ast);
+
+    if sink.failed() {
+        Err(builderr())?;
+    }
+
+    // 6. desugarring, AST => DSIR
     let mut desugarrer = Desugarrer::new(sink.clone(), argv.orb_name.clone());
-    let dsir = desugarrer.produce(ast).ok_or_else(builderr)?;
+    let dsir = desugarrer.produce_orb(ast, packages).ok_or_else(builderr)?;
 
     //    maybe print the DSIR
     if argv.debug_print_at(DebugPrint::DsirTree) {
@@ -583,7 +616,7 @@ pub fn run() -> Result<()> {
         Err(builderr())?;
     }
 
-    // 6. type-checking and all the semantic analysis, DSIR => SCIR
+    // 7. type-checking and all the semantic analysis, DSIR => SCIR
     let mut semacker = SemaChecker::new(sink.clone(), argv.target.clone().triplet().unwrap());
     let scir = semacker.produce(dsir).ok_or_else(builderr)?;
 
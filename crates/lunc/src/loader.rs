@@ -0,0 +1,159 @@
+//! Loading of the files imported by an orb.
+//!
+//! An import declaration, `import "util.lun";`, loads the file at the path,
+//! relative to the file of the declaration. The module of an imported file
+//! becomes a submodule of the orb named after its package, a file imported by
+//! multiple files is only loaded once.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    diag::DiagnosticSink,
+    diags::{ImportCycle, ImportedFileNotFound, MissingPackageDeclaration},
+    lexer::Lexer,
+    parser::{Parser, item::Module},
+    utils::Span,
+};
+
+/// A file being loaded, with the import declaration that loads it.
+#[derive(Debug, Clone)]
+struct Frame {
+    /// canonical path of the file
+    path: PathBuf,
+    /// name of the file in the sink
+    name: String,
+    /// location of the import declaration, `None` for the root module
+    import: Option<Span>,
+}
+
+/// Loads the files imported by the root module of an orb, and the files
+/// they import.
+#[derive(Debug, Clone)]
+pub struct ModuleLoader {
+    sink: DiagnosticSink,
+    /// modules of the files loaded, a module is after the ones it imports
+    packages: Vec<Module>,
+    /// canonical path of the files already loaded, with the name of their
+    /// package, `None` if the file failed to load
+    loaded: Vec<(PathBuf, Option<String>)>,
+    /// the files being loaded, the last one is the file we are loading the
+    /// imports of
+    stack: Vec<Frame>,
+}
+
+impl ModuleLoader {
+    /// Create a new module loader.
+    pub fn new(sink: DiagnosticSink) -> ModuleLoader {
+        ModuleLoader {
+            sink,
+            packages: Vec::new(),
+            loaded: Vec::new(),
+            stack: Vec::new(),
+        }
+    }
+
+    /// Loads the files imported by `root`, the root module of the orb, and
+    /// returns the modules of their packages.
+    pub fn load(mut self, root: &mut Module) -> Vec<Module> {
+        let name = self.sink.name(root.fid).unwrap();
+
+        self.stack.push(Frame {
+            path: fs::canonicalize(&name).unwrap_or_else(|_| PathBuf::from(&name)),
+            name,
+            import: None,
+        });
+
+        self.load_imports(root);
+
+        self.packages
+    }
+
+    /// Loads the files imported by `module`, it is the last file of the stack.
+    fn load_imports(&mut self, module: &mut Module) {
+        let importing = PathBuf::from(&self.stack.last().unwrap().name);
+        let dir = importing.parent().unwrap_or(Path::new(""));
+
+        for import in &mut module.imports {
+            let path = dir.join(&import.path);
+
+            let Ok(canonical) = fs::canonicalize(&path) else {
+                self.sink.emit(ImportedFileNotFound {
+                    path,
+                    loc: import.loc.clone(),
+                });
+                continue;
+            };
+
+            // the file is already being loaded, it imports itself
+            if let Some(i) = self.stack.iter().position(|frame| frame.path == canonical) {
+                let cycle = &self.stack[i..];
+
+                self.sink.emit(ImportCycle {
+                    files: cycle.iter().map(|frame| frame.name.clone()).collect(),
+                    imports: cycle[1..]
+                        .iter()
+                        .filter_map(|frame| frame.import.clone())
+                        .chain([import.loc.clone()])
+                        .collect(),
+                });
+                continue;
+            }
+
+            if let Some((_, package)) = self.loaded.iter().find(|(p, _)| *p == canonical) {
+                import.package = package.clone();
+                continue;
+            }
+
+            let name = path.to_string_lossy().to_string();
+            let package = self.load_file(&canonical, name, import.loc.clone());
+
+            import.package = package.clone();
+            self.loaded.push((canonical, package));
+        }
+    }
+
+    /// Loads the file at `path`, with its imports, returns the name of its
+    /// package if it was loaded.
+    fn load_file(&mut self, path: &Path, name: String, import: Span) -> Option<String> {
+        let Ok(source_code) = fs::read_to_string(path) else {
+            self.sink.emit(ImportedFileNotFound {
+                path: PathBuf::from(name),
+                loc: import,
+            });
+            return None;
+        };
+
+        let fid = self.sink.register_file(name.clone(), source_code.clone());
+
+        let mut lexer = Lexer::new(self.sink.clone(), source_code, fid);
+        let tokenstream = lexer.produce()?;
+
+        let mut parser = Parser::new(tokenstream, self.sink.clone(), fid);
+        let mut module = parser.produce()?;
+
+        let Some(package) = &module.package else {
+            self.sink.emit(MissingPackageDeclaration {
+                path: PathBuf::from(name),
+                loc: import,
+            });
+            return None;
+        };
+        let package = package.name.clone();
+
+        self.stack.push(Frame {
+            path: path.to_path_buf(),
+            name,
+            import: Some(import),
+        });
+
+        self.load_imports(&mut module);
+
+        self.stack.pop();
+        self.packages.push(module);
+
+        Some(package)
+    }
+}
@@ -120,6 +120,39 @@ impl DiagnosticSink {
         inner.push(diag);
     }
 
+    /// Returns a copy of all the diagnostics in the sink.
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        let inner = self.0.read().unwrap();
+        inner.diags.clone()
+    }
+
+    /// Returns the number of diagnostics in the sink.
+    pub fn len(&self) -> usize {
+        let inner = self.0.read().unwrap();
+        inner.diags.len()
+    }
+
+    /// Adds `label` to every diagnostic emitted since the sink had `start`
+    /// diagnostics, see [`DiagnosticSink::len`]. A diagnostic that already has
+    /// a label at the location of `label` is left as is.
+    pub fn label_since(&mut self, start: usize, label: Label<FileId>) {
+        let mut inner = self.0.write().unwrap();
+
+        for diag in inner.diags.iter_mut().skip(start) {
+            // NOTE: a recursive instantiation would label the same location
+            // once per instance
+            if diag
+                .labels
+                .iter()
+                .any(|l| l.file_id == label.file_id && l.range == label.range)
+            {
+                continue;
+            }
+
+            diag.labels.push(label.clone());
+        }
+    }
+
     /// Return the name of the current file
     pub fn name(&self, fid: FileId) -> Option<String> {
         let inner = self.0.read().unwrap();
@@ -296,7 +329,7 @@ impl ToDiagnostic for Diagnostic {
 /// |`E030`| `tests/scir/E030.lun`                             |
 /// |`E031`| `tests/scir/E031.lun`                             |
 /// |`E032`| `tests/scir/E032.lun`                             |
-/// |`E033`| `tests/scir/E033.lun`                             |
+/// |`E033`| deprecated, **CAN BE REPLACED BY A NEW CODE**     |
 /// |`E034`| `tests/scir/E034.lun`                             |
 /// |`E035`| `tests/parser/E035_1.lun`,                        |
 /// |  ^   | `tests/parser/E035_2.lun`,                        |
@@ -306,6 +339,23 @@ impl ToDiagnostic for Diagnostic {
 /// |`E038`| `tests/parser/E038.lun`                           |
 /// |`E039`| `tests/scir/E039.lun`                             |
 /// |`E040`| `tests/scir/E040.lun`                             |
+/// |`E041`| `tests/scir/E041.lun`                             |
+/// |`E042`| `tests/scir/E042.lun`                             |
+/// |`E043`| `tests/scir/E043.lun`                             |
+/// |`E044`| `tests/scir/E044.lun`                             |
+/// |`E045`| `tests/scir/E045.lun`                             |
+/// |`E046`| `tests/parser/E046.lun`                           |
+/// |`E047`| `tests/scir/E047.lun`                             |
+/// |`E048`| `tests/scir/E048.lun`                             |
+/// |`E049`| `tests/scir/E049.lun`                             |
+/// |`E050`| `tests/scir/E050.lun`                             |
+/// |`E051`| `tests/scir/E051.lun`                             |
+/// |`E052`| `tests/multifile/E052.lun`                        |
+/// |`E053`| `tests/parser/E053.lun`                           |
+/// |`E054`| `tests/scir/E054.lun`                             |
+/// |`E055`| `tests/scir/E055.lun`                             |
+/// |`E056`| `tests/scir/E056.lun`                             |
+/// |`E057`| `tests/parser/E057.lun`                           |
 ///
 /// # Note
 ///
@@ -354,7 +404,8 @@ pub enum ErrorCode {
     /// an integer literal must fit in 64 bits, so they must not exceed
     /// `340_282_366_920_938_463_463_374_607_431_768_211_455`
     TooLargeIntegerLiteral = 3,
-    /// A string (") was not terminated.
+    /// A string (") or a character (') literal was not terminated before the
+    /// end of the line.
     ///
     /// Erroneous example
     /// ```lun
@@ -514,8 +565,8 @@ pub enum ErrorCode {
     BreakUseAnImplicitLabelInBlock = 31,
     /// a block cannot be continued.
     CantContinueABlock = 32,
-    /// break from a predicate or iterator loop with a value, it is only
-    /// supported inside a labeled block or an infinite loop.
+    /// break from a predicate or iterator loop with a value
+    #[deprecated(note = "pls replace me")]
     BreakWithValueUnsupported = 33,
     /// a literal expression is overflowing
     OverflowingLiteral = 34,
@@ -532,6 +583,60 @@ pub enum ErrorCode {
     /// cannot have a function definition / declaration inside of a global
     /// mutable definition
     FunctionInGlobalMut = 40,
+    /// no field with this name on the type
+    NoFieldOnType = 41,
+    /// fields of the struct are missing in the struct literal
+    MissingFieldsInLiteral = 42,
+    /// a field is defined multiple times in a struct type or a struct literal
+    FieldDefinedMultipleTimes = 43,
+    /// item not allowed inside an impl block
+    ItemNotAllowedInImpl = 44,
+    /// an associated item is defined multiple times for the same type
+    AssociatedItemDefinedMultipleTimes = 45,
+    /// unknown specialization of a literal, a prefix like `r"..."` or a
+    /// suffix like `12u8`
+    UnknownLiteralSpecialization = 46,
+    /// `null` used as a value of a type that is not a pointer
+    NullOfNonPointerType = 47,
+    /// `return` inside the expression of a defer statement
+    ReturnInDefer = 48,
+    /// a variant is defined multiple times in an enum type
+    VariantDefinedMultipleTimes = 49,
+    /// two variants of an enum type have the same discriminant
+    DuplicateDiscriminant = 50,
+    /// the arms of a match expression don't cover all the possible values of
+    /// the matched expression
+    NonExhaustiveMatch = 51,
+    /// a private definition is used outside of the module it's defined in
+    PrivateDefinition = 52,
+    /// a positional argument follows a named argument in a function call
+    PositionalAfterNamedArg = 53,
+    /// a named argument doesn't match any parameter of the called function
+    UnknownNamedArg = 54,
+    /// an argument is given multiple times in a function call
+    ArgumentGivenMultipleTimes = 55,
+    /// a cast expression converts between types that can't be cast
+    InvalidCast = 56,
+    /// an expression is nested too deeply for the parser
+    ExpressionTooDeeplyNested = 57,
+    /// a file imports itself, directly or through the files it imports
+    ImportCycle = 58,
+    /// the file of an import declaration can't be read
+    ImportedFileNotFound = 59,
+    /// an imported file doesn't declare its package
+    MissingPackageDeclaration = 60,
+    /// the instances of a generic function are nested too deeply, it
+    /// instantiates itself with new arguments endlessly
+    InstantiationTooDeep = 61,
+    /// a struct type contains itself without a pointer, it has an infinite
+    /// size
+    RecursiveStruct = 62,
+    /// `break` or `continue` inside the expression of a defer statement, to a
+    /// label outside of it
+    JumpOutOfDefer = 63,
+    /// a float literal is too large to fit in a `f64`, or so small that it
+    /// would be rounded to zero
+    FloatLiteralOutOfRange = 64,
 }
 
 impl Display for ErrorCode {
@@ -97,3 +97,32 @@ impl ToDiagnostic for UnderscoreInExpression {
             .with_label(Label::primary(self.loc.fid, self.loc))
     }
 }
+
+#[derive(Debug, Clone)]
+pub struct PrivateDefinition {
+    pub name: String,
+    /// location of the private definition
+    pub loc_def: Option<Span>,
+    /// location of the use of the definition
+    pub loc: Span,
+}
+
+impl ToDiagnostic for PrivateDefinition {
+    fn into_diag(self) -> Diagnostic {
+        let mut diag = Diagnostic::error()
+            .with_code(ErrorCode::PrivateDefinition)
+            .with_message(format!("`{}` is private", self.name))
+            .with_label(Label::primary(self.loc.fid, self.loc).with_message("private definition"));
+
+        if let Some(loc_def) = self.loc_def {
+            diag = diag.with_label(
+                Label::secondary(loc_def.fid, loc_def).with_message("defined here without `pub`"),
+            );
+        }
+
+        diag.with_note(format!(
+            "help: add `pub` before the definition of `{}` to use it outside of its module",
+            self.name
+        ))
+    }
+}
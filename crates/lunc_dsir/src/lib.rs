@@ -6,16 +6,19 @@
 use std::{collections::HashMap, fmt::Debug, fs, path::PathBuf};
 
 use diags::{
-    ModuleFileDoesnotExist, NameDefinedMultipleTimes, NotFoundInScope, UnderscoreInExpression,
-    UnderscoreReservedIdent,
+    ModuleFileDoesnotExist, NameDefinedMultipleTimes, NotFoundInScope, PrivateDefinition,
+    UnderscoreInExpression, UnderscoreReservedIdent,
 };
 use lunc_diag::{Diagnostic, DiagnosticSink, FileId, ToDiagnostic, feature_todo};
 use lunc_lexer::Lexer;
 use lunc_parser::{
     Parser,
     directive::Directive,
-    expr::{Arg, Else, Expr, Expression, IfExpression},
-    item::{Item, Module},
+    expr::{
+        Arg, Else, EnumVariant, Expr, Expression, FieldValue, IfExpression, MatchArm, NamedArg,
+        StructField,
+    },
+    item::{ImplDef, Item, Module, PackageDecl},
     stmt::{Block, Statement, Stmt},
 };
 use lunc_utils::{
@@ -25,8 +28,8 @@ use lunc_utils::{
 
 pub use lunc_parser::{
     directive::QualifiedPath,
-    expr::{BinOp, UnaryOp},
-    item::Abi,
+    expr::{BinOp, FloatSuffix, IntSuffix, UnaryOp},
+    item::{Abi, Vis},
 };
 
 pub mod diags;
@@ -54,10 +57,30 @@ impl FromHigher for DsModule {
     type Higher = Module;
 
     fn lower(node: Self::Higher) -> Self {
-        let Module { items, fid } = node;
+        let Module {
+            package: _,
+            imports,
+            items,
+            fid,
+        } = node;
+
+        // an import declaration is an import of the module of its package,
+        // the declarations of a file that wasn't loaded are dropped.
+        let imports = imports.into_iter().filter_map(|import| {
+            let package = import.package?;
+
+            Some(DsItem::Directive(DsDirective::Import {
+                path: QualifiedPath {
+                    path: EffectivePath::from_vec(vec!["orb".to_string(), package]),
+                    loc: import.loc.clone(),
+                },
+                alias: import.alias,
+                loc: Some(import.loc),
+            }))
+        });
 
         DsModule {
-            items: lower(items),
+            items: imports.chain(items.into_iter().map(lower)).collect(),
             fid,
         }
     }
@@ -73,6 +96,7 @@ pub enum DsItem {
     /// [`GlobalVar`]: lunc_parser::item::Item::GlobalVar
     /// [`GlobalConst`]: lunc_parser::item::Item::GlobalConst
     GlobalDef {
+        vis: Vis,
         name: String,
         name_loc: OSpan,
         mutable: bool,
@@ -86,6 +110,7 @@ pub enum DsItem {
     ///
     /// [`Item::GlobalUninit`]: lunc_parser::item::Item::GlobalUninit
     GlobalUninit {
+        vis: Vis,
         name: String,
         name_loc: OSpan,
         typexpr: DsExpression,
@@ -115,6 +140,15 @@ pub enum DsItem {
         items: Vec<DsItem>,
         loc: OSpan,
     },
+    /// See [`Item::Impl`]
+    ///
+    /// [`Item::Impl`]: lunc_parser::item::Item::Impl
+    Impl {
+        params: Vec<DsArg>,
+        target: DsExpression,
+        defs: Vec<DsImplDef>,
+        loc: OSpan,
+    },
     /// See [`Item::Directive`]
     ///
     /// [`Item::Directive`]: lunc_parser::item::Item::Directive
@@ -135,6 +169,28 @@ pub enum DsDirective {
     Mod { name: String, loc: OSpan },
 }
 
+/// See [`ImplDef`]
+///
+/// [`ImplDef`]: lunc_parser::item::ImplDef
+#[derive(Debug, Clone)]
+pub struct DsImplDef {
+    pub vis: Vis,
+    pub item: DsItem,
+}
+
+impl FromHigher for DsImplDef {
+    type Higher = ImplDef;
+
+    fn lower(node: Self::Higher) -> Self {
+        let ImplDef { vis, item } = node;
+
+        DsImplDef {
+            vis,
+            item: lower(item),
+        }
+    }
+}
+
 impl FromHigher for DsDirective {
     type Higher = Directive;
 
@@ -159,6 +215,7 @@ impl FromHigher for DsItem {
     fn lower(node: Self::Higher) -> Self {
         match node {
             Item::GlobalConst {
+                vis,
                 name,
                 name_loc,
                 typexpr,
@@ -166,6 +223,7 @@ impl FromHigher for DsItem {
                 loc,
             } => DsItem::GlobalDef {
                 sym: LazySymbol::Name(name.clone()),
+                vis,
                 name,
                 name_loc: Some(name_loc),
                 mutable: false,
@@ -174,6 +232,7 @@ impl FromHigher for DsItem {
                 loc: Some(loc),
             },
             Item::GlobalVar {
+                vis,
                 name,
                 name_loc,
                 typexpr,
@@ -181,6 +240,7 @@ impl FromHigher for DsItem {
                 loc,
             } => DsItem::GlobalDef {
                 sym: LazySymbol::Name(name.clone()),
+                vis,
                 name,
                 name_loc: Some(name_loc),
                 mutable: true,
@@ -189,12 +249,14 @@ impl FromHigher for DsItem {
                 loc: Some(loc),
             },
             Item::GlobalUninit {
+                vis,
                 name,
                 name_loc,
                 typexpr,
                 loc,
             } => DsItem::GlobalUninit {
                 sym: LazySymbol::Name(name.clone()),
+                vis,
                 name,
                 name_loc: Some(name_loc),
                 typexpr: lower(typexpr),
@@ -205,6 +267,17 @@ impl FromHigher for DsItem {
                 items: lower(items),
                 loc: Some(loc),
             },
+            Item::Impl {
+                params,
+                target,
+                defs,
+                loc,
+            } => DsItem::Impl {
+                params: lower(params),
+                target: lower(target),
+                defs: lower(defs),
+                loc: Some(loc),
+            },
             Item::Directive(directive) => DsItem::Directive(lower(directive)),
         }
     }
@@ -236,13 +309,14 @@ impl FromHigher for DsExpression {
 
     fn lower(node: Self::Higher) -> Self {
         let expr = match node.expr {
-            Expr::IntLit(i) => DsExpr::IntLit(i),
+            Expr::IntLit(i, suffix) => DsExpr::IntLit(i, suffix),
             Expr::BoolLit(b) => DsExpr::BoolLit(b),
             Expr::StringLit(str) => DsExpr::StringLit(str),
             Expr::CharLit(c) => DsExpr::CharLit(c),
-            Expr::FloatLit(f) => DsExpr::FloatLit(f),
+            Expr::FloatLit(f, suffix) => DsExpr::FloatLit(f, suffix),
             // we remove the parenthesis we don't need them anymore
             Expr::Grouping(e) => return lower(*e),
+            Expr::Tuple(exprs) => DsExpr::Tuple(lower(exprs)),
             Expr::Ident(id) => DsExpr::Ident(LazySymbol::Name(id)),
             Expr::Binary { lhs, op, rhs } => DsExpr::Binary {
                 lhs: lower(lhs),
@@ -253,16 +327,31 @@ impl FromHigher for DsExpression {
                 op,
                 expr: lower(expr),
             },
+            Expr::Cast { expr, typexpr } => DsExpr::Cast {
+                expr: lower(expr),
+                typexpr: lower(typexpr),
+            },
             Expr::Borrow { mutable, expr } => DsExpr::Borrow {
                 mutable,
                 expr: lower(expr),
             },
+            Expr::Range {
+                start,
+                end,
+                inclusive,
+            } => DsExpr::Range {
+                start: lower(start),
+                end: lower(end),
+                inclusive,
+            },
             Expr::FunCall {
                 callee: called,
                 args,
+                named_args,
             } => DsExpr::FunCall {
                 callee: lower(called),
                 args: lower(args),
+                named_args: lower(named_args),
             },
             Expr::If(ifexpr) => lower_if_expression(ifexpr),
             Expr::IfThenElse {
@@ -323,12 +412,210 @@ impl FromHigher for DsExpression {
                     None,
                 ),
             },
-            Expr::IteratorLoop { loc, .. } => DsExpr::Poisoned {
-                diag: Some(feature_todo! {
-                    feature: "iterator loop",
-                    label: "traits and iterators aren't yet implemented",
-                    loc: loc,
-                }),
+            // NOTE: iterator loops over a range with a start are lowered the
+            // following way, eg:
+            //
+            // ```
+            // label: for i in start..<end {
+            //     // body
+            // }
+            // ```
+            //
+            // gets lowered down to
+            //
+            // ```
+            // {
+            //     for#iter := start; // <- not nameable by the user
+            //     for#end := end; // <- omitted for `start..`
+            //     for#iter..<for#end; // <- checks the bounds like a range
+            //
+            //     label: loop {
+            //         if !(for#end > for#iter) { // <- omitted for `start..`
+            //             break :label;
+            //         }
+            //
+            //         i := for#iter;
+            //         for#iter = for#iter + 1;
+            //
+            //         {
+            //             // body
+            //         };
+            //     }
+            // }
+            // ```
+            //
+            // and an inclusive range `start..=end` tests the end before
+            // incrementing, so that `0u8..=255u8` doesn't overflow:
+            //
+            // ```
+            // {
+            //     for#iter := start;
+            //     for#end := end;
+            //     for#iter..=for#end;
+            //     for#done := !(for#end >= for#iter);
+            //
+            //     label: loop {
+            //         if for#done {
+            //             break :label;
+            //         }
+            //
+            //         i := for#iter;
+            //
+            //         if for#iter == for#end {
+            //             for#done = true;
+            //         } else {
+            //             for#iter = for#iter + 1;
+            //         }
+            //
+            //         {
+            //             // body
+            //         };
+            //     }
+            // }
+            // ```
+            //
+            // NOTE: the start and the end are evaluated once, the range
+            // expression only checks them, eg: that they are integers.
+            //
+            // NOTE: like the while loop the `if` must stay the first statement
+            // of the loop, so that it is detected as a predicate loop in the SCIR.
+            Expr::IteratorLoop {
+                label,
+                variable,
+                variable_loc,
+                iterator,
+                body,
+                loc,
+            } => match iterator.expr {
+                Expr::Range {
+                    start: Some(start),
+                    end,
+                    inclusive,
+                } => {
+                    let iter = format!("for#{}", loc.lo);
+                    let end_var = format!("for#end#{}", loc.lo);
+                    let done = format!("for#done#{}", loc.lo);
+                    let break_out = || expr_break(label.clone().map(|(name, _)| name), None);
+
+                    // NOTE: the generated expressions are located at the range
+                    // so that their diagnostics point at it.
+                    let at_range = |expr: DsExpression| DsExpression {
+                        loc: Some(iterator.loc.clone()),
+                        ..expr
+                    };
+                    let ident = |name: &String| at_range(expr_ident(name.clone()));
+
+                    let mut before = vec![stmt_var_def(
+                        &iter,
+                        Some(start.loc.clone()),
+                        true,
+                        lower(*start),
+                    )];
+                    let mut stmts = Vec::new();
+                    let has_end = end.is_some();
+
+                    if let Some(end) = end {
+                        before.push(stmt_var_def(
+                            &end_var,
+                            Some(end.loc.clone()),
+                            false,
+                            lower(*end),
+                        ));
+                    }
+
+                    before.push(DsStatement {
+                        stmt: DsStmt::BoundsCheck(DsExpression {
+                            expr: DsExpr::Range {
+                                start: Some(Box::new(ident(&iter))),
+                                end: has_end.then(|| Box::new(ident(&end_var))),
+                                inclusive,
+                            },
+                            loc: Some(iterator.loc.clone()),
+                        }),
+                        loc: None,
+                    });
+
+                    if has_end {
+                        if inclusive {
+                            before.push(stmt_var_def(
+                                &done,
+                                None,
+                                true,
+                                at_range(expr_unary(
+                                    UnaryOp::Not,
+                                    at_range(expr_binary(
+                                        ident(&end_var),
+                                        BinOp::CompGE,
+                                        ident(&iter),
+                                    )),
+                                )),
+                            ));
+                            stmts.push(stmt_expr(expr_if(ident(&done), break_out(), None)));
+                        } else {
+                            stmts.push(stmt_expr(expr_if(
+                                at_range(expr_unary(
+                                    UnaryOp::Not,
+                                    at_range(expr_binary(
+                                        ident(&end_var),
+                                        BinOp::CompGT,
+                                        ident(&iter),
+                                    )),
+                                )),
+                                break_out(),
+                                None,
+                            )));
+                        }
+                    }
+
+                    stmts.push(stmt_var_def(
+                        variable,
+                        Some(variable_loc),
+                        false,
+                        ident(&iter),
+                    ));
+
+                    let increment = at_range(expr_binary(
+                        ident(&iter),
+                        BinOp::Assignment,
+                        at_range(expr_binary(
+                            ident(&iter),
+                            BinOp::Add,
+                            at_range(expr_int(1u8)),
+                        )),
+                    ));
+
+                    if inclusive && has_end {
+                        stmts.push(stmt_expr(at_range(expr_if(
+                            at_range(expr_binary(ident(&iter), BinOp::CompEq, ident(&end_var))),
+                            at_range(expr_binary(
+                                ident(&done),
+                                BinOp::Assignment,
+                                at_range(expr_bool(true)),
+                            )),
+                            Some(increment),
+                        ))));
+                    } else {
+                        stmts.push(stmt_expr(increment));
+                    }
+
+                    stmts.push(stmt_expr(expr_block(lower(body))));
+
+                    DsExpr::Block {
+                        label: None,
+                        block: block(
+                            loc.clone(),
+                            before,
+                            Some(Box::new(expr_loop(label, block(None, stmts, None)))),
+                        ),
+                    }
+                }
+                _ => DsExpr::Poisoned {
+                    diag: Some(feature_todo! {
+                        feature: "iterator loop",
+                        label: "traits and iterators aren't yet implemented",
+                        loc: loc,
+                    }),
+                },
             },
             Expr::InfiniteLoop { label, body } => DsExpr::Loop {
                 label,
@@ -338,14 +625,24 @@ impl FromHigher for DsExpression {
             Expr::Break { label, expr: val } => DsExpr::Break {
                 label,
                 expr: lower(val),
+                desugared: false,
             },
             Expr::Continue { label } => DsExpr::Continue { label },
             Expr::Null => DsExpr::Null,
+            Expr::Comptime(expr) => DsExpr::Comptime(lower(expr)),
             Expr::MemberAccess { expr, member } => DsExpr::MemberAccess {
                 expr: lower(expr),
                 member,
             },
+            Expr::StructLit { typexpr, fields } => DsExpr::StructLit {
+                typexpr: lower(typexpr),
+                fields: lower(fields),
+            },
             Expr::Orb => DsExpr::Ident(LazySymbol::Name("orb".to_string())),
+            Expr::Match { expr, arms } => DsExpr::Match {
+                expr: lower(expr),
+                arms: lower(arms),
+            },
             Expr::FunDefinition {
                 args,
                 rettypexpr,
@@ -367,6 +664,12 @@ impl FromHigher for DsExpression {
                 args: lower(args),
                 ret: lower(ret),
             },
+            Expr::Struct { fields } => DsExpr::Struct {
+                fields: lower(fields),
+            },
+            Expr::Enum { variants } => DsExpr::Enum {
+                variants: lower(variants),
+            },
         };
 
         DsExpression {
@@ -405,7 +708,7 @@ pub enum DsExpr {
     /// See [`Expr::IntLit`]
     ///
     /// [`Expr::IntLit`]: lunc_parser::expr::Expr::IntLit
-    IntLit(u128),
+    IntLit(u128, Option<IntSuffix>),
     /// See [`Expr::BoolLit`]
     ///
     /// [`Expr::BoolLit`]: lunc_parser::expr::Expr::BoolLit
@@ -421,7 +724,11 @@ pub enum DsExpr {
     /// See [`Expr::FloatLit`]
     ///
     /// [`Expr::FloatLit`]: lunc_parser::expr::Expr::FloatLit
-    FloatLit(f64),
+    FloatLit(f64, Option<FloatSuffix>),
+    /// See [`Expr::Tuple`]
+    ///
+    /// [`Expr::Tuple`]: lunc_parser::expr::Expr::Tuple
+    Tuple(Vec<DsExpression>),
     /// See [`Expr::Ident`]
     ///
     /// [`Expr::Ident`]: lunc_parser::expr::Expr::Ident
@@ -441,6 +748,13 @@ pub enum DsExpr {
         op: UnaryOp,
         expr: Box<DsExpression>,
     },
+    /// See [`Expr::Cast`]
+    ///
+    /// [`Expr::Cast`]: lunc_parser::expr::Expr::Cast
+    Cast {
+        expr: Box<DsExpression>,
+        typexpr: Box<DsExpression>,
+    },
     /// See [`Expr::Borrow`]
     ///
     /// [`Expr::Borrow`]: lunc_parser::expr::Expr::Borrow
@@ -448,12 +762,21 @@ pub enum DsExpr {
         mutable: bool,
         expr: Box<DsExpression>,
     },
+    /// See [`Expr::Range`]
+    ///
+    /// [`Expr::Range`]: lunc_parser::expr::Expr::Range
+    Range {
+        start: Option<Box<DsExpression>>,
+        end: Option<Box<DsExpression>>,
+        inclusive: bool,
+    },
     /// See [`Expr::FunCall`]
     ///
     /// [`Expr::FunCall`]: lunc_parser::expr::Expr::FunCall
     FunCall {
         callee: Box<DsExpression>,
         args: Vec<DsExpression>,
+        named_args: Vec<DsNamedArg>,
     },
     /// See [`Expr::If`] and [`Expr::IfThenElse`]
     ///
@@ -490,6 +813,9 @@ pub enum DsExpr {
     Break {
         label: Option<String>,
         expr: Option<Box<DsExpression>>,
+        /// is this `break` added by the desugaring? like the one exiting a
+        /// `while` loop when its condition is false.
+        desugared: bool,
     },
     /// See [`Expr::Continue`]
     ///
@@ -499,6 +825,10 @@ pub enum DsExpr {
     ///
     /// [`Expr::Null`]: lunc_parser::expr::Expr::Null
     Null,
+    /// See [`Expr::Comptime`]
+    ///
+    /// [`Expr::Comptime`]: lunc_parser::expr::Expr::Comptime
+    Comptime(Box<DsExpression>),
     /// See [`Expr::MemberAccess`]
     ///
     /// After the name resolution, member access of modules are converted to [`EffectivePath`]
@@ -507,6 +837,20 @@ pub enum DsExpr {
         expr: Box<DsExpression>,
         member: String,
     },
+    /// See [`Expr::StructLit`]
+    ///
+    /// [`Expr::StructLit`]: lunc_parser::expr::Expr::StructLit
+    StructLit {
+        typexpr: Box<DsExpression>,
+        fields: Vec<DsFieldValue>,
+    },
+    /// See [`Expr::Match`]
+    ///
+    /// [`Expr::Match`]: lunc_parser::expr::Expr::Match
+    Match {
+        expr: Box<DsExpression>,
+        arms: Vec<DsMatchArm>,
+    },
     /// Constructed from member access, eg:
     ///
     /// `orb.driver.run` are member accesses and it refers to a function "run",
@@ -518,7 +862,8 @@ pub enum DsExpr {
         sym: LazySymbol,
     },
     /// Constructed from the lazy ident `_`, but only in certain cases, like
-    /// when it's part of an assignment like so: `_ = expr`
+    /// when it's part of an assignment like so: `_ = expr` or when it's the
+    /// pattern of a match arm.
     Underscore,
     /// See [`Expr::FunDefinition`]
     ///
@@ -549,6 +894,14 @@ pub enum DsExpr {
         args: Vec<DsExpression>,
         ret: Option<Box<DsExpression>>,
     },
+    /// See [`Expr::Struct`]
+    ///
+    /// [`Expr::Struct`]: lunc_parser::expr::Expr::Struct
+    Struct { fields: Vec<DsStructField> },
+    /// See [`Expr::Enum`]
+    ///
+    /// [`Expr::Enum`]: lunc_parser::expr::Expr::Enum
+    Enum { variants: Vec<DsEnumVariant> },
     /// This is a special node, it holds a diagnostic and is emitted in lowering
     /// (ast -> dsir) to emit error instead of panic.
     ///
@@ -572,7 +925,7 @@ impl DsExpr {
 /// Creates an integer expression without location.
 pub fn expr_int(i: impl Into<u128>) -> DsExpression {
     DsExpression {
-        expr: DsExpr::IntLit(i.into()),
+        expr: DsExpr::IntLit(i.into(), None),
         loc: None,
     }
 }
@@ -604,7 +957,7 @@ pub fn expr_char(c: impl Into<char>) -> DsExpression {
 /// Creates an character expression without location.
 pub fn expr_float(f: f64) -> DsExpression {
     DsExpression {
-        expr: DsExpr::FloatLit(f),
+        expr: DsExpr::FloatLit(f, None),
         loc: None,
     }
 }
@@ -660,6 +1013,7 @@ pub fn expr_funcall(
         expr: DsExpr::FunCall {
             callee: Box::new(called),
             args: args.collect(),
+            named_args: Vec::new(),
         },
         loc: None,
     }
@@ -707,12 +1061,13 @@ pub fn expr_return(val: impl Into<Option<DsExpression>>) -> DsExpression {
     }
 }
 
-/// Creates a break expression without location.
+/// Creates a desugared break expression without location.
 pub fn expr_break(label: Option<String>, val: impl Into<Option<DsExpression>>) -> DsExpression {
     DsExpression {
         expr: DsExpr::Break {
             label,
             expr: val.into().map(Box::new),
+            desugared: true,
         },
         loc: None,
     }
@@ -887,6 +1242,9 @@ pub enum DsStmt {
     ///
     /// [`Stmt::Expression`]: lunc_parser::stmt::Stmt::Expression
     Expression(DsExpression),
+    /// The range of a `for` loop, added by the desugaring to check that both
+    /// bounds are of the same integer type.
+    BoundsCheck(DsExpression),
 }
 
 /// Creates an expression statement without location.
@@ -897,14 +1255,38 @@ pub fn stmt_expr(expr: DsExpression) -> DsStatement {
     }
 }
 
+/// Creates a variable definition statement without location.
+pub fn stmt_var_def(
+    name: impl ToString,
+    name_loc: OSpan,
+    mutable: bool,
+    value: DsExpression,
+) -> DsStatement {
+    let name = name.to_string();
+
+    DsStatement {
+        stmt: DsStmt::VariableDef {
+            sym: LazySymbol::Name(name.clone()),
+            name,
+            name_loc,
+            mutable,
+            typexpr: None,
+            value: Box::new(value),
+        },
+        loc: None,
+    }
+}
+
 /// A desugared argument, see the sweet version [`Arg`]
 ///
 /// [`Arg`]: lunc_parser::expr::Arg
 #[derive(Debug, Clone)]
 pub struct DsArg {
+    pub comptime: bool,
     pub name: String,
     pub name_loc: OSpan,
     pub typexpr: DsExpression,
+    pub default: Option<DsExpression>,
     pub loc: OSpan,
     pub sym: LazySymbol,
 }
@@ -914,17 +1296,169 @@ impl FromHigher for DsArg {
 
     fn lower(node: Self::Higher) -> Self {
         let Arg {
+            comptime,
             name,
             name_loc,
             typexpr,
+            default,
             loc,
         } = node;
 
         DsArg {
             sym: LazySymbol::Name(name.clone()),
+            comptime,
             name,
             name_loc: Some(name_loc),
             typexpr: lower(typexpr),
+            default: lower(default),
+            loc: Some(loc),
+        }
+    }
+}
+
+/// A desugared named argument, see the sweet version [`NamedArg`]
+///
+/// [`NamedArg`]: lunc_parser::expr::NamedArg
+#[derive(Debug, Clone)]
+pub struct DsNamedArg {
+    pub name: String,
+    pub name_loc: OSpan,
+    pub value: DsExpression,
+    pub loc: OSpan,
+}
+
+impl FromHigher for DsNamedArg {
+    type Higher = NamedArg;
+
+    fn lower(node: Self::Higher) -> Self {
+        let NamedArg {
+            name,
+            name_loc,
+            value,
+            loc,
+        } = node;
+
+        DsNamedArg {
+            name,
+            name_loc: Some(name_loc),
+            value: lower(value),
+            loc: Some(loc),
+        }
+    }
+}
+
+/// A desugared struct field, see the sweet version [`StructField`]
+///
+/// [`StructField`]: lunc_parser::expr::StructField
+#[derive(Debug, Clone)]
+pub struct DsStructField {
+    pub name: String,
+    pub name_loc: OSpan,
+    pub typexpr: DsExpression,
+    pub loc: OSpan,
+}
+
+impl FromHigher for DsStructField {
+    type Higher = StructField;
+
+    fn lower(node: Self::Higher) -> Self {
+        let StructField {
+            name,
+            name_loc,
+            typexpr,
+            loc,
+        } = node;
+
+        DsStructField {
+            name,
+            name_loc: Some(name_loc),
+            typexpr: lower(typexpr),
+            loc: Some(loc),
+        }
+    }
+}
+
+/// A desugared enum variant, see the sweet version [`EnumVariant`]
+///
+/// [`EnumVariant`]: lunc_parser::expr::EnumVariant
+#[derive(Debug, Clone)]
+pub struct DsEnumVariant {
+    pub name: String,
+    pub name_loc: OSpan,
+    pub value: Option<DsExpression>,
+    pub loc: OSpan,
+}
+
+impl FromHigher for DsEnumVariant {
+    type Higher = EnumVariant;
+
+    fn lower(node: Self::Higher) -> Self {
+        let EnumVariant {
+            name,
+            name_loc,
+            value,
+            loc,
+        } = node;
+
+        DsEnumVariant {
+            name,
+            name_loc: Some(name_loc),
+            value: lower(value),
+            loc: Some(loc),
+        }
+    }
+}
+
+/// A desugared match arm, see the sweet version [`MatchArm`]
+///
+/// [`MatchArm`]: lunc_parser::expr::MatchArm
+#[derive(Debug, Clone)]
+pub struct DsMatchArm {
+    pub pats: Vec<DsExpression>,
+    pub body: DsExpression,
+    pub loc: OSpan,
+}
+
+impl FromHigher for DsMatchArm {
+    type Higher = MatchArm;
+
+    fn lower(node: Self::Higher) -> Self {
+        let MatchArm { pats, body, loc } = node;
+
+        DsMatchArm {
+            pats: lower(pats),
+            body: lower(body),
+            loc: Some(loc),
+        }
+    }
+}
+
+/// A desugared field value, see the sweet version [`FieldValue`]
+///
+/// [`FieldValue`]: lunc_parser::expr::FieldValue
+#[derive(Debug, Clone)]
+pub struct DsFieldValue {
+    pub name: String,
+    pub name_loc: OSpan,
+    pub value: DsExpression,
+    pub loc: OSpan,
+}
+
+impl FromHigher for DsFieldValue {
+    type Higher = FieldValue;
+
+    fn lower(node: Self::Higher) -> Self {
+        let FieldValue {
+            name,
+            name_loc,
+            value,
+            loc,
+        } = node;
+
+        DsFieldValue {
+            name,
+            name_loc: Some(name_loc),
+            value: lower(value),
             loc: Some(loc),
         }
     }
@@ -961,6 +1495,13 @@ impl Desugarrer {
 
     /// Try to produce a desugarred module.
     pub fn produce(&mut self, ast: Module) -> Option<DsModule> {
+        self.produce_orb(ast, Vec::new())
+    }
+
+    /// Try to produce the desugarred root module of the orb, the `packages`
+    /// are the modules of the files imported by the orb, they become
+    /// submodules of the root module named after their package.
+    pub fn produce_orb(&mut self, ast: Module, packages: Vec<Module>) -> Option<DsModule> {
         let mut module = lower(ast);
 
         self.inline_modules(&mut module);
@@ -971,6 +1512,30 @@ impl Desugarrer {
             return Some(module);
         }
 
+        // NOTE: the packages are before the items of the root module, so that
+        // they are bound before the imports of them, a package is after the
+        // ones it imports.
+        let mut package_items = Vec::new();
+
+        for package in packages {
+            let Some(PackageDecl { name, loc }) = package.package.clone() else {
+                continue;
+            };
+
+            let Some(package_dsir) = self.produce(package) else {
+                continue;
+            };
+
+            package_items.push(DsItem::Module {
+                name: name.clone(),
+                module: package_dsir,
+                loc: Some(loc),
+                sym: LazySymbol::Name(name),
+            });
+        }
+
+        module.items.splice(0..0, package_items);
+
         self.orb.sym = LazySymbol::Sym(self.table.lookup("orb").unwrap());
 
         // resolve the root module, then it will recurse
@@ -1042,6 +1607,16 @@ impl Desugarrer {
                     None => continue,
                 };
 
+                // NOTE: the files imported are loaded by the driver before
+                // the desugaring, it doesn't see the submodules.
+                if let Some(import) = ast.imports.first() {
+                    self.sink.emit(feature_todo! {
+                        feature: "import declarations in a submodule",
+                        label: "imported in a submodule",
+                        loc: import.loc.clone(),
+                    });
+                }
+
                 // 6. desugar it.
                 let submodule_dsir = match self.produce(ast) {
                     Some(dsir) => dsir,
@@ -1100,6 +1675,8 @@ impl Desugarrer {
                 self.resolve_module(submod, self.current_path.clone());
             }
         }
+
+        self.current_path.pop();
     }
 
     /// Resolve names of an item
@@ -1133,6 +1710,66 @@ impl Desugarrer {
 
                 Ok(())
             }
+            DsItem::Impl {
+                params,
+                target,
+                defs,
+                loc: _,
+            } => {
+                self.table.scope_enter(); // impl scope
+
+                for DsArg {
+                    comptime: _,
+                    name,
+                    name_loc,
+                    typexpr,
+                    default: _,
+                    loc: _,
+                    sym,
+                } in params
+                {
+                    match self.resolve_expr(typexpr) {
+                        Ok(()) => {}
+                        Err(d) => self.sink.emit(d),
+                    }
+
+                    // NOTE: the parameters are compile-time known, so they
+                    // are visible in the functions of the impl block.
+                    let mut path = self.current_path.clone();
+                    path.push(name.clone());
+
+                    let symref = Symbol::global(
+                        false,
+                        name.clone(),
+                        path,
+                        Typeness::Explicit,
+                        name_loc.clone(),
+                    );
+
+                    *sym = LazySymbol::Sym(symref.clone());
+
+                    match self.table.bind(name.clone(), symref) {
+                        Ok(()) => {}
+                        Err(d) => self.sink.emit(d),
+                    }
+                }
+
+                match self.resolve_expr(target) {
+                    Ok(()) => {}
+                    Err(d) => self.sink.emit(d),
+                }
+
+                for DsImplDef { item: def, .. } in defs {
+                    match self.resolve_item(def) {
+                        Ok(()) => {}
+                        Err(d) => self.sink.emit(d),
+                    }
+                }
+
+                self.table.scope_exit(); // impl scope
+
+                Ok(())
+            }
             DsItem::Module { .. } | DsItem::Directive(_) => Ok(()),
         }
     }
@@ -1199,18 +1836,20 @@ impl Desugarrer {
 
                 Ok(())
             }
-            DsStmt::Defer { expr } | DsStmt::Expression(expr) => self.resolve_expr(expr),
+            DsStmt::Defer { expr } | DsStmt::Expression(expr) | DsStmt::BoundsCheck(expr) => {
+                self.resolve_expr(expr)
+            }
         }
     }
 
     /// Resolve expression
     pub fn resolve_expr(&mut self, expr: &mut DsExpression) -> Result<(), Diagnostic> {
         match &mut expr.expr {
-            DsExpr::IntLit(_)
+            DsExpr::IntLit(..)
             | DsExpr::BoolLit(_)
             | DsExpr::StringLit(_)
             | DsExpr::CharLit(_)
-            | DsExpr::FloatLit(_) => Ok(()),
+            | DsExpr::FloatLit(..) => Ok(()),
             DsExpr::Binary {
                 lhs,
                 op: BinOp::Assignment,
@@ -1227,13 +1866,47 @@ impl Desugarrer {
             DsExpr::Unary { op: _, expr } | DsExpr::Borrow { mutable: _, expr } => {
                 self.resolve_expr(expr)
             }
-            DsExpr::FunCall { callee, args } => {
+            DsExpr::Cast { expr, typexpr } => {
+                self.resolve_expr(expr)?;
+                self.resolve_expr(typexpr)
+            }
+            DsExpr::Range {
+                start,
+                end,
+                inclusive: _,
+            } => {
+                if let Some(start) = start {
+                    self.resolve_expr(start)?;
+                }
+
+                if let Some(end) = end {
+                    self.resolve_expr(end)?;
+                }
+
+                Ok(())
+            }
+            DsExpr::Tuple(exprs) => {
+                for expr in exprs {
+                    self.resolve_expr(expr)?;
+                }
+
+                Ok(())
+            }
+            DsExpr::FunCall {
+                callee,
+                args,
+                named_args,
+            } => {
                 self.resolve_expr(callee)?;
 
                 for arg in args {
                     self.resolve_expr(arg)?;
                 }
 
+                for arg in named_args {
+                    self.resolve_expr(&mut arg.value)?;
+                }
+
                 Ok(())
             }
             DsExpr::If {
@@ -1258,7 +1931,7 @@ impl Desugarrer {
 
                 Ok(())
             }
-            DsExpr::Return { expr } | DsExpr::Break { label: _, expr } => {
+            DsExpr::Return { expr } | DsExpr::Break { expr, .. } => {
                 if let Some(expr) = expr {
                     self.resolve_expr(expr)?;
                 }
@@ -1266,6 +1939,7 @@ impl Desugarrer {
                 Ok(())
             }
             DsExpr::Continue { label: _ } | DsExpr::Null => Ok(()),
+            DsExpr::Comptime(expr) => self.resolve_expr(expr),
             DsExpr::PointerType {
                 mutable: _,
                 typexpr,
@@ -1281,6 +1955,50 @@ impl Desugarrer {
 
                 Ok(())
             }
+            DsExpr::Struct { fields } => {
+                for field in fields {
+                    self.resolve_expr(&mut field.typexpr)?;
+                }
+
+                Ok(())
+            }
+            DsExpr::StructLit { typexpr, fields } => {
+                self.resolve_expr(typexpr)?;
+
+                for field in fields {
+                    self.resolve_expr(&mut field.value)?;
+                }
+
+                Ok(())
+            }
+            DsExpr::Enum { variants } => {
+                for variant in variants {
+                    if let Some(value) = &mut variant.value {
+                        self.resolve_expr(value)?;
+                    }
+                }
+
+                Ok(())
+            }
+            DsExpr::Match { expr, arms } => {
+                self.resolve_expr(expr)?;
+
+                for arm in arms {
+                    for pat in &mut arm.pats {
+                        if matches!(&pat.expr, DsExpr::Ident(LazySymbol::Name(id)) if id.as_str() == "_")
+                        {
+                            // we allow _ as a pattern, it matches anything
+                            pat.expr = DsExpr::Underscore;
+                        } else {
+                            self.resolve_expr(pat)?;
+                        }
+                    }
+
+                    self.resolve_expr(&mut arm.body)?;
+                }
+
+                Ok(())
+            }
             DsExpr::Ident(LazySymbol::Name(name)) => {
                 if name == "_" {
                     return Err(UnderscoreInExpression {
@@ -1325,6 +2043,7 @@ impl Desugarrer {
                     && let Some(symref) = module.def_or_mod(&sym_name)
                 {
                     // looked up in orb tree for absolute paths (in general)
+                    self.ck_vis(module, &symref, &path.loc)?;
                     *sym = LazySymbol::Sym(symref);
 
                     Ok(())
@@ -1332,6 +2051,7 @@ impl Desugarrer {
                     && let Some(symref) = module.def_or_mod(&sym_name)
                 {
                     // looked up in orb tree for relative paths (in general)
+                    self.ck_vis(module, &symref, &path.loc)?;
                     *sym = LazySymbol::Sym(symref);
 
                     Ok(())
@@ -1352,6 +2072,7 @@ impl Desugarrer {
                     // a relative path is a path that does not start with `orb`,
                     // and the first member is a refers to a module
 
+                    self.ck_vis(module, &symref, &path.loc)?;
                     *sym = LazySymbol::Sym(symref);
                     Ok(())
                 } else {
@@ -1397,12 +2118,23 @@ impl Desugarrer {
                 rettypexpr,
                 body,
             } => {
-                self.table.scope_enter(); // fundef scope
+                // NOTE: the default values are resolved outside of the
+                // function, they can't refer to the other arguments.
+                for default in args.iter_mut().filter_map(|arg| arg.default.as_mut()) {
+                    match self.resolve_expr(default) {
+                        Ok(()) => {}
+                        Err(d) => self.sink.emit(d),
+                    }
+                }
+
+                self.table.fun_scope_enter(); // fundef scope
 
                 for DsArg {
+                    comptime: _,
                     name,
                     name_loc,
                     typexpr,
+                    default: _,
                     loc: _,
                     sym,
                 } in args
@@ -1502,6 +2234,7 @@ impl Desugarrer {
     ) -> Result<(), Diagnostic> {
         match item {
             DsItem::GlobalDef {
+                vis,
                 name,
                 name_loc,
                 mutable: _,
@@ -1517,10 +2250,11 @@ impl Desugarrer {
                     sym.symbol()
                         .unwrap_or(Symbol::function(name.clone(), path, name_loc.clone()));
 
-                self.orb
-                    .goto_mut(&self.current_path)
-                    .unwrap()
-                    .define(name.clone(), symref.clone());
+                self.orb.goto_mut(&self.current_path).unwrap().define(
+                    name.clone(),
+                    symref.clone(),
+                    vis.clone(),
+                );
 
                 *sym = LazySymbol::Sym(symref.clone());
 
@@ -1534,6 +2268,7 @@ impl Desugarrer {
                 Ok(())
             }
             DsItem::GlobalDef {
+                vis,
                 name,
                 name_loc,
                 mutable,
@@ -1557,10 +2292,11 @@ impl Desugarrer {
                     name_loc.clone(),
                 ));
 
-                self.orb
-                    .goto_mut(&self.current_path)
-                    .unwrap()
-                    .define(name.clone(), symref.clone());
+                self.orb.goto_mut(&self.current_path).unwrap().define(
+                    name.clone(),
+                    symref.clone(),
+                    vis.clone(),
+                );
 
                 *sym = LazySymbol::Sym(symref.clone());
 
@@ -1574,6 +2310,7 @@ impl Desugarrer {
                 Ok(())
             }
             DsItem::GlobalUninit {
+                vis,
                 name,
                 name_loc,
                 typexpr: _,
@@ -1591,10 +2328,11 @@ impl Desugarrer {
                     name_loc.clone(),
                 ));
 
-                self.orb
-                    .goto_mut(&self.current_path)
-                    .unwrap()
-                    .define(name.clone(), symref.clone());
+                self.orb.goto_mut(&self.current_path).unwrap().define(
+                    name.clone(),
+                    symref.clone(),
+                    vis.clone(),
+                );
 
                 *sym = LazySymbol::Sym(symref.clone());
 
@@ -1673,7 +2411,21 @@ impl Desugarrer {
                 if let Some(module) = self.orb.goto(&mod_path)
                     && let Some(symref) = module.def_or_mod(&name)
                 {
-                    self.table.bind(alias.clone().unwrap_or(name), symref)
+                    self.ck_vis(module, &symref, &path.loc)?;
+
+                    let name = alias.clone().unwrap_or(name);
+
+                    // NOTE: the definition is already in scope with this name,
+                    // like a package imported by the root module.
+                    if self
+                        .table
+                        .lookup(&name)
+                        .is_some_and(|previous| previous.path() == symref.path())
+                    {
+                        return Ok(());
+                    }
+
+                    self.table.bind(name, symref)
                 } else {
                     Err(NotFoundInScope {
                         name: path.path.to_string(),
@@ -1682,9 +2434,120 @@ impl Desugarrer {
                     .into_diag())
                 }
             }
+            DsItem::Impl {
+                params: _,
+                target,
+                defs,
+                loc: _,
+            } => {
+                // NOTE: the definitions of an impl block are not bound in the
+                // module, they are only reachable through the target type.
+                let mut path = self.current_path.clone();
+
+                match &target.expr {
+                    DsExpr::Ident(LazySymbol::Name(name)) => path.push(name.clone()),
+                    // a generic target, `Vec(T)`
+                    DsExpr::FunCall { callee, .. } => {
+                        if let DsExpr::Ident(LazySymbol::Name(name)) = &callee.expr {
+                            path.push(name.clone());
+                        }
+                    }
+                    _ => {}
+                }
+
+                for DsImplDef { item: def, .. } in defs {
+                    self.bind_impl_def(def, path.clone());
+                }
+
+                Ok(())
+            }
             DsItem::Directive(DsDirective::Mod { .. }) => Ok(()),
         }
     }
+
+    /// Checks that the definition `symref` of `module` can be used from the
+    /// module we are currently in. A private definition can only be used
+    /// inside of the module it is defined in and its submodules.
+    fn ck_vis(&self, module: &ModuleTree, symref: &Symbol, loc: &Span) -> Result<(), Diagnostic> {
+        if module.def_vis(symref.name()) == Vis::Public {
+            return Ok(());
+        }
+
+        let mut def_mod = symref.path();
+        def_mod.pop();
+
+        if self.current_path.as_slice().starts_with(def_mod.as_slice()) {
+            return Ok(());
+        }
+
+        Err(PrivateDefinition {
+            name: symref.path().to_string(),
+            loc_def: symref.loc(),
+            loc: loc.clone(),
+        }
+        .into_diag())
+    }
+
+    /// Creates the symbol of a definition inside of an impl block, `path` is
+    /// the path of the impl block's target.
+    fn bind_impl_def(&mut self, item: &mut DsItem, mut path: EffectivePath) {
+        match item {
+            DsItem::GlobalDef {
+                vis: _,
+                name,
+                name_loc,
+                mutable,
+                typexpr,
+                value,
+                loc: _,
+                sym,
+            } => {
+                path.push(name.clone());
+
+                let symref = if value.expr.is_fundef() || value.expr.is_fundecl() {
+                    sym.symbol()
+                        .unwrap_or(Symbol::function(name.clone(), path, name_loc.clone()))
+                } else {
+                    sym.symbol().unwrap_or(Symbol::global(
+                        *mutable,
+                        name.clone(),
+                        path,
+                        if typexpr.is_some() {
+                            Typeness::Explicit
+                        } else {
+                            Typeness::Implicit
+                        },
+                        name_loc.clone(),
+                    ))
+                };
+
+                *sym = LazySymbol::Sym(symref);
+            }
+            DsItem::GlobalUninit {
+                vis: _,
+                name,
+                name_loc,
+                typexpr: _,
+                loc: _,
+                sym,
+            } => {
+                path.push(name.clone());
+
+                let symref = sym.symbol().unwrap_or(Symbol::global(
+                    true,
+                    name.clone(),
+                    path,
+                    Typeness::Explicit,
+                    name_loc.clone(),
+                ));
+
+                *sym = LazySymbol::Sym(symref);
+            }
+            // NOTE: the parser only produces global definitions inside of an
+            // impl block
+            _ => {}
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -1695,6 +2558,9 @@ pub struct SymbolMap {
     local_count: usize,
     arg_count: usize,
     mod_count: usize,
+    /// is this map the scope of a function? locals and arguments of the
+    /// enclosing scopes are not visible past it
+    fun_boundary: bool,
 }
 
 impl SymbolMap {
@@ -1706,6 +2572,7 @@ impl SymbolMap {
             local_count: 0,
             arg_count: 0,
             mod_count: 0,
+            fun_boundary: false,
         }
     }
 
@@ -1736,6 +2603,7 @@ impl SymbolMap {
                     Symbol::new_typ("usz", Type::Noreturn),
                 ),
                 ("void".to_string(), Symbol::new_typ("usz", Type::Void)),
+                ("type".to_string(), Symbol::new_typ("type", Type::Type)),
                 (
                     "orb".to_string(),
                     // NOTE: here we can set the loc to be 0..0 into the root
@@ -1753,6 +2621,7 @@ impl SymbolMap {
             local_count: 0,
             arg_count: 0,
             mod_count: 0,
+            fun_boundary: false,
         }
     }
 }
@@ -1798,7 +2667,16 @@ impl SymbolTable {
         self.tabs.push(SymbolMap::new())
     }
 
-    /// Enter a new scope
+    /// Enter a new function scope, the locals and arguments of the enclosing
+    /// scopes can't be captured
+    pub fn fun_scope_enter(&mut self) {
+        self.tabs.push(SymbolMap {
+            fun_boundary: true,
+            ..SymbolMap::new()
+        })
+    }
+
+    /// Exit the current scope
     pub fn scope_exit(&mut self) {
         assert_ne!(self.tabs.len(), 1, "can't exit out of the global scope");
 
@@ -1867,10 +2745,17 @@ impl SymbolTable {
     pub fn lookup(&mut self, name: impl AsRef<str>) -> Option<Symbol> {
         let name = name.as_ref();
 
+        // did we go past the scope of a function?
+        let mut crossed_fun = false;
+
         for tab in self.tabs.iter().rev() {
-            if let Some(symref) = tab.map.get(name) {
+            if let Some(symref) = tab.map.get(name)
+                && !(crossed_fun && matches!(symref.kind(), SymKind::Local { .. } | SymKind::Arg))
+            {
                 return Some(symref.clone());
             }
+
+            crossed_fun |= tab.fun_boundary;
         }
 
         None
@@ -1917,7 +2802,7 @@ pub struct ModuleTree {
     /// definitions in this module tree, a definition can only be one of:
     /// - global
     /// - function
-    defs: HashMap<String, Symbol>,
+    defs: HashMap<String, (Symbol, Vis)>,
     /// is this module tree the root module?
     root_name: Option<String>,
     /// symbol of the module
@@ -1946,14 +2831,15 @@ impl ModuleTree {
         self.submodules.get_mut(name.as_ref())
     }
 
-    /// Define a new symbol inside the current module tree
-    pub fn define(&mut self, name: String, sym: Symbol) {
+    /// Define a new symbol inside the current module tree, with its
+    /// visibility
+    pub fn define(&mut self, name: String, sym: Symbol, vis: Vis) {
         assert!(matches!(
             sym.kind(),
             SymKind::Global { .. } | SymKind::Function
         ));
 
-        self.defs.insert(name, sym.clone());
+        self.defs.insert(name, (sym.clone(), vis));
     }
 
     /// Define a new module in the current module tree
@@ -2010,7 +2896,16 @@ impl ModuleTree {
 
     /// Get a definition in the current module tree
     pub fn def(&self, name: impl AsRef<str>) -> Option<Symbol> {
-        self.defs.get(name.as_ref()).cloned()
+        self.defs.get(name.as_ref()).map(|(sym, _)| sym.clone())
+    }
+
+    /// Get the visibility of the definition with this name, submodules are
+    /// always public
+    pub fn def_vis(&self, name: impl AsRef<str>) -> Vis {
+        self.defs
+            .get(name.as_ref())
+            .map(|(_, vis)| vis.clone())
+            .unwrap_or(Vis::Public)
     }
 
     /// Returns the symbol of the definition or the module with this name
@@ -8,7 +8,8 @@ use lunc_utils::{
 };
 
 use crate::{
-    DsArg, DsBlock, DsDirective, DsExpr, DsExpression, DsItem, DsModule, DsStatement, DsStmt,
+    DsArg, DsBlock, DsDirective, DsEnumVariant, DsExpr, DsExpression, DsFieldValue, DsImplDef,
+    DsItem, DsMatchArm, DsModule, DsNamedArg, DsStatement, DsStmt, DsStructField,
 };
 
 impl PrettyDump for DsModule {
@@ -21,6 +22,7 @@ impl PrettyDump for DsItem {
     fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
         match self {
             DsItem::GlobalDef {
+                vis,
                 name,
                 name_loc,
                 mutable,
@@ -30,6 +32,7 @@ impl PrettyDump for DsItem {
                 sym,
             } => {
                 ctx.pretty_struct("GlobalDef")
+                    .field("vis", vis)
                     .field("name", (name, name_loc))
                     .field("mutable", mutable)
                     .field("typexpr", typexpr)
@@ -42,6 +45,7 @@ impl PrettyDump for DsItem {
                 Ok(())
             }
             DsItem::GlobalUninit {
+                vis,
                 name,
                 name_loc,
                 typexpr,
@@ -49,6 +53,7 @@ impl PrettyDump for DsItem {
                 sym,
             } => {
                 ctx.pretty_struct("GlobalUninit")
+                    .field("vis", vis)
                     .field("name", (name, name_loc))
                     .field("typexpr", typexpr)
                     .field("sym", sym)
@@ -82,11 +87,40 @@ impl PrettyDump for DsItem {
 
                 Ok(())
             }
+            DsItem::Impl {
+                params,
+                target,
+                defs,
+                loc,
+            } => {
+                ctx.pretty_struct("Impl")
+                    .field("params", params.as_slice())
+                    .field("target", target)
+                    .field("defs", defs.as_slice())
+                    .finish()?;
+
+                ctx.print_loc(loc)?;
+
+                Ok(())
+            }
             DsItem::Directive(directive) => directive.try_dump(ctx),
         }
     }
 }
 
+impl PrettyDump for DsImplDef {
+    fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
+        let DsImplDef { vis, item } = self;
+
+        ctx.pretty_struct("ImplDef")
+            .field("vis", vis)
+            .field("item", item)
+            .finish()?;
+
+        Ok(())
+    }
+}
+
 impl PrettyDump for DsDirective {
     fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
         match self {
@@ -126,11 +160,13 @@ impl PrettyDump for DsExpr {
         let out = &mut ctx.out;
 
         match self {
-            DsExpr::IntLit(i) => write!(out, "integer {i}"),
+            DsExpr::IntLit(i, None) => write!(out, "integer {i}"),
+            DsExpr::IntLit(i, Some(suffix)) => write!(out, "integer {i}{suffix}"),
             DsExpr::BoolLit(b) => write!(out, "boolean {b}"),
             DsExpr::StringLit(s) => write!(out, "string {s:?}"),
             DsExpr::CharLit(c) => write!(out, "character {c:?}"),
-            DsExpr::FloatLit(f) => write!(out, "float {f:.}"),
+            DsExpr::FloatLit(f, None) => write!(out, "float {f:.}"),
+            DsExpr::FloatLit(f, Some(suffix)) => write!(out, "float {f:.}{suffix}"),
             DsExpr::Ident(lazysym) => lazysym.try_dump(ctx),
             DsExpr::Binary { lhs, op, rhs } => {
                 ctx.pretty_struct("Binary")
@@ -149,6 +185,14 @@ impl PrettyDump for DsExpr {
 
                 Ok(())
             }
+            DsExpr::Cast { expr, typexpr } => {
+                ctx.pretty_struct("Cast")
+                    .field("expr", expr)
+                    .field("typexpr", typexpr)
+                    .finish()?;
+
+                Ok(())
+            }
             DsExpr::Borrow { mutable, expr } => {
                 ctx.pretty_struct("Borrow")
                     .field("mutable", mutable)
@@ -157,10 +201,35 @@ impl PrettyDump for DsExpr {
 
                 Ok(())
             }
-            DsExpr::FunCall { callee, args } => {
+            DsExpr::Range {
+                start,
+                end,
+                inclusive,
+            } => {
+                ctx.pretty_struct("Range")
+                    .field("start", start)
+                    .field("end", end)
+                    .field("inclusive", inclusive)
+                    .finish()?;
+
+                Ok(())
+            }
+            DsExpr::Tuple(exprs) => {
+                ctx.pretty_struct("Tuple")
+                    .field("exprs", exprs.as_slice())
+                    .finish()?;
+
+                Ok(())
+            }
+            DsExpr::FunCall {
+                callee,
+                args,
+                named_args,
+            } => {
                 ctx.pretty_struct("FunCall")
                     .field("callee", callee)
                     .field("args", args.as_slice())
+                    .field("named_args", named_args.as_slice())
                     .finish()?;
 
                 Ok(())
@@ -210,10 +279,15 @@ impl PrettyDump for DsExpr {
                 ctx.pretty_struct("Return").field("expr", expr).finish()?;
                 Ok(())
             }
-            DsExpr::Break { label, expr } => {
+            DsExpr::Break {
+                label,
+                expr,
+                desugared,
+            } => {
                 ctx.pretty_struct("Break")
                     .field("label", label)
                     .field("expr", expr)
+                    .field("desugared", desugared)
                     .finish()?;
                 Ok(())
             }
@@ -227,6 +301,11 @@ impl PrettyDump for DsExpr {
             DsExpr::Null => {
                 write!(ctx.out, "Null")
             }
+            DsExpr::Comptime(expr) => {
+                ctx.pretty_struct("Comptime").field("expr", expr).finish()?;
+
+                Ok(())
+            }
             DsExpr::MemberAccess { expr, member } => {
                 ctx.pretty_struct("MemberAccess")
                     .field("expr", expr)
@@ -235,6 +314,22 @@ impl PrettyDump for DsExpr {
 
                 Ok(())
             }
+            DsExpr::StructLit { typexpr, fields } => {
+                ctx.pretty_struct("StructLit")
+                    .field("typexpr", typexpr)
+                    .field("fields", fields.as_slice())
+                    .finish()?;
+
+                Ok(())
+            }
+            DsExpr::Match { expr, arms } => {
+                ctx.pretty_struct("Match")
+                    .field("expr", expr)
+                    .field("arms", arms.as_slice())
+                    .finish()?;
+
+                Ok(())
+            }
             DsExpr::QualifiedPath { path, sym } => {
                 ctx.pretty_struct("QualifiedPath")
                     .field("path", path)
@@ -281,6 +376,20 @@ impl PrettyDump for DsExpr {
 
                 Ok(())
             }
+            DsExpr::Struct { fields } => {
+                ctx.pretty_struct("Struct")
+                    .field("fields", fields.as_slice())
+                    .finish()?;
+
+                Ok(())
+            }
+            DsExpr::Enum { variants } => {
+                ctx.pretty_struct("Enum")
+                    .field("variants", variants.as_slice())
+                    .finish()?;
+
+                Ok(())
+            }
             DsExpr::Poisoned { diag } => {
                 write!(ctx.out, "POISONED: {diag:#?}")
             }
@@ -291,16 +400,20 @@ impl PrettyDump for DsExpr {
 impl PrettyDump for DsArg {
     fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
         let DsArg {
+            comptime,
             name,
             name_loc,
             typexpr,
+            default,
             loc,
             sym,
         } = self;
 
         ctx.pretty_struct("Arg")
+            .field("comptime", comptime)
             .field("name", (name, name_loc))
             .field("typexpr", typexpr)
+            .field("default", default)
             .field("sym", sym)
             .finish()?;
         ctx.print_loc(loc)?;
@@ -309,6 +422,96 @@ impl PrettyDump for DsArg {
     }
 }
 
+impl PrettyDump for DsNamedArg {
+    fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
+        let DsNamedArg {
+            name,
+            name_loc,
+            value,
+            loc,
+        } = self;
+
+        ctx.pretty_struct("NamedArg")
+            .field("name", (name, name_loc))
+            .field("value", value)
+            .finish()?;
+        ctx.print_loc(loc)?;
+
+        Ok(())
+    }
+}
+
+impl PrettyDump for DsStructField {
+    fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
+        let DsStructField {
+            name,
+            name_loc,
+            typexpr,
+            loc,
+        } = self;
+
+        ctx.pretty_struct("StructField")
+            .field("name", (name, name_loc))
+            .field("typexpr", typexpr)
+            .finish()?;
+        ctx.print_loc(loc)?;
+
+        Ok(())
+    }
+}
+
+impl PrettyDump for DsEnumVariant {
+    fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
+        let DsEnumVariant {
+            name,
+            name_loc,
+            value,
+            loc,
+        } = self;
+
+        ctx.pretty_struct("EnumVariant")
+            .field("name", (name, name_loc))
+            .field("value", value)
+            .finish()?;
+        ctx.print_loc(loc)?;
+
+        Ok(())
+    }
+}
+
+impl PrettyDump for DsMatchArm {
+    fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
+        let DsMatchArm { pats, body, loc } = self;
+
+        ctx.pretty_struct("MatchArm")
+            .field("pats", pats.as_slice())
+            .field("body", body)
+            .finish()?;
+        ctx.print_loc(loc)?;
+
+        Ok(())
+    }
+}
+
+impl PrettyDump for DsFieldValue {
+    fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
+        let DsFieldValue {
+            name,
+            name_loc,
+            value,
+            loc,
+        } = self;
+
+        ctx.pretty_struct("FieldValue")
+            .field("name", (name, name_loc))
+            .field("value", value)
+            .finish()?;
+        ctx.print_loc(loc)?;
+
+        Ok(())
+    }
+}
+
 impl PrettyDump for DsBlock {
     fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
         struct LastExpr<'a>(&'a Option<Box<DsExpression>>);
@@ -371,6 +574,12 @@ impl PrettyDump for DsStmt {
                 expr.try_dump(ctx)?;
                 Ok(())
             }
+            DsStmt::BoundsCheck(expr) => {
+                ctx.pretty_struct("BoundsCheck")
+                    .field("expr", expr)
+                    .finish()?;
+                Ok(())
+            }
         }
     }
 }
@@ -55,6 +55,30 @@ impl ToDiagnostic for TooLargeIntegerLiteral {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct FloatLiteralOutOfRange {
+    /// is the literal too large, or too small to not be rounded to zero?
+    pub too_large: bool,
+    /// location of the float literal
+    pub loc: Span,
+}
+
+impl ToDiagnostic for FloatLiteralOutOfRange {
+    fn into_diag(self) -> Diagnostic {
+        let note = if self.too_large {
+            format!("float exceeds the limit of `{:e}`", f64::MAX)
+        } else {
+            "float is so small that it would be rounded to zero".to_string()
+        };
+
+        Diagnostic::error()
+            .with_code(ErrorCode::FloatLiteralOutOfRange)
+            .with_message("float literal is out of range")
+            .with_label(Label::primary(self.loc.fid, self.loc))
+            .with_note(note)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct UnterminatedStringLiteral {
     /// location of the unterminated string literal
@@ -70,6 +94,21 @@ impl ToDiagnostic for UnterminatedStringLiteral {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct UnterminatedCharLiteral {
+    /// location of the unterminated character literal
+    pub loc: Span,
+}
+
+impl ToDiagnostic for UnterminatedCharLiteral {
+    fn into_diag(self) -> Diagnostic {
+        Diagnostic::error()
+            .with_code(ErrorCode::UnterminatedStringLiteral)
+            .with_message("unterminated character literal")
+            .with_label(Label::primary(self.loc.fid, self.loc))
+    }
+}
+
 // TODO: add location of the string literal or the char literal the error is in.
 #[derive(Debug, Clone)]
 pub struct UnknownCharacterEscape {
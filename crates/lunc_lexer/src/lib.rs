@@ -4,10 +4,10 @@
 )]
 
 use diags::{
-    EmptyCharLiteral, ExpectedExponentPart, InvalidDigitInNumber, InvalidUnicodeEscape,
-    InvalidUnicodeNote, NoDigitsInANonDecimal, NotEnoughHexDigits, TooLargeIntegerLiteral,
-    TooManyCodepointsInCharLiteral, UnknownCharacterEscape, UnknownToken,
-    UnterminatedStringLiteral,
+    EmptyCharLiteral, ExpectedExponentPart, FloatLiteralOutOfRange, InvalidDigitInNumber,
+    InvalidUnicodeEscape, InvalidUnicodeNote, NoDigitsInANonDecimal, NotEnoughHexDigits,
+    TooLargeIntegerLiteral, TooManyCodepointsInCharLiteral, UnknownCharacterEscape, UnknownToken,
+    UnterminatedCharLiteral, UnterminatedStringLiteral,
 };
 use lunc_diag::{Diagnostic, DiagnosticSink, FileId, ReachedEOF};
 
@@ -32,6 +32,8 @@ pub struct Lexer {
     sink: DiagnosticSink,
     /// file id of the file we are lexing
     fid: FileId,
+    /// did we skip a comment?
+    had_comments: bool,
 }
 
 impl Lexer {
@@ -43,6 +45,7 @@ impl Lexer {
             head: LexHead::new(),
             sink,
             fid,
+            had_comments: false,
         }
     }
 
@@ -76,6 +79,11 @@ impl Lexer {
         Some(tt)
     }
 
+    /// Did the lexer skip a comment? They are not part of the token stream.
+    pub fn had_comments(&self) -> bool {
+        self.had_comments
+    }
+
     /// return the char that is n-chars offsetted
     pub fn peek_nth(&self, offset: isize) -> Option<char> {
         self.chars
@@ -112,6 +120,14 @@ impl Lexer {
         }
     }
 
+    /// Does the rest of the current line contains the character `c`?
+    pub fn line_contains(&self, c: char) -> bool {
+        self.chars[self.head.cur_chars()..]
+            .iter()
+            .take_while(|&&ch| ch != '\n')
+            .any(|&ch| ch == c)
+    }
+
     pub fn lex_until(&mut self, stopper: char) -> String {
         // TODO: instead of reconstructing the String character by character we
         // could find where the closest character that is a stopper is and
@@ -169,6 +185,10 @@ impl Lexer {
                         self.pop();
                         return Ok(Punct(Equal2));
                     }
+                    Some('>') => {
+                        self.pop();
+                        return Ok(Punct(EqualGt));
+                    }
                     _ => return Ok(Punct(Equal)),
                 }
             }
@@ -217,12 +237,14 @@ impl Lexer {
                     Some('/') => {
                         // start of a line comment
                         self.pop();
+                        self.had_comments = true;
                         self.lex_until('\n');
                         return Ok(TokenType::__NotAToken__);
                     }
                     Some('*') => {
                         // start of multiline comment
                         self.pop();
+                        self.had_comments = true;
 
                         loop {
                             match (self.peek(), self.peek_nth(1)) {
@@ -249,6 +271,20 @@ impl Lexer {
                         self.pop();
                         return Ok(Punct(DotStar));
                     }
+                    Some('.') => {
+                        self.pop();
+                        match self.peek() {
+                            Some('<') => {
+                                self.pop();
+                                return Ok(Punct(Dot2Lt));
+                            }
+                            Some('=') => {
+                                self.pop();
+                                return Ok(Punct(Dot2Equal));
+                            }
+                            _ => return Ok(Punct(Dot2)),
+                        }
+                    }
                     _ => return Ok(Punct(Dot)),
                 }
             }
@@ -318,6 +354,7 @@ impl Lexer {
                     Keyword::CONTINUE => Kw(Keyword::Continue),
                     Keyword::DEFER => Kw(Keyword::Defer),
                     Keyword::ELSE => Kw(Keyword::Else),
+                    Keyword::ENUM => Kw(Keyword::Enum),
                     Keyword::EXTERN => Kw(Keyword::Extern),
                     Keyword::FALSE => Kw(Keyword::False),
                     Keyword::FOR => Kw(Keyword::For),
@@ -327,6 +364,7 @@ impl Lexer {
                     Keyword::IN => Kw(Keyword::In),
                     Keyword::LET => Kw(Keyword::Let),
                     Keyword::LOOP => Kw(Keyword::Loop),
+                    Keyword::MATCH => Kw(Keyword::Match),
                     Keyword::MUT => Kw(Keyword::Mut),
                     Keyword::NULL => Kw(Keyword::Null),
                     Keyword::OR => Kw(Keyword::Or),
@@ -334,6 +372,7 @@ impl Lexer {
                     Keyword::PUB => Kw(Keyword::Pub),
                     Keyword::RETURN => Kw(Keyword::Return),
                     Keyword::SELF => Kw(Keyword::SelfVal),
+                    Keyword::STRUCT => Kw(Keyword::Struct),
                     Keyword::THEN => Kw(Keyword::Then),
                     Keyword::TRAIT => Kw(Keyword::Trait),
                     Keyword::TRUE => Kw(Keyword::True),
@@ -390,8 +429,37 @@ impl Lexer {
     }
 
     pub fn lex_number(&mut self) -> Result<TokenType, Diagnostic> {
+        // NOTE: a number right after a single dot is a tuple index, so that
+        // `t.0.1` is lexed as `t` `.` `0` `.` `1` and not as `t` `.` `0.1`
+        if self.peek_nth(-1) == Some('.') && self.peek_nth(-2) != Some('.') {
+            let int_str = self.lex_decimal();
+
+            return Ok(TokenType::IntLit(self.parse_u128(&int_str, 10)?));
+        }
+
         let number = self.lex_number_internal()?;
 
+        // integer suffix, `12u8`, `0xFFi64`
+        if let TokenType::IntLit(int) = number
+            && matches!(self.peek(), Some('i' | 'u'))
+        {
+            return Ok(TokenType::SpecializedIntLit {
+                specialization: self.lex_word(),
+                int,
+            });
+        }
+
+        // float suffix, `2.5f32`
+        if let TokenType::FloatLit(float) = number
+            && self.peek() == Some('f')
+            && matches!(self.peek_nth(1), Some('0'..='9'))
+        {
+            return Ok(TokenType::SpecializedFloatLit {
+                specialization: self.lex_word(),
+                float,
+            });
+        }
+
         match self.peek() {
             Some('\'') => {
                 self.pop();
@@ -459,7 +527,7 @@ impl Lexer {
                 };
 
                 match self.peek() {
-                    Some('.') => {
+                    Some('.') if self.peek_nth(1) != Some('.') => {
                         self.pop();
 
                         let (frac_part, frac_divisor) = match self.peek() {
@@ -507,8 +575,10 @@ impl Lexer {
 
                                 let exp_str = self.lex_decimal();
 
+                                // NOTE: an exponent too large for an `i32`
+                                // saturates, the literal is then out of range.
                                 let exp = match self.parse_u128(&exp_str, 10) {
-                                    Ok(e) => e as i32,
+                                    Ok(e) => i32::try_from(e).unwrap_or(i32::MAX),
                                     Err(d) => {
                                         self.sink.emit(d);
                                         0
@@ -539,6 +609,7 @@ impl Lexer {
                         let base = int_f64 + frac_f64;
 
                         let float = base * 2.0f64.powi(exp_value);
+                        self.ck_float_range(float, int_part != 0 || frac_part != 0);
 
                         return Ok(TokenType::FloatLit(float));
                     }
@@ -573,7 +644,7 @@ impl Lexer {
 
                         let exp_value = sign
                             * match self.parse_u128(&exp_str, 10) {
-                                Ok(e) => e as i32,
+                                Ok(e) => i32::try_from(e).unwrap_or(i32::MAX),
                                 Err(d) => {
                                     self.sink.emit(d);
                                     0
@@ -582,6 +653,7 @@ impl Lexer {
 
                         let int_f64 = int_part as f64;
                         let float = int_f64 * 2.0f64.powi(exp_value);
+                        self.ck_float_range(float, int_part != 0);
 
                         return Ok(TokenType::FloatLit(float));
                     }
@@ -595,95 +667,159 @@ impl Lexer {
             }
             _ => 10,
         };
-        let int_str = self.lex_word();
+        let mut int_str = if radix == 10 {
+            self.lex_decimal()
+        } else {
+            self.lex_int_digits()
+        };
+
+        if radix == 10 && !self.is_float_continuation() {
+            // NOTE: we lex the rest of the word so that invalid digits are
+            // reported as part of the number
+            int_str.push_str(&self.lex_int_digits());
+        }
 
         if int_str.is_empty() {
             self.sink.emit(NoDigitsInANonDecimal { loc: self.loc() });
         }
 
-        let int_part = self.parse_u128(&int_str, radix)?;
-
         match self.peek() {
-            Some('.') if radix == 10 => {
+            // NOTE: `1..` is a range, not the float `1.` followed by a dot.
+            Some('.') if radix == 10 && self.peek_nth(1) != Some('.') => {
                 // Decimal floating point number grammar:
                 //
                 // float_lit = decimal_float_lit | hex_float_lit ;
                 // decimal_float_lit = decimal_digits "." [decimal_digits] [ decimal_exponent ]
-                //   | decimal_digits decimal_exponent
-                //   | "." decimal_digits [decimal_exponent] ;
+                //   | decimal_digits decimal_exponent ;
                 // decimal_exponent = ("e" | "E") ["+" | "-"] decimal_digits ;
+                //
+                // NOTE: `1.` is a float but `.5` is not, the leading dot is
+                // lexed as a punctuation.
                 self.pop();
 
-                let (frac_part, frac_divisor) = match self.peek() {
-                    Some('0'..='9') => {
-                        let frac_str = self.lex_decimal();
-
-                        match self.parse_u128_with_digit_count(&frac_str, 10) {
-                            Ok((f, n)) => (f, n as i32),
-                            Err(d) => {
-                                // NOTE: we are not using ? to propagate the diag, we just use
-                                // a poisoned value
-                                self.sink.emit(d);
-                                (0, 0)
-                            }
-                        }
-                    }
-                    _ => (0, 0),
+                let frac_str = match self.peek() {
+                    Some('0'..='9') => self.lex_decimal(),
+                    _ => String::new(),
                 };
 
-                let exp_value = match self.peek() {
-                    Some('e' | 'E') => {
-                        self.pop();
-                        let sign = match self.peek() {
-                            Some('-') => {
-                                self.pop();
-                                -1i32
-                            }
-                            Some('+') => {
-                                self.pop();
-                                1
-                            }
-                            Some('_' | '0'..='9') => 1,
-                            Some(c) => {
-                                self.sink.emit(InvalidDigitInNumber {
-                                    c,
-                                    loc_c: self.loc_current_char(),
-                                    loc_i: self.loc(),
-                                });
-                                1
-                            }
-                            _ => {
-                                self.sink.emit(ReachedEOF { loc: self.loc() });
-                                1
-                            }
-                        };
+                let exp_str = match self.peek() {
+                    Some('e' | 'E') => self.lex_decimal_exponent(),
+                    _ => String::from("0"),
+                };
 
-                        let exp_str = self.lex_decimal();
+                Ok(TokenType::FloatLit(
+                    self.parse_decimal_float(&int_str, &frac_str, &exp_str),
+                ))
+            }
+            Some('e' | 'E') if radix == 10 => {
+                let exp_str = self.lex_decimal_exponent();
 
-                        let exp = match self.parse_u128(&exp_str, 10) {
-                            Ok(e) => e as i32,
-                            Err(d) => {
-                                self.sink.emit(d);
-                                0
-                            }
-                        };
+                Ok(TokenType::FloatLit(
+                    self.parse_decimal_float(&int_str, "", &exp_str),
+                ))
+            }
+            _ => Ok(TokenType::IntLit(self.parse_u128(&int_str, radix)?)),
+        }
+    }
 
-                        sign * exp
-                    }
-                    _ => 0,
-                };
+    /// Parses the parts of a decimal float literal into the nearest `f64`,
+    /// the digits may contain underscores.
+    fn parse_decimal_float(&mut self, int_str: &str, frac_str: &str, exp_str: &str) -> f64 {
+        let digits = |s: &str| s.chars().filter(|c| *c != '_').collect::<String>();
+        let (int_digits, frac_digits) = (digits(int_str), digits(frac_str));
+
+        let float = format!("{int_digits}.{frac_digits}e{exp_str}")
+            .parse::<f64>()
+            .expect("the float literal is made of decimal digits");
+
+        let is_nonzero = int_digits
+            .chars()
+            .chain(frac_digits.chars())
+            .any(|c| c != '0');
+        self.ck_float_range(float, is_nonzero);
+
+        float
+    }
 
-                let int_f64 = int_part as f64;
-                let frac_f64 = frac_part as f64 * 10f64.powi(-frac_divisor);
+    /// Reports the float literal being lexed if it is infinite, or rounded to
+    /// zero while its digits are not all zeros.
+    fn ck_float_range(&mut self, float: f64, is_nonzero: bool) {
+        if float.is_infinite() || (float == 0.0 && is_nonzero) {
+            self.sink.emit(FloatLiteralOutOfRange {
+                too_large: float.is_infinite(),
+                loc: self.loc(),
+            });
+        }
+    }
+
+    /// Lexes the digits of an integer, like [`lex_word`] but stops at the start
+    /// of an integer suffix, `i` or `u`, because they are not digits in any
+    /// base.
+    ///
+    /// [`lex_word`]: Self::lex_word
+    fn lex_int_digits(&mut self) -> String {
+        let mut digits = String::new();
+
+        while let Some(c @ ('A'..='Z' | 'a'..='z' | '_' | '0'..='9')) = self.peek()
+            && c != 'i'
+            && c != 'u'
+        {
+            digits.push(c);
+            self.pop();
+        }
 
-                let base = int_f64 + frac_f64;
+        digits
+    }
+
+    /// Is the character after the digits of a decimal number the start of the
+    /// fractional part or of the exponent of a float?
+    fn is_float_continuation(&self) -> bool {
+        match (self.peek(), self.peek_nth(1)) {
+            // NOTE: `1..` is a range, not the float `1.` followed by a dot.
+            (Some('.'), next) => next != Some('.'),
+            (Some('e' | 'E'), Some('+' | '-' | '0'..='9')) => true,
+            _ => false,
+        }
+    }
 
-                let float = base * 10.0f64.powi(exp_value);
+    /// Lexes the exponent of a decimal float, the `e` / `E` has not been
+    /// popped yet. Returns the signed digits of the exponent, like `-27`.
+    ///
+    /// decimal_exponent = ("e" | "E") ["+" | "-"] decimal_digits ;
+    fn lex_decimal_exponent(&mut self) -> String {
+        self.pop(); // e / E
 
-                Ok(TokenType::FloatLit(float))
+        let sign = match self.peek() {
+            Some('-') => {
+                self.pop();
+                "-"
+            }
+            Some('+') => {
+                self.pop();
+                ""
+            }
+            Some('_' | '0'..='9') => "",
+            Some(c) => {
+                self.sink.emit(InvalidDigitInNumber {
+                    c,
+                    loc_c: self.loc_current_char(),
+                    loc_i: self.loc(),
+                });
+                ""
+            }
+            _ => {
+                self.sink.emit(ReachedEOF { loc: self.loc() });
+                ""
             }
-            _ => Ok(TokenType::IntLit(int_part)),
+        };
+
+        let digits: String = self.lex_decimal().chars().filter(|c| *c != '_').collect();
+
+        if digits.is_empty() {
+            return String::from("0");
         }
+
+        format!("{sign}{digits}")
     }
 
     pub fn lex_string(&mut self) -> Result<TokenType, Diagnostic> {
@@ -699,6 +835,10 @@ impl Lexer {
         // pop the first "
         self.pop();
 
+        if self.peek() == Some('"') && self.peek_nth(1) == Some('"') {
+            return self.lex_multiline_string(support_escape);
+        }
+
         loop {
             match self.peek() {
                 Some('"') => {
@@ -708,9 +848,12 @@ impl Lexer {
                 Some('\\') if support_escape => {
                     self.pop();
 
-                    let es = match self.pop() {
-                        Some(es) => es,
-                        None => continue,
+                    let es = match self.peek() {
+                        Some('\n') | None => continue,
+                        Some(es) => {
+                            self.pop();
+                            es
+                        }
                     };
 
                     if es == '"' {
@@ -727,21 +870,188 @@ impl Lexer {
                         }
                     }
                 }
+                // NOTE: a string literal can't span multiple lines, we stop
+                // at the end of the line so that the rest of the file is
+                // still lexed.
+                Some('\n') | None => {
+                    self.sink
+                        .emit(UnterminatedStringLiteral { loc: self.loc() });
+                    break;
+                }
                 Some(c) => {
                     str.push(c);
                     self.pop();
                 }
-                _ => {
-                    self.sink
-                        .emit(UnterminatedStringLiteral { loc: self.loc() });
+            }
+        }
+
+        Ok(TokenType::StringLit(str))
+    }
+
+    /// Lexes a multi-line string literal, the first `"` is already popped.
+    ///
+    /// ```text
+    /// multiline_string_lit = '"""' { char } '"""' ;
+    /// ```
+    ///
+    /// The newline right after the opening `"""` is not part of the string,
+    /// neither is the newline before the closing `"""` if it is alone on its
+    /// line. The indentation of the closing `"""` is stripped from the start of
+    /// every line of the string, e.g:
+    ///
+    /// ```lun
+    /// s :: """
+    ///     hello
+    ///       world
+    ///     """;
+    /// ```
+    ///
+    /// is the string `"hello\n  world"`.
+    pub fn lex_multiline_string(&mut self, support_escape: bool) -> Result<TokenType, Diagnostic> {
+        let mut str = String::new();
+
+        // pop the two remaining "
+        self.pop();
+        self.pop();
+
+        // find the closing """
+        let start = self.head.cur_chars();
+        let mut close = None;
+        let mut i = start;
+
+        while i + 2 < self.chars.len() {
+            match self.chars[i] {
+                '\\' if support_escape => i += 2,
+                '"' if self.chars[i + 1] == '"' && self.chars[i + 2] == '"' => {
+                    close = Some(i);
                     break;
                 }
+                _ => i += 1,
+            }
+        }
+
+        let Some(close) = close else {
+            self.sink
+                .emit(UnterminatedStringLiteral { loc: self.loc() });
+
+            // the literal ends before the next item so that the rest of the
+            // file is still lexed
+            let end = self.multiline_string_recovery(start);
+
+            while self.head.cur_chars() < end {
+                str.extend(self.pop());
+            }
+
+            return Ok(TokenType::StringLit(str));
+        };
+
+        // the indentation of the closing """ and where the content ends
+        let closing_line = self.chars[start..close]
+            .iter()
+            .rposition(|&c| c == '\n')
+            .map(|nl| start + nl);
+
+        let (indent, content_end) = match closing_line {
+            Some(nl)
+                if self.chars[nl + 1..close]
+                    .iter()
+                    .all(|&c| c == ' ' || c == '\t') =>
+            {
+                (close - nl - 1, nl)
+            }
+            _ => (0, close),
+        };
+
+        if self.peek() == Some('\n') {
+            self.pop();
+            self.skip_indent(indent);
+        }
+
+        while self.head.cur_chars() < content_end {
+            match self.peek() {
+                Some('\\') if support_escape => {
+                    self.pop();
+
+                    let Some(es) = self.pop() else {
+                        break;
+                    };
+
+                    if es == '"' {
+                        str.push(es);
+                        continue;
+                    }
+
+                    match self.lex_escape_sequence(es, true) {
+                        Ok(c) => str.push(c),
+                        Err(d) => self.sink.emit(d),
+                    }
+                }
+                Some('\n') => {
+                    str.push('\n');
+                    self.pop();
+                    self.skip_indent(indent);
+                }
+                Some(c) => {
+                    str.push(c);
+                    self.pop();
+                }
+                None => break,
             }
         }
 
+        // pop the end of the content and the closing """
+        while self.head.cur_chars() < close + 3 {
+            self.pop();
+        }
+
         Ok(TokenType::StringLit(str))
     }
 
+    /// Returns the start of the first line after `start` that looks like the
+    /// start of an item, a line that isn't indented and starts with `pub`,
+    /// `impl`, `extern`, `}` or an identifier followed by `:`. Returns the end
+    /// of the input if there is none.
+    fn multiline_string_recovery(&self, start: usize) -> usize {
+        let mut i = start;
+
+        while let Some(nl) = self.chars[i..].iter().position(|&c| c == '\n') {
+            i += nl + 1;
+
+            let word_len = self.chars[i..]
+                .iter()
+                .take_while(|c| matches!(c, 'A'..='Z' | 'a'..='z' | '_' | '0'..='9'))
+                .count();
+            let word: String = self.chars[i..i + word_len].iter().collect();
+            let after = self.chars[i + word_len..]
+                .iter()
+                .find(|&&c| c != ' ' && c != '\t');
+
+            let is_item = match word.as_str() {
+                "" => self.chars.get(i) == Some(&'}'),
+                "pub" | "impl" | "extern" => true,
+                w => !w.starts_with(|c: char| c.is_ascii_digit()) && after == Some(&':'),
+            };
+
+            if is_item {
+                return i;
+            }
+        }
+
+        self.chars.len()
+    }
+
+    /// Skips at most `indent` spaces or tabs.
+    fn skip_indent(&mut self, indent: usize) {
+        for _ in 0..indent {
+            match self.peek() {
+                Some(' ' | '\t') => {
+                    self.pop();
+                }
+                _ => break,
+            }
+        }
+    }
+
     pub fn lex_char(&mut self) -> Result<TokenType, Diagnostic> {
         self.expect('\'');
 
@@ -750,11 +1060,14 @@ impl Lexer {
             Some('\\') => {
                 self.pop();
 
-                let es = match self.pop() {
-                    Some(es) => es,
-                    None => {
-                        self.sink.emit(ReachedEOF { loc: self.loc() });
-                        char::default()
+                let es = match self.peek() {
+                    Some('\n') | None => {
+                        self.sink.emit(UnterminatedCharLiteral { loc: self.loc() });
+                        return Ok(TokenType::CharLit(char::default()));
+                    }
+                    Some(es) => {
+                        self.pop();
+                        es
                     }
                 };
 
@@ -776,14 +1089,14 @@ impl Lexer {
                 empty_char = true;
                 char::default()
             }
+            Some('\n') | None => {
+                self.sink.emit(UnterminatedCharLiteral { loc: self.loc() });
+                return Ok(TokenType::CharLit(char::default()));
+            }
             Some(c) => {
                 self.pop();
                 c
             }
-            None => {
-                self.sink.emit(ReachedEOF { loc: self.loc() });
-                char::default()
-            }
         };
 
         if !empty_char {
@@ -791,14 +1104,14 @@ impl Lexer {
                 Some('\'') => {
                     self.pop();
                 }
-                Some(_) => {
+                Some(_) if self.line_contains('\'') => {
                     self.lex_until('\'');
                     self.pop(); // '
                     self.sink
                         .emit(TooManyCodepointsInCharLiteral { loc: self.loc() });
                 }
-                None => {
-                    self.sink.emit(ReachedEOF { loc: self.loc() });
+                _ => {
+                    self.sink.emit(UnterminatedCharLiteral { loc: self.loc() });
                 }
             }
         }
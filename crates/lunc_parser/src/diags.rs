@@ -8,7 +8,7 @@ use lunc_utils::{
 
 use std::fmt::Display;
 
-use crate::directive::Directive;
+use crate::{directive::Directive, expr::IntSuffix};
 
 pub struct ExpectedToken {
     /// what token was expected?
@@ -136,3 +136,146 @@ impl ToDiagnostic for UnknownAbi {
             .with_label(Label::primary(self.loc.fid, self.loc))
     }
 }
+
+#[derive(Debug, Clone)]
+pub struct StatementAtTopLevel {
+    /// the token that starts the statement
+    pub found: TokenType,
+    /// location of the found token
+    pub loc: Span,
+}
+
+impl ToDiagnostic for StatementAtTopLevel {
+    fn into_diag(self) -> Diagnostic {
+        Diagnostic::error()
+            .with_code(ErrorCode::ExpectedToken)
+            .with_message(format!("expected item, found {}", self.found))
+            .with_label(
+                Label::primary(self.loc.fid, self.loc)
+                    .with_message("statements are only allowed inside of a function body"),
+            )
+            .with_note(
+                "top-level definitions are written `name :: value`, e.g. `main :: fun() { ... }`",
+            )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ChainedRange {
+    /// location of the first range expression
+    pub range_loc: Span,
+    /// location of the chained range operator
+    pub loc: Span,
+}
+
+impl ToDiagnostic for ChainedRange {
+    fn into_diag(self) -> Diagnostic {
+        Diagnostic::error()
+            .with_code(ErrorCode::ExpectedToken)
+            .with_message("range operators cannot be chained")
+            .with_label(Label::primary(self.loc.fid, self.loc))
+            .with_label(
+                Label::secondary(self.range_loc.fid, self.range_loc)
+                    .with_message("this is already a range"),
+            )
+            .with_note("use parentheses to clarify the bounds of the ranges")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PositionalAfterNamedArg {
+    /// location of the last named argument before the positional one
+    pub named_loc: Span,
+    /// location of the positional argument
+    pub loc: Span,
+}
+
+impl ToDiagnostic for PositionalAfterNamedArg {
+    fn into_diag(self) -> Diagnostic {
+        Diagnostic::error()
+            .with_code(ErrorCode::PositionalAfterNamedArg)
+            .with_message("positional argument after a named argument")
+            .with_label(Label::primary(self.loc.fid, self.loc))
+            .with_label(
+                Label::secondary(self.named_loc.fid, self.named_loc)
+                    .with_message("named argument here"),
+            )
+            .with_note("positional arguments must come before the named arguments")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UnknownLiteralSpecialization {
+    /// the kind of specialization, `"prefix"` or `"suffix"`
+    pub kind: &'static str,
+    /// the unknown specialization
+    pub s12n: String,
+    /// the specializations that are valid for this literal
+    pub expected: &'static [&'static str],
+    /// location of the literal
+    pub loc: Span,
+}
+
+impl ToDiagnostic for UnknownLiteralSpecialization {
+    fn into_diag(self) -> Diagnostic {
+        let suggestion = suggest(&self.s12n, self.expected, DEFAULT_MAX_LEVENSHTEIN_DISTANCE);
+        let expected: Vec<String> = self.expected.iter().map(|s| format!("'{s}'")).collect();
+
+        Diagnostic::error()
+            .with_code(ErrorCode::UnknownLiteralSpecialization)
+            .with_message(format!("unknown literal {} '{}'", self.kind, self.s12n))
+            .with_label(Label::primary(self.loc.fid, self.loc))
+            .with_note(match expected.as_slice() {
+                [one] => format!("expected {one}"),
+                _ => format!("expected one of {}", list_fmt(&expected)),
+            })
+            .with_notes_iter(suggestion.map(|suggested| format!("did you mean '{suggested}'?")))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OverflowingSuffixedLiteral {
+    /// the integer literal
+    pub int: u128,
+    /// the suffix of the literal
+    pub suffix: IntSuffix,
+    /// maximum value of the type of the suffix
+    pub max: u128,
+    /// location of the literal
+    pub loc: Span,
+}
+
+impl ToDiagnostic for OverflowingSuffixedLiteral {
+    fn into_diag(self) -> Diagnostic {
+        Diagnostic::error()
+            .with_code(ErrorCode::OverflowingLiteral)
+            .with_message("literal out of range")
+            .with_label(Label::primary(self.loc.fid, self.loc))
+            .with_note(format!(
+                "the literal (of value {}) does not fit in the type '{}'",
+                self.int, self.suffix
+            ))
+            .with_note(format!("the maximum value for this type is '{}'", self.max))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ExpressionTooDeeplyNested {
+    /// the maximum nesting depth of an expression
+    pub max: usize,
+    /// location of the expression that goes over the limit
+    pub loc: Span,
+}
+
+impl ToDiagnostic for ExpressionTooDeeplyNested {
+    fn into_diag(self) -> Diagnostic {
+        Diagnostic::error()
+            .with_code(ErrorCode::ExpressionTooDeeplyNested)
+            .with_message("expression too deeply nested")
+            .with_label(Label::primary(self.loc.fid, self.loc))
+            .with_note(format!(
+                "an expression can't be nested more than {} times",
+                self.max
+            ))
+    }
+}
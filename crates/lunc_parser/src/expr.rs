@@ -1,6 +1,6 @@
 //! Parsing of lun's expressions.
 
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
 
 use lunc_utils::opt_unreachable;
 
@@ -35,6 +35,19 @@ impl Expression {
                 | Expr::IteratorLoop { .. }
                 | Expr::FunDefinition { .. }
                 | Expr::InfiniteLoop { .. }
+                | Expr::Struct { .. }
+                | Expr::Enum { .. }
+                | Expr::Match { .. }
+        ) || matches!(&self.expr, Expr::Comptime(expr) if expr.is_expr_with_block())
+    }
+
+    /// Can the expression be the type of a struct literal? Only paths and
+    /// calls, like `Point` or `Vec(T)` are allowed to not be confused with a
+    /// block after an expression.
+    pub fn is_struct_lit_typexpr(&self) -> bool {
+        matches!(
+            self.expr,
+            Expr::Ident(_) | Expr::MemberAccess { .. } | Expr::FunCall { .. }
         )
     }
 }
@@ -71,8 +84,8 @@ impl AstNode for Expression {
 pub enum Expr {
     /// integer literal expression
     ///
-    /// `integer`
-    IntLit(u128),
+    /// `integer [int_suffix]`
+    IntLit(u128, Option<IntSuffix>),
     /// boolean literal expression
     ///
     /// `"true" | "false"`
@@ -87,12 +100,16 @@ pub enum Expr {
     CharLit(char),
     /// float literal expression
     ///
-    /// `float`
-    FloatLit(f64),
+    /// `float [float_suffix]`
+    FloatLit(f64, Option<FloatSuffix>),
     /// grouping expression (just parenthesis)
     ///
     /// `"(" expr ")"`
     Grouping(Box<Expression>),
+    /// tuple expression, `()` is the empty tuple, the value of `void`
+    ///
+    /// `"(" ")" | "(" expr "," ( expr ),* ")"`
+    Tuple(Vec<Expression>),
     /// an identifier expression
     ///
     /// `ident`
@@ -109,6 +126,13 @@ pub enum Expr {
     ///
     /// `op expr`
     Unary { op: UnaryOp, expr: Box<Expression> },
+    /// cast expression, converts the value of an expression to another type
+    ///
+    /// `expr "as" expr`
+    Cast {
+        expr: Box<Expression>,
+        typexpr: Box<Expression>,
+    },
     /// Borrow operator
     ///
     /// `"&" "mut"? expression`
@@ -116,12 +140,21 @@ pub enum Expr {
         mutable: bool,
         expr: Box<Expression>,
     },
+    /// range expression
+    ///
+    /// `expr? ".." | expr? "..<" expr | expr? "..=" expr`
+    Range {
+        start: Option<Box<Expression>>,
+        end: Option<Box<Expression>>,
+        inclusive: bool,
+    },
     /// function call expression
     ///
-    /// `expr "(" ( expr ),* ")"`
+    /// `expr "(" ( expr ),* ( ident ":" expr ),* ")"`
     FunCall {
         callee: Box<Expression>,
         args: Vec<Expression>,
+        named_args: Vec<NamedArg>,
     },
     /// if else expression
     ///
@@ -154,6 +187,7 @@ pub enum Expr {
     IteratorLoop {
         label: Option<(String, Span)>,
         variable: String,
+        variable_loc: Span,
         iterator: Box<Expression>,
         body: Block,
         // NOTE: this is used to emit the diagnostic `feature_todo`.
@@ -185,17 +219,35 @@ pub enum Expr {
     ///
     /// `"null"`
     Null,
+    /// comptime expression, evaluated during semantic checking
+    ///
+    /// `"comptime" expr`
+    Comptime(Box<Expression>),
     /// member access expression
     ///
-    /// `expr "." ident`
+    /// `expr "." ( ident | int )`
     MemberAccess {
         expr: Box<Expression>,
         member: String,
     },
+    /// struct literal expression
+    ///
+    /// `expr "{" ( ident ":" expr ),* "}"`
+    StructLit {
+        typexpr: Box<Expression>,
+        fields: Vec<FieldValue>,
+    },
     /// orb expression
     ///
     /// `"orb"`
     Orb,
+    /// match expression
+    ///
+    /// `"match" expr "{" ( expr ( "|" expr )* "=>" expr ),* "}"`
+    Match {
+        expr: Box<Expression>,
+        arms: Vec<MatchArm>,
+    },
     //
     // definitions
     //
@@ -224,13 +276,21 @@ pub enum Expr {
         mutable: bool,
         typexpr: Box<Expression>,
     },
-    /// function pointer type
+    /// function pointer type, the `*` can be omitted in type position
     ///
     /// `"*" "fun" "(" ( expr ),* ")" [ "->" expr ]`
     FunPtrType {
         args: Vec<Expression>,
         ret: Option<Box<Expression>>,
     },
+    /// struct type expression
+    ///
+    /// `"struct" "{" ( ident ":" expr ),* "}"`
+    Struct { fields: Vec<StructField> },
+    /// enum type expression
+    ///
+    /// `"enum" "{" ( ident [ "=" expr ] ),* "}"`
+    Enum { variants: Vec<EnumVariant> },
 }
 
 #[derive(Debug, Clone)]
@@ -247,11 +307,63 @@ pub enum Else {
     Block(Block),
 }
 
+/// An argument of a function definition,
+/// `"comptime"? ident ":" expr ( "=" expr )?`
 #[derive(Debug, Clone)]
 pub struct Arg {
+    /// is the argument a `comptime` parameter, its value must be known at
+    /// compile-time
+    pub comptime: bool,
     pub name: String,
     pub name_loc: Span,
     pub typexpr: Expression,
+    /// the default value of the argument, used when a call omits it
+    pub default: Option<Expression>,
+    pub loc: Span,
+}
+
+/// A named argument of a function call, `ident ":" expr`
+#[derive(Debug, Clone)]
+pub struct NamedArg {
+    pub name: String,
+    pub name_loc: Span,
+    pub value: Expression,
+    pub loc: Span,
+}
+
+/// A field of a struct type expression, `ident ":" expr`
+#[derive(Debug, Clone)]
+pub struct StructField {
+    pub name: String,
+    pub name_loc: Span,
+    pub typexpr: Expression,
+    pub loc: Span,
+}
+
+/// A variant of an enum type expression, `ident [ "=" expr ]`
+#[derive(Debug, Clone)]
+pub struct EnumVariant {
+    pub name: String,
+    pub name_loc: Span,
+    /// the explicit discriminant of the variant
+    pub value: Option<Expression>,
+    pub loc: Span,
+}
+
+/// An arm of a match expression, `expr ( "|" expr )* "=>" expr`
+#[derive(Debug, Clone)]
+pub struct MatchArm {
+    pub pats: Vec<Expression>,
+    pub body: Expression,
+    pub loc: Span,
+}
+
+/// A field of a struct literal, `ident ":" expr`
+#[derive(Debug, Clone)]
+pub struct FieldValue {
+    pub name: String,
+    pub name_loc: Span,
+    pub value: Expression,
     pub loc: Span,
 }
 
@@ -259,19 +371,42 @@ pub struct Arg {
 ///
 /// `typexpr` when set to true, it will parse with some constraints described in
 /// [`parse_typexpr`].
+///
+/// Every expression parsed inside of this one goes through this function, so
+/// it is also where the nesting depth is limited, see [`MAX_EXPR_DEPTH`].
 pub fn parse_expr_precedence(
     parser: &mut Parser,
     precedence: Precedence,
     typexpr: bool,
 ) -> Result<Expression, Diagnostic> {
-    // TODO: parsing of range expressions, `expr..<expr` and `expr..=expr`, and
-    // maybe `..<expr`, `..=expr` and maybe `expr..`
-    let mut lhs = match parser.peek_tt() {
-        Some(IntLit(_)) => parse!(@fn parser => parse_intlit_expr),
+    parser.nested(|parser| parse_expr_precedence_unlimited(parser, precedence, typexpr))
+}
+
+/// Maximum nesting depth of expressions, past it the parser stops with an
+/// error instead of overflowing the stack.
+///
+/// Blocks and `else if` branches count as one more level, because they are
+/// parsed without going through [`parse_expr_precedence`], a function nested
+/// in another one is two levels deeper. Every binary or postfix operator of a
+/// chain like `a + b + c` or `a.b.c` also counts as one more level, because
+/// it nests the expression before it.
+pub const MAX_EXPR_DEPTH: usize = 128;
+
+fn parse_expr_precedence_unlimited(
+    parser: &mut Parser,
+    precedence: Precedence,
+    typexpr: bool,
+) -> Result<Expression, Diagnostic> {
+    let lhs = match parser.peek_tt() {
+        Some(IntLit(_) | SpecializedIntLit { .. }) => parse!(@fn parser => parse_intlit_expr),
         Some(Kw(Keyword::True | Keyword::False)) => parse!(@fn parser => parse_boollit_expr),
-        Some(StringLit(_)) => parse!(@fn parser => parse_strlit_expr),
+        Some(StringLit(_) | SpecializedStringLit { .. }) => {
+            parse!(@fn parser => parse_strlit_expr)
+        }
         Some(CharLit(_)) => parse!(@fn parser => parse_charlit_expr),
-        Some(FloatLit(_)) => parse!(@fn parser => parse_floatlit_expr),
+        Some(FloatLit(_) | SpecializedFloatLit { .. }) => {
+            parse!(@fn parser => parse_floatlit_expr)
+        }
         Some(Punct(Punctuation::LParen)) => parse!(@fn parser => parse_grouping_expr),
         Some(Punct(Punctuation::Ampsand)) => parse!(@fn parser => parse_borrow_expr),
         Some(Ident(_)) if !typexpr && parser.is_labeled_expr() => match parser.nth_tt(2) {
@@ -283,7 +418,11 @@ pub fn parse_expr_precedence(
             // be a keyword and one of loop, while or for
             _ => opt_unreachable!(),
         },
-        Some(Ident(_)) => parse!(@fn parser => parse_ident_expr),
+        Some(Ident(_) | Kw(Keyword::SelfVal)) => parse!(@fn parser => parse_ident_expr),
+        // NOTE: in type position, `fun(..) -> ..` is a function pointer type
+        // like `*fun(..) -> ..`, because a function can't be defined or
+        // declared there.
+        Some(Kw(Keyword::Fun)) if typexpr => parse!(@fn parser => parse_funptr_type_expr),
         Some(Kw(Keyword::Fun)) => parse!(@fn parser => parse_funkw_expr),
         Some(Kw(Keyword::If)) => parse!(@fn parser => parse_if_else_expr, false),
         Some(Kw(Keyword::While)) => parse!(@fn parser => parse_predicate_loop_expr),
@@ -293,6 +432,7 @@ pub fn parse_expr_precedence(
         Some(Kw(Keyword::Break)) => parse!(@fn parser => parse_break_expr),
         Some(Kw(Keyword::Continue)) => parse!(@fn parser => parse_continue_expr),
         Some(Kw(Keyword::Null)) => parse!(@fn parser => parse_null_expr),
+        Some(Kw(Keyword::Comptime)) => parse!(@fn parser => parse_comptime_expr),
         Some(Kw(Keyword::Orb)) => parse!(@fn parser => parse_orb_expr),
         Some(Punct(Punctuation::LBrace)) => parse!(@fn parser => parse_block_expr),
         Some(Punct(Punctuation::Star))
@@ -301,6 +441,12 @@ pub fn parse_expr_precedence(
             parse!(@fn parser => parse_funptr_type_expr)
         }
         Some(Punct(Punctuation::Star)) => parse!(@fn parser => parse_pointer_type_expr),
+        Some(Kw(Keyword::Struct)) => parse!(@fn parser => parse_struct_type_expr),
+        Some(Kw(Keyword::Enum)) => parse!(@fn parser => parse_enum_type_expr),
+        Some(Kw(Keyword::Match)) => parse!(@fn parser => parse_match_expr),
+        Some(Punct(Punctuation::Dot2 | Punctuation::Dot2Lt | Punctuation::Dot2Equal)) => {
+            parse!(@fn parser => parse_range_expr, None)
+        }
         Some(tt) if UnaryOp::left_from_token(tt.clone()).is_some() => {
             parse!(@fn parser => parse_unary_left_expr)
         }
@@ -318,11 +464,24 @@ pub fn parse_expr_precedence(
         }
     };
 
-    loop {
-        let Some(tt) = parser.peek_tt().cloned() else {
-            break;
-        };
+    // the operators of the chain are nested in each other, they are limited
+    // like nested expressions
+    let depth = parser.depth;
+    let res = parse_postfix_chain(parser, lhs, precedence, typexpr);
+    parser.depth = depth;
+
+    res
+}
 
+/// Parses the binary and postfix operators following `lhs`, with a precedence
+/// of at least `precedence`.
+fn parse_postfix_chain(
+    parser: &mut Parser,
+    mut lhs: Expression,
+    precedence: Precedence,
+    typexpr: bool,
+) -> Result<Expression, Diagnostic> {
+    while let Some(tt) = parser.peek_tt().cloned() {
         let Some(pr) = Precedence::from(tt) else {
             // the next token isn't part of a post expression
             break;
@@ -332,6 +491,8 @@ pub fn parse_expr_precedence(
             break;
         }
 
+        parser.deeper()?;
+
         lhs = match parser.peek_tt() {
             Some(Punct(Punctuation::LParen)) => {
                 parse!(@fn parser => parse_funcall_expr, Box::new(lhs))
@@ -339,6 +500,15 @@ pub fn parse_expr_precedence(
             Some(Punct(Punctuation::Dot)) => {
                 parse!(@fn parser => parse_member_access_expr, lhs)
             }
+            Some(Kw(Keyword::As)) => parse!(@fn parser => parse_cast_expr, lhs),
+            Some(Punct(Punctuation::LBrace))
+                if !typexpr && parser.struct_lit_allowed() && lhs.is_struct_lit_typexpr() =>
+            {
+                parse!(@fn parser => parse_struct_lit_expr, lhs)
+            }
+            Some(Punct(Punctuation::Dot2 | Punctuation::Dot2Lt | Punctuation::Dot2Equal)) => {
+                parse!(@fn parser => parse_range_expr, Some(lhs))
+            }
             Some(maybe_bin_op) if BinOp::from_tt(maybe_bin_op.clone()).is_some() => {
                 parse!(@fn parser => parse_binary_expr, lhs)
             }
@@ -367,10 +537,45 @@ impl Parser {
 /// Parse an integer literal expression
 pub fn parse_intlit_expr(parser: &mut Parser) -> Result<Expression, Diagnostic> {
     // TEST: n/a
-    let (i, loc) = expect_token!(parser => [IntLit(i), *i], "integer literal");
+    let ((i, suffix), loc) = expect_token!(
+        parser => [
+            IntLit(i), (*i, None);
+            SpecializedIntLit { specialization, int }, (*int, Some(specialization.clone()));
+        ],
+        "integer literal"
+    );
+
+    let suffix = match suffix.map(|s| s.parse::<IntSuffix>().map_err(|()| s)) {
+        Some(Ok(suffix)) => Some(suffix),
+        Some(Err(s)) => {
+            parser.sink.emit(UnknownLiteralSpecialization {
+                kind: "suffix",
+                s12n: s,
+                expected: IntSuffix::SUFFIXES,
+                loc: loc.clone(),
+            });
+
+            None
+        }
+        None => None,
+    };
+
+    // NOTE: the literal of a signed or pointer-sized suffix is checked later,
+    // see `IntSuffix::max`.
+    if let Some(suffix) = suffix
+        && let Some(max) = suffix.max()
+        && i > max
+    {
+        parser.sink.emit(OverflowingSuffixedLiteral {
+            int: i,
+            suffix,
+            max,
+            loc: loc.clone(),
+        });
+    }
 
     Ok(Expression {
-        expr: Expr::IntLit(i),
+        expr: Expr::IntLit(i, suffix),
         loc,
     })
 }
@@ -389,7 +594,26 @@ pub fn parse_boollit_expr(parser: &mut Parser) -> Result<Expression, Diagnostic>
 /// Parse a string literal expression
 pub fn parse_strlit_expr(parser: &mut Parser) -> Result<Expression, Diagnostic> {
     // TEST: n/a
-    let (str, loc) = expect_token!(parser => [StringLit(s), s.clone()], "string literal");
+    let ((str, prefix), loc) = expect_token!(
+        parser => [
+            StringLit(s), (s.clone(), None);
+            SpecializedStringLit { specialization, str }, (str.clone(), Some(specialization.clone()));
+        ],
+        "string literal"
+    );
+
+    // NOTE: the only string prefix is `r`, for raw strings, they are lexed
+    // without escape sequences so they are just string literals now.
+    if let Some(prefix) = prefix
+        && prefix != "r"
+    {
+        parser.sink.emit(UnknownLiteralSpecialization {
+            kind: "prefix",
+            s12n: prefix,
+            expected: &["r"],
+            loc: loc.clone(),
+        });
+    }
 
     Ok(Expression {
         expr: Expr::StringLit(str),
@@ -411,32 +635,78 @@ pub fn parse_charlit_expr(parser: &mut Parser) -> Result<Expression, Diagnostic>
 /// Parses a float literal expression
 pub fn parse_floatlit_expr(parser: &mut Parser) -> Result<Expression, Diagnostic> {
     // TEST: n/a
-    let (float, loc) = expect_token!(parser => [FloatLit(f), *f], "float literal");
+    let ((float, suffix), loc) = expect_token!(
+        parser => [
+            FloatLit(f), (*f, None);
+            SpecializedFloatLit { specialization, float }, (*float, Some(specialization.clone()));
+        ],
+        "float literal"
+    );
+
+    let suffix = match suffix.map(|s| s.parse::<FloatSuffix>().map_err(|()| s)) {
+        Some(Ok(suffix)) => Some(suffix),
+        Some(Err(s)) => {
+            parser.sink.emit(UnknownLiteralSpecialization {
+                kind: "suffix",
+                s12n: s,
+                expected: FloatSuffix::SUFFIXES,
+                loc: loc.clone(),
+            });
+
+            None
+        }
+        None => None,
+    };
 
     Ok(Expression {
-        expr: Expr::FloatLit(float),
+        expr: Expr::FloatLit(float, suffix),
         loc,
     })
 }
 
-/// Parse a grouping expression
+/// Parses a grouping expression or a tuple expression, they are told apart by
+/// the presence of a comma: `(a)` is a grouping, `(a,)` and `(a, b)` are
+/// tuples, and `()` is the empty tuple.
 pub fn parse_grouping_expr(parser: &mut Parser) -> Result<Expression, Diagnostic> {
     // TEST: n/a
     let ((), lo) = expect_token!(parser => [Punct(Punctuation::LParen), ()], [Punctuation::LParen]);
-    let expr = parse!(box: parser => Expression);
-    // TEST: yes
+
+    let mut exprs = Vec::new();
+    let mut is_tuple = false;
+
+    loop {
+        if let Some(Punct(Punctuation::RParen)) = parser.peek_tt() {
+            is_tuple |= exprs.is_empty();
+            break;
+        }
+
+        exprs.push(parser.with_struct_lit(true, Expression::parse)?);
+
+        // TEST: yes
+        expect_token!(parser => [Punct(Punctuation::Comma), (); Punct(Punctuation::RParen), (), in break], [Punctuation::Comma, Punctuation::RParen]);
+        is_tuple = true;
+    }
+
+    // TEST: n/a
     let ((), hi) = expect_token!(parser => [Punct(Punctuation::RParen), ()], [Punctuation::RParen]);
 
+    let expr = if is_tuple {
+        Expr::Tuple(exprs)
+    } else {
+        Expr::Grouping(Box::new(exprs.pop().unwrap()))
+    };
+
     Ok(Expression {
-        expr: Expr::Grouping(expr),
+        expr,
         loc: Span::from_ends(lo, hi),
     })
 }
 
 /// Parse an identifier expression
 pub fn parse_ident_expr(parser: &mut Parser) -> Result<Expression, Diagnostic> {
+    // NOTE: `self` is an identifier for the first argument of a method
     // TEST: n/a
-    let (id, loc) = expect_token!(parser => [Ident(s), s.clone()], Ident(String::new()));
+    let (id, loc) = expect_token!(parser => [Ident(s), s.clone(); Kw(Keyword::SelfVal), Keyword::SELF.to_string()], Ident(String::new()));
 
     Ok(Expression {
         expr: Expr::Ident(id),
@@ -445,7 +715,7 @@ pub fn parse_ident_expr(parser: &mut Parser) -> Result<Expression, Diagnostic> {
 }
 
 /// The precedence table of Lun
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Precedence {
     // `None` is a special precedence value, it is used to exit out of the loop
     // when a non expression token is after an expression. It is not the
@@ -459,6 +729,8 @@ pub enum Precedence {
     //
     /// `a = b`
     Assignment,
+    /// `a..<b ; a..=b ; a..`
+    Range,
     /// `a or b`
     LogicalOr,
     /// `a and b`
@@ -479,6 +751,8 @@ pub enum Precedence {
     Term,
     /// `a * b ; a / b ; a % b`
     Factor,
+    /// `a as T`
+    Cast,
     /// `op expression`
     Unary,
     /// `expression "(" expression,* ")"`
@@ -498,7 +772,8 @@ impl Precedence {
     pub fn next(self) -> Precedence {
         match self {
             Self::__First__ => Self::Assignment,
-            Self::Assignment => Self::LogicalOr,
+            Self::Assignment => Self::Range,
+            Self::Range => Self::LogicalOr,
             Self::LogicalOr => Self::LogicalAnd,
             Self::LogicalAnd => Self::Comparison,
             Self::Comparison => Self::Equality,
@@ -508,7 +783,8 @@ impl Precedence {
             Self::BitwiseAnd => Self::Shift,
             Self::Shift => Self::Term,
             Self::Term => Self::Factor,
-            Self::Factor => Self::Unary,
+            Self::Factor => Self::Cast,
+            Self::Cast => Self::Unary,
             Self::Unary => Self::Call,
             Self::Call => Self::MemberAccess,
             Self::MemberAccess => Self::Primary,
@@ -520,6 +796,7 @@ impl Precedence {
     pub fn associativity(&self) -> Associativity {
         match self {
             Self::Assignment => Associativity::RightToLeft,
+            Self::Range => Associativity::None,
             Self::LogicalOr => Associativity::LeftToRight,
             Self::LogicalAnd => Associativity::LeftToRight,
             Self::Comparison => Associativity::LeftToRight,
@@ -530,6 +807,7 @@ impl Precedence {
             Self::Shift => Associativity::LeftToRight,
             Self::Term => Associativity::LeftToRight,
             Self::Factor => Associativity::LeftToRight,
+            Self::Cast => Associativity::LeftToRight,
             Self::Unary => Associativity::RightToLeft,
             Self::Call => Associativity::LeftToRight,
             Self::MemberAccess => Associativity::LeftToRight,
@@ -544,6 +822,9 @@ impl Precedence {
         use TokenType::Punct;
         match value {
             Punct(Punctuation::Equal) => Some(Precedence::Assignment),
+            Punct(Punctuation::Dot2 | Punctuation::Dot2Lt | Punctuation::Dot2Equal) => {
+                Some(Precedence::Range)
+            }
             Kw(Keyword::Or) => Some(Precedence::LogicalOr),
             Kw(Keyword::And) => Some(Precedence::LogicalAnd),
             Punct(
@@ -558,7 +839,8 @@ impl Precedence {
             Punct(Punctuation::Star | Punctuation::Slash | Punctuation::Percent) => {
                 Some(Precedence::Factor)
             }
-            Punct(Punctuation::LParen) => Some(Precedence::Call),
+            Kw(Keyword::As) => Some(Precedence::Cast),
+            Punct(Punctuation::LParen | Punctuation::LBrace) => Some(Precedence::Call),
             Punct(Punctuation::Dot) => Some(Precedence::MemberAccess),
             Punct(Punctuation::DotStar) => Some(Precedence::Primary),
             _ => None,
@@ -595,9 +877,9 @@ pub enum BinOp {
     CompNe,
     /// assignment
     Assignment,
-    /// and
+    /// logical and, short-circuiting, `and`
     LogicalAnd,
-    /// or
+    /// logical or, short-circuiting, `or`
     LogicalOr,
     /// &
     BitwiseAnd,
@@ -688,11 +970,27 @@ impl BinOp {
         )
     }
 
+    /// Is the operator a short-circuiting logical operator, `and` / `or`?
     pub fn is_logical(&self) -> bool {
-        // TODO: implement logical operators like `"not" expr`, `expr "and"
-        // expr`, `expr "or" expr`, `expr "xor" expr`
         matches!(self, Self::LogicalAnd | Self::LogicalOr)
     }
+
+    /// Returns the precedence of the operator.
+    pub fn precedence(&self) -> Precedence {
+        match self {
+            Self::Assignment => Precedence::Assignment,
+            Self::LogicalOr => Precedence::LogicalOr,
+            Self::LogicalAnd => Precedence::LogicalAnd,
+            Self::CompLT | Self::CompLE | Self::CompGT | Self::CompGE => Precedence::Comparison,
+            Self::CompEq | Self::CompNe => Precedence::Equality,
+            Self::BitwiseOr => Precedence::BitwiseOr,
+            Self::BitwiseXor => Precedence::BitwiseXor,
+            Self::BitwiseAnd => Precedence::BitwiseAnd,
+            Self::Shr | Self::Shl => Precedence::Shift,
+            Self::Add | Self::Sub => Precedence::Term,
+            Self::Mul | Self::Div | Self::Rem => Precedence::Factor,
+        }
+    }
 }
 
 /// Parse binary expression, `expression op expression`
@@ -737,13 +1035,198 @@ pub fn parse_binary_expr(parser: &mut Parser, lhs: Expression) -> Result<Express
     })
 }
 
+/// Parse range expression, `expr? ".." | expr? "..<" expr | expr? "..=" expr`
+pub fn parse_range_expr(
+    parser: &mut Parser,
+    start: Option<Expression>,
+) -> Result<Expression, Diagnostic> {
+    // TEST: n/a
+    let ((inclusive, has_end), op_loc) = expect_token!(
+        parser => [
+            Punct(Punctuation::Dot2), (false, false);
+            Punct(Punctuation::Dot2Lt), (false, true);
+            Punct(Punctuation::Dot2Equal), (true, true);
+        ],
+        [Punctuation::Dot2, Punctuation::Dot2Lt, Punctuation::Dot2Equal]
+    );
+
+    let end = if has_end {
+        Some(parse!(box: @fn parser => parse_expr_precedence, Precedence::Range.next(), false))
+    } else {
+        None
+    };
+
+    let lo = start
+        .as_ref()
+        .map(|s| s.loc.clone())
+        .unwrap_or(op_loc.clone());
+    let hi = end.as_ref().map(|e| e.loc.clone()).unwrap_or(op_loc);
+    let loc = Span::from_ends(lo, hi);
+
+    // range expressions are non associative
+    if let Some(Token {
+        tt: Punct(Punctuation::Dot2 | Punctuation::Dot2Lt | Punctuation::Dot2Equal),
+        loc: op_loc,
+    }) = parser.peek_tok()
+    {
+        // TEST: no. 1
+        return Err(ChainedRange {
+            range_loc: loc,
+            loc: op_loc.clone(),
+        }
+        .into_diag());
+    }
+
+    Ok(Expression {
+        expr: Expr::Range {
+            start: start.map(Box::new),
+            end,
+            inclusive,
+        },
+        loc,
+    })
+}
+
+/// Suffix of an integer literal, it forces the type of the literal.
+///
+/// `12u8`, `0xFF'i64`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IntSuffix {
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    Isz,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    Usz,
+}
+
+impl IntSuffix {
+    /// all the valid integer suffixes
+    pub const SUFFIXES: &[&str] = &[
+        "i8", "i16", "i32", "i64", "i128", "isz", "u8", "u16", "u32", "u64", "u128", "usz",
+    ];
+
+    /// Returns the maximum value of the type of an unsigned suffix, `None` for
+    /// the signed suffixes because a negated literal can be one past their
+    /// maximum, `-128i8`, and for the pointer-sized integers because their
+    /// size depends on the target.
+    pub fn max(&self) -> Option<u128> {
+        match self {
+            IntSuffix::I8
+            | IntSuffix::I16
+            | IntSuffix::I32
+            | IntSuffix::I64
+            | IntSuffix::I128
+            | IntSuffix::Isz => None,
+            IntSuffix::U8 => Some(u8::MAX as u128),
+            IntSuffix::U16 => Some(u16::MAX as u128),
+            IntSuffix::U32 => Some(u32::MAX as u128),
+            IntSuffix::U64 => Some(u64::MAX as u128),
+            IntSuffix::U128 => Some(u128::MAX),
+            IntSuffix::Usz => None,
+        }
+    }
+}
+
+impl FromStr for IntSuffix {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "i8" => Ok(IntSuffix::I8),
+            "i16" => Ok(IntSuffix::I16),
+            "i32" => Ok(IntSuffix::I32),
+            "i64" => Ok(IntSuffix::I64),
+            "i128" => Ok(IntSuffix::I128),
+            "isz" => Ok(IntSuffix::Isz),
+            "u8" => Ok(IntSuffix::U8),
+            "u16" => Ok(IntSuffix::U16),
+            "u32" => Ok(IntSuffix::U32),
+            "u64" => Ok(IntSuffix::U64),
+            "u128" => Ok(IntSuffix::U128),
+            "usz" => Ok(IntSuffix::Usz),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Display for IntSuffix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            Self::I8 => "i8",
+            Self::I16 => "i16",
+            Self::I32 => "i32",
+            Self::I64 => "i64",
+            Self::I128 => "i128",
+            Self::Isz => "isz",
+            Self::U8 => "u8",
+            Self::U16 => "u16",
+            Self::U32 => "u32",
+            Self::U64 => "u64",
+            Self::U128 => "u128",
+            Self::Usz => "usz",
+        };
+
+        f.write_str(str)
+    }
+}
+
+/// Suffix of a float literal, it forces the type of the literal.
+///
+/// `2.5f32`, `1e-9'f64`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FloatSuffix {
+    F16,
+    F32,
+    F64,
+    F128,
+}
+
+impl FloatSuffix {
+    /// all the valid float suffixes
+    pub const SUFFIXES: &[&str] = &["f16", "f32", "f64", "f128"];
+}
+
+impl FromStr for FloatSuffix {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "f16" => Ok(FloatSuffix::F16),
+            "f32" => Ok(FloatSuffix::F32),
+            "f64" => Ok(FloatSuffix::F64),
+            "f128" => Ok(FloatSuffix::F128),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Display for FloatSuffix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            Self::F16 => "f16",
+            Self::F32 => "f32",
+            Self::F64 => "f64",
+            Self::F128 => "f128",
+        };
+
+        f.write_str(str)
+    }
+}
+
 /// Unary Operators
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum UnaryOp {
     // left unary operator
     /// `- expression`
     Negation,
-    /// `! expression`
+    /// `! expression`, logical not of a boolean, bitwise not of an integer
     Not,
     // right unary operator
     /// `expression.*`
@@ -823,13 +1306,47 @@ pub fn parse_funcall_expr(
     expect_token!(parser => [Punct(Punctuation::LParen), ()], Punctuation::LParen);
 
     let mut args = Vec::new();
+    let mut named_args: Vec<NamedArg> = Vec::new();
 
     loop {
         if let Some(Punct(Punctuation::RParen)) = parser.peek_tt() {
             break;
         }
 
-        args.push(parse!(parser => Expression));
+        if let (Some(Ident(_)), Some(Punct(Punctuation::Colon))) =
+            (parser.peek_tt(), parser.nth_tt(1))
+        {
+            // named argument
+            let (name, name_loc) = match parser.pop() {
+                Some(Token { tt: Ident(id), loc }) => (id, loc),
+                // SAFETY: we peeked an identifier just above
+                _ => opt_unreachable!(),
+            };
+
+            parser.pop();
+
+            let value = parser.with_struct_lit(true, Expression::parse)?;
+
+            named_args.push(NamedArg {
+                name,
+                name_loc: name_loc.clone(),
+                loc: Span::from_ends(name_loc, value.loc.clone()),
+                value,
+            });
+        } else {
+            let arg = parser.with_struct_lit(true, Expression::parse)?;
+
+            if let Some(named) = named_args.last() {
+                // TEST: no. 1
+                return Err(PositionalAfterNamedArg {
+                    named_loc: named.loc.clone(),
+                    loc: arg.loc,
+                }
+                .into_diag());
+            }
+
+            args.push(arg);
+        }
 
         // TEST: yes
         expect_token!(parser => [Punct(Punctuation::Comma), (); Punct(Punctuation::RParen), (), in break], [Punctuation::Comma, Punctuation::RParen]);
@@ -842,6 +1359,7 @@ pub fn parse_funcall_expr(
         expr: Expr::FunCall {
             callee: called,
             args,
+            named_args,
         },
         loc: Span::from_ends(lo, hi),
     })
@@ -856,7 +1374,8 @@ pub fn parse_funkw_expr(parser: &mut Parser) -> Result<Expression, Diagnostic> {
     expect_token!(parser => [Punct(Punctuation::LParen), ()], Punctuation::LParen);
 
     match (parser.peek_tt(), parser.nth_tt(1)) {
-        (Some(Ident(_)), Some(Punct(Punctuation::Colon))) => {
+        (Some(Ident(_) | Kw(Keyword::SelfVal)), Some(Punct(Punctuation::Colon)))
+        | (Some(Kw(Keyword::Comptime)), _) => {
             // function definition
 
             let mut args = Vec::new();
@@ -866,20 +1385,41 @@ pub fn parse_funkw_expr(parser: &mut Parser) -> Result<Expression, Diagnostic> {
                     break;
                 }
 
+                let comptime_lo = if let Some(Kw(Keyword::Comptime)) = parser.peek_tt() {
+                    Some(parser.pop().unwrap().loc)
+                } else {
+                    None
+                };
+
+                // NOTE: `self` is allowed as the name of the receiver of a
+                // method
                 // TEST: n/a
-                let (name, lo_arg) =
-                    expect_token!(parser => [Ident(id), id.clone()], Ident(String::new()));
+                let (name, name_loc) = expect_token!(parser => [Ident(id), id.clone(); Kw(Keyword::SelfVal), Keyword::SELF.to_string()], Ident(String::new()));
 
                 // TEST: n/a
                 expect_token!(parser => [Punct(Punctuation::Colon), ()], Punct(Punctuation::Colon));
 
                 let typexpr = parse!(@fn parser => parse_typexpr);
 
+                let default = if let Some(Punct(Punctuation::Equal)) = parser.peek_tt() {
+                    parser.pop();
+                    Some(parse!(parser => Expression))
+                } else {
+                    None
+                };
+
+                let hi = default
+                    .as_ref()
+                    .map(|default| default.loc.clone())
+                    .unwrap_or(typexpr.loc.clone());
+
                 args.push(Arg {
+                    comptime: comptime_lo.is_some(),
                     name,
-                    name_loc: lo_arg.clone(),
-                    typexpr: typexpr.clone(),
-                    loc: Span::from_ends(lo_arg, typexpr.loc),
+                    name_loc: name_loc.clone(),
+                    typexpr,
+                    default,
+                    loc: Span::from_ends(comptime_lo.unwrap_or(name_loc), hi),
                 });
 
                 // TEST: no. 2
@@ -996,7 +1536,7 @@ pub fn parse_if_else_expr(parser: &mut Parser, only_block: bool) -> Result<Expre
     // TEST: n/a
     let (_, lo) = expect_token!(parser => [Kw(Keyword::If), ()], Kw(Keyword::If));
 
-    let cond = parse!(box: parser => Expression);
+    let cond = Box::new(parser.with_struct_lit(false, Expression::parse)?);
 
     if let Some(TokenType::Punct(Punctuation::LBrace)) = parser.peek_tt() {
         // if expr
@@ -1013,7 +1553,7 @@ pub fn parse_if_else_expr(parser: &mut Parser, only_block: bool) -> Result<Expre
                     let Expression {
                         expr: Expr::If(if_expr),
                         loc: _,
-                    } = parse!(@fn parser => parse_if_else_expr, true)
+                    } = parser.nested(|parser| parse_if_else_expr(parser, true))?
                     else {
                         unreachable!();
                     };
@@ -1147,7 +1687,7 @@ pub fn parse_predicate_loop_expr(parser: &mut Parser) -> Result<Expression, Diag
     let (_, lo_while) = expect_token!(parser => [Kw(Keyword::While), ()], Kw(Keyword::While));
     let lo = label.as_ref().map(|l| l.1.clone()).unwrap_or(lo_while);
 
-    let cond = parse!(box: parser => Expression);
+    let cond = Box::new(parser.with_struct_lit(false, Expression::parse)?);
     let body = parse!(parser => Block);
 
     let hi = body.loc.clone();
@@ -1179,12 +1719,13 @@ pub fn parse_iterator_loop_expr(parser: &mut Parser) -> Result<Expression, Diagn
     let lo = label.as_ref().map(|l| l.1.clone()).unwrap_or(lo_for);
 
     // TEST: no. 1
-    let (variable, _) = expect_token!(parser => [Ident(id), id.clone()], Ident(String::new()));
+    let (variable, variable_loc) =
+        expect_token!(parser => [Ident(id), id.clone()], Ident(String::new()));
 
     // TEST: no. 2
     expect_token!(parser => [Kw(Keyword::In), ()], Kw(Keyword::In));
 
-    let iterator = parse!(box: parser => Expression);
+    let iterator = Box::new(parser.with_struct_lit(false, Expression::parse)?);
 
     let body = parse!(parser => Block);
 
@@ -1196,6 +1737,7 @@ pub fn parse_iterator_loop_expr(parser: &mut Parser) -> Result<Expression, Diagn
         expr: Expr::IteratorLoop {
             label,
             variable,
+            variable_loc,
             iterator,
             body,
             loc: loc.clone(),
@@ -1320,6 +1862,19 @@ pub fn parse_continue_expr(parser: &mut Parser) -> Result<Expression, Diagnostic
     })
 }
 
+/// parses comptime expression
+pub fn parse_comptime_expr(parser: &mut Parser) -> Result<Expression, Diagnostic> {
+    // TEST: n/a
+    let (_, lo) = expect_token!(parser => [Kw(Keyword::Comptime), ()], Kw(Keyword::Comptime));
+
+    let expr = parse!(parser => Expression);
+
+    Ok(Expression {
+        loc: Span::from_ends(lo, expr.loc.clone()),
+        expr: Expr::Comptime(Box::new(expr)),
+    })
+}
+
 /// parses null expression
 pub fn parse_null_expr(parser: &mut Parser) -> Result<Expression, Diagnostic> {
     // TEST: n/a
@@ -1342,13 +1897,18 @@ pub fn parse_orb_expr(parser: &mut Parser) -> Result<Expression, Diagnostic> {
     })
 }
 
-/// parses function pointer type
+/// parses function pointer type, with or without the `*`
 pub fn parse_funptr_type_expr(parser: &mut Parser) -> Result<Expression, Diagnostic> {
-    // TEST: n/a
-    let (_, lo) = expect_token!(parser => [Punct(Punctuation::Star), ()], Punctuation::Star);
+    let lo_star = if let Some(Punct(Punctuation::Star)) = parser.peek_tt() {
+        parser.pop().map(|t| t.loc)
+    } else {
+        None
+    };
 
     // TEST: n/a
-    expect_token!(parser => [Kw(Keyword::Fun), ()], Kw(Keyword::Fun));
+    let (_, lo_fun) = expect_token!(parser => [Kw(Keyword::Fun), ()], Kw(Keyword::Fun));
+
+    let lo = lo_star.unwrap_or(lo_fun);
 
     // TEST: no. 1
     expect_token!(parser => [Punct(Punctuation::LParen), ()], Punctuation::LParen);
@@ -1439,6 +1999,25 @@ pub fn parse_unary_right_expr(
     })
 }
 
+/// parses cast expression
+pub fn parse_cast_expr(parser: &mut Parser, lhs: Expression) -> Result<Expression, Diagnostic> {
+    // TEST: n/a
+    expect_token!(parser => [Kw(Keyword::As), ()], Kw(Keyword::As));
+
+    // NOTE: the type is parsed with a higher precedence than the cast so that
+    // `a as T + b` is parsed as `(a as T) + b`
+    let typexpr = parse!(@fn parser => parse_expr_precedence, Precedence::Cast.next(), true);
+    let loc = Span::from_ends(lhs.loc.clone(), typexpr.loc.clone());
+
+    Ok(Expression {
+        expr: Expr::Cast {
+            expr: Box::new(lhs),
+            typexpr: Box::new(typexpr),
+        },
+        loc,
+    })
+}
+
 pub fn parse_borrow_expr(parser: &mut Parser) -> Result<Expression, Diagnostic> {
     // TEST: n/a
     let (_, lo) = expect_token!(parser => [Punct(Punctuation::Ampsand), ()], Punctuation::Ampsand);
@@ -1467,8 +2046,9 @@ pub fn parse_member_access_expr(
     // TEST: n/a
     expect_token!(parser => [Punct(Punctuation::Dot), ()], Punctuation::Dot);
 
+    // NOTE: the member of a tuple is its index, `t.0`
     // TEST: no. 1
-    let (member, hi) = expect_token!(parser => [Ident(id), id.clone()], Ident(String::new()));
+    let (member, hi) = expect_token!(parser => [Ident(id), id.clone(); IntLit(idx), idx.to_string()], Ident(String::new()));
 
     let loc = Span::from_ends(expr.loc.clone(), hi);
 
@@ -1480,3 +2060,209 @@ pub fn parse_member_access_expr(
         loc,
     })
 }
+
+/// parses struct literal expression
+pub fn parse_struct_lit_expr(
+    parser: &mut Parser,
+    typexpr: Expression,
+) -> Result<Expression, Diagnostic> {
+    let lo = typexpr.loc.clone();
+
+    // TEST: n/a
+    expect_token!(parser => [Punct(Punctuation::LBrace), ()], Punctuation::LBrace);
+
+    let mut fields = Vec::new();
+
+    loop {
+        if let Some(Punct(Punctuation::RBrace)) = parser.peek_tt() {
+            break;
+        }
+
+        // TEST: no. 1
+        let (name, name_loc) =
+            expect_token!(parser => [Ident(id), id.clone()], Ident(String::new()));
+
+        // TEST: no. 2
+        expect_token!(parser => [Punct(Punctuation::Colon), ()], Punctuation::Colon);
+
+        let value = parser.with_struct_lit(true, Expression::parse)?;
+
+        fields.push(FieldValue {
+            name,
+            name_loc: name_loc.clone(),
+            loc: Span::from_ends(name_loc, value.loc.clone()),
+            value,
+        });
+
+        // TEST: no. 3
+        expect_token!(parser => [Punct(Punctuation::Comma), (); Punct(Punctuation::RBrace), (), in break], [Punctuation::Comma, Punctuation::RBrace]);
+    }
+
+    // TEST: n/a
+    let ((), hi) = expect_token!(parser => [Punct(Punctuation::RBrace), ()], Punctuation::RBrace);
+
+    Ok(Expression {
+        expr: Expr::StructLit {
+            typexpr: Box::new(typexpr),
+            fields,
+        },
+        loc: Span::from_ends(lo, hi),
+    })
+}
+
+/// parses struct type expression
+pub fn parse_struct_type_expr(parser: &mut Parser) -> Result<Expression, Diagnostic> {
+    // TEST: n/a
+    let (_, lo) = expect_token!(parser => [Kw(Keyword::Struct), ()], Kw(Keyword::Struct));
+
+    // TEST: no. 1
+    expect_token!(parser => [Punct(Punctuation::LBrace), ()], Punctuation::LBrace);
+
+    let mut fields = Vec::new();
+
+    loop {
+        if let Some(Punct(Punctuation::RBrace)) = parser.peek_tt() {
+            break;
+        }
+
+        // TEST: no. 2
+        let (name, name_loc) =
+            expect_token!(parser => [Ident(id), id.clone()], Ident(String::new()));
+
+        // TEST: no. 3
+        expect_token!(parser => [Punct(Punctuation::Colon), ()], Punctuation::Colon);
+
+        let typexpr = parse!(@fn parser => parse_typexpr);
+
+        fields.push(StructField {
+            name,
+            name_loc: name_loc.clone(),
+            loc: Span::from_ends(name_loc, typexpr.loc.clone()),
+            typexpr,
+        });
+
+        // TEST: no. 4
+        expect_token!(parser => [Punct(Punctuation::Comma), (); Punct(Punctuation::RBrace), (), in break], [Punctuation::Comma, Punctuation::RBrace]);
+    }
+
+    // TEST: n/a
+    let ((), hi) = expect_token!(parser => [Punct(Punctuation::RBrace), ()], Punctuation::RBrace);
+
+    Ok(Expression {
+        expr: Expr::Struct { fields },
+        loc: Span::from_ends(lo, hi),
+    })
+}
+
+/// parses enum type expression
+pub fn parse_enum_type_expr(parser: &mut Parser) -> Result<Expression, Diagnostic> {
+    // TEST: n/a
+    let (_, lo) = expect_token!(parser => [Kw(Keyword::Enum), ()], Kw(Keyword::Enum));
+
+    // TEST: no. 1
+    expect_token!(parser => [Punct(Punctuation::LBrace), ()], Punctuation::LBrace);
+
+    let mut variants = Vec::new();
+
+    loop {
+        if let Some(Punct(Punctuation::RBrace)) = parser.peek_tt() {
+            break;
+        }
+
+        // TEST: no. 2
+        let (name, name_loc) =
+            expect_token!(parser => [Ident(id), id.clone()], Ident(String::new()));
+
+        let value = if let Some(Punct(Punctuation::Equal)) = parser.peek_tt() {
+            parser.pop();
+
+            Some(parser.with_struct_lit(true, Expression::parse)?)
+        } else {
+            None
+        };
+
+        let hi = value
+            .as_ref()
+            .map(|value| value.loc.clone())
+            .unwrap_or(name_loc.clone());
+
+        variants.push(EnumVariant {
+            name,
+            name_loc: name_loc.clone(),
+            value,
+            loc: Span::from_ends(name_loc, hi),
+        });
+
+        // TEST: no. 3
+        expect_token!(parser => [Punct(Punctuation::Comma), (); Punct(Punctuation::RBrace), (), in break], [Punctuation::Comma, Punctuation::RBrace]);
+    }
+
+    // TEST: n/a
+    let ((), hi) = expect_token!(parser => [Punct(Punctuation::RBrace), ()], Punctuation::RBrace);
+
+    Ok(Expression {
+        expr: Expr::Enum { variants },
+        loc: Span::from_ends(lo, hi),
+    })
+}
+
+/// parses match expression
+pub fn parse_match_expr(parser: &mut Parser) -> Result<Expression, Diagnostic> {
+    // TEST: n/a
+    let (_, lo) = expect_token!(parser => [Kw(Keyword::Match), ()], Kw(Keyword::Match));
+
+    let expr = Box::new(parser.with_struct_lit(false, Expression::parse)?);
+
+    // TEST: no. 1
+    expect_token!(parser => [Punct(Punctuation::LBrace), ()], Punctuation::LBrace);
+
+    let mut arms = Vec::new();
+
+    loop {
+        if let Some(Punct(Punctuation::RBrace)) = parser.peek_tt() {
+            break;
+        }
+
+        // NOTE: the patterns are parsed with a precedence higher than the
+        // bitwise or, so that `|` separates the patterns of an arm.
+        let mut pats =
+            vec![parse!(@fn parser => parse_expr_precedence, Precedence::BitwiseXor, false)];
+
+        while let Some(Punct(Punctuation::Pipe)) = parser.peek_tt() {
+            parser.pop();
+
+            pats.push(parse!(@fn parser => parse_expr_precedence, Precedence::BitwiseXor, false));
+        }
+
+        // TEST: no. 2
+        expect_token!(parser => [Punct(Punctuation::EqualGt), ()], Punctuation::EqualGt);
+
+        let body = parser.with_struct_lit(true, Expression::parse)?;
+
+        arms.push(MatchArm {
+            loc: Span::from_ends(pats[0].loc.clone(), body.loc.clone()),
+            pats,
+            body,
+        });
+
+        if arms.last().unwrap().body.is_expr_with_block() {
+            // the comma is optional after an arm with a block body
+            if let Some(Punct(Punctuation::Comma)) = parser.peek_tt() {
+                parser.pop();
+            }
+
+            continue;
+        }
+
+        // TEST: no. 3
+        expect_token!(parser => [Punct(Punctuation::Comma), (); Punct(Punctuation::RBrace), (), in break], [Punctuation::Comma, Punctuation::RBrace]);
+    }
+
+    // TEST: n/a
+    let ((), hi) = expect_token!(parser => [Punct(Punctuation::RBrace), ()], Punctuation::RBrace);
+
+    Ok(Expression {
+        expr: Expr::Match { expr, arms },
+        loc: Span::from_ends(lo, hi),
+    })
+}
@@ -0,0 +1,1356 @@
+//! Source formatter, prints the AST back as canonical lun source code.
+//!
+//! Parenthesis are only added where they are needed for the printed source to
+//! be parsed back to the same AST, a [`Expr::Grouping`] is always printed.
+//!
+//! The comments are not part of the AST, so they are dropped by the formatter.
+//! Files are formatted with the `lunc fmt` command, the formatted source of a
+//! file is also printed by the compiler with `-Dprint=ast-source`.
+
+use std::fmt::{self, Display, Write};
+
+use lunc_diag::DiagnosticSink;
+use lunc_lexer::Lexer;
+use lunc_utils::token::TokenType;
+
+use crate::{
+    directive::Directive,
+    expr::{
+        Arg, Associativity, Else, Expr, Expression, HIGHEST_PRECEDENCE, IfExpression, MatchArm,
+        NamedArg, Precedence, UnaryOp,
+    },
+    item::{Abi, ImportDecl, Item, Module, PackageDecl, Vis},
+    stmt::{Block, Statement, Stmt},
+};
+
+/// Configuration of the [`SourceFormatter`].
+#[derive(Debug, Clone)]
+pub struct FormatConfig {
+    /// maximum width of a line, the lists that don't fit in it are wrapped
+    /// with one element per line
+    pub max_width: usize,
+    /// amount of spaces of one indentation level
+    pub indent: usize,
+}
+
+impl FormatConfig {
+    pub const DEFAULT_MAX_WIDTH: usize = 100;
+    pub const DEFAULT_INDENT: usize = 4;
+}
+
+impl Default for FormatConfig {
+    fn default() -> Self {
+        FormatConfig {
+            max_width: FormatConfig::DEFAULT_MAX_WIDTH,
+            indent: FormatConfig::DEFAULT_INDENT,
+        }
+    }
+}
+
+/// A node of the AST that can be printed back as source code.
+pub trait ToSource {
+    /// print the node in the formatter
+    fn fmt_source(&self, f: &mut SourceFormatter);
+
+    /// Formats the node with the given configuration.
+    fn to_source(&self, cfg: &FormatConfig) -> String {
+        let mut f = SourceFormatter::new(cfg.clone());
+        self.fmt_source(&mut f);
+
+        f.finish()
+    }
+}
+
+macro_rules! impl_display_with_source {
+    ($($node:ty),* $(,)?) => {
+        $(
+            impl Display for $node {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.write_str(&self.to_source(&FormatConfig::default()))
+                }
+            }
+        )*
+    };
+}
+
+impl_display_with_source!(Module, Item, Block, Statement, Expression);
+
+/// Where an expression is printed, used to know if it must be wrapped in
+/// parenthesis.
+#[derive(Debug, Clone, Copy)]
+struct ExprCtx {
+    /// the lowest precedence the expression can have
+    min: Precedence,
+    /// precedence of the operator following the expression, `None` if the
+    /// following token can't continue an expression, like `,` or `;`
+    follow: Option<Precedence>,
+    /// is the expression parsed as a type expression, see [`parse_typexpr`]
+    ///
+    /// [`parse_typexpr`]: crate::expr::parse_typexpr
+    typexpr: bool,
+}
+
+impl ExprCtx {
+    /// an expression delimited by tokens that are not operators, like an
+    /// argument of a call
+    const FULL: ExprCtx = ExprCtx {
+        min: HIGHEST_PRECEDENCE,
+        follow: None,
+        typexpr: false,
+    };
+
+    /// a type expression, like the type of an argument
+    const TYPE: ExprCtx = ExprCtx {
+        min: Precedence::LogicalOr,
+        follow: None,
+        typexpr: true,
+    };
+
+    fn new(min: Precedence, follow: Option<Precedence>) -> ExprCtx {
+        ExprCtx {
+            min,
+            follow,
+            typexpr: false,
+        }
+    }
+
+    fn followed_by(self, follow: Option<Precedence>) -> ExprCtx {
+        ExprCtx { follow, ..self }
+    }
+}
+
+/// An argument of a function call, positional or named.
+enum CallArg<'a> {
+    Positional(&'a Expression),
+    Named(&'a NamedArg),
+}
+
+/// Prints the AST as source code.
+#[derive(Debug, Clone)]
+pub struct SourceFormatter {
+    cfg: FormatConfig,
+    out: String,
+    /// current indentation level
+    level: usize,
+    /// when true the lists are never wrapped, used to measure them
+    flat: bool,
+    /// are struct literals allowed, like in the parser see
+    /// [`Parser::with_struct_lit`]
+    ///
+    /// [`Parser::with_struct_lit`]: crate::Parser::with_struct_lit
+    struct_lit: bool,
+}
+
+impl SourceFormatter {
+    /// Create a new formatter with the given configuration.
+    pub fn new(cfg: FormatConfig) -> SourceFormatter {
+        SourceFormatter {
+            cfg,
+            out: String::new(),
+            level: 0,
+            flat: false,
+            struct_lit: true,
+        }
+    }
+
+    /// Returns the formatted source code.
+    pub fn finish(self) -> String {
+        self.out
+    }
+
+    fn write(&mut self, s: &str) {
+        self.out.push_str(s);
+    }
+
+    /// Starts a new line at the current indentation level.
+    fn newline(&mut self) {
+        self.out.push('\n');
+
+        let width = self.level * self.cfg.indent;
+        self.out.extend(std::iter::repeat_n(' ', width));
+    }
+
+    /// Column of the end of the output.
+    fn column(&self) -> usize {
+        self.out.rsplit('\n').next().unwrap_or("").chars().count()
+    }
+
+    /// Calls `f` with struct literals allowed or not and then restores the
+    /// previous state, see [`Parser::with_struct_lit`].
+    ///
+    /// [`Parser::with_struct_lit`]: crate::Parser::with_struct_lit
+    fn with_struct_lit(&mut self, allowed: bool, f: impl FnOnce(&mut SourceFormatter)) {
+        let previous = std::mem::replace(&mut self.struct_lit, allowed);
+        f(self);
+        self.struct_lit = previous;
+    }
+
+    /// Renders `f` in a new formatter at the same indentation level.
+    fn render(&self, flat: bool, f: impl FnOnce(&mut SourceFormatter)) -> String {
+        let mut fmt = SourceFormatter {
+            cfg: self.cfg.clone(),
+            out: String::new(),
+            level: self.level,
+            flat,
+            struct_lit: self.struct_lit,
+        };
+        f(&mut fmt);
+
+        fmt.out
+    }
+
+    /// Prints a comma separated list, on one line if it fits in the maximum
+    /// width, or with one item per line. `padded` adds spaces inside of the
+    /// delimiters when the list is on one line, like `{ a, b }`.
+    fn list<T>(
+        &mut self,
+        open: &str,
+        items: &[T],
+        close: &str,
+        padded: bool,
+        fmt_item: impl Fn(&mut SourceFormatter, &T),
+    ) {
+        self.list_reserving(open, items, close, padded, 0, fmt_item);
+    }
+
+    /// Like [`SourceFormatter::list`] but `reserve` columns are kept for what
+    /// follows the list on the same line.
+    fn list_reserving<T>(
+        &mut self,
+        open: &str,
+        items: &[T],
+        close: &str,
+        padded: bool,
+        reserve: usize,
+        fmt_item: impl Fn(&mut SourceFormatter, &T),
+    ) {
+        if items.is_empty() {
+            self.write(open);
+            self.write(close);
+            return;
+        }
+
+        let pad = if padded { " " } else { "" };
+        let flat_list = |f: &mut SourceFormatter| {
+            f.write(open);
+            f.write(pad);
+            for (i, item) in items.iter().enumerate() {
+                if i != 0 {
+                    f.write(", ");
+                }
+                fmt_item(f, item);
+            }
+            f.write(pad);
+            f.write(close);
+        };
+
+        if self.flat {
+            flat_list(self);
+            return;
+        }
+
+        let flat = self.render(true, flat_list);
+
+        if !flat.contains('\n')
+            && self.column() + flat.chars().count() + reserve <= self.cfg.max_width
+        {
+            self.write(&flat);
+            return;
+        }
+
+        self.write(open);
+        self.level += 1;
+        for item in items {
+            self.newline();
+            fmt_item(self, item);
+            self.write(",");
+        }
+        self.level -= 1;
+        self.newline();
+        self.write(close);
+    }
+
+    fn vis(&mut self, vis: &Vis) {
+        if *vis == Vis::Public {
+            self.write("pub ");
+        }
+    }
+
+    /// `name : typexpr : value` or `name : typexpr = value`, `sep` is either
+    /// `:` or `=`.
+    fn global_def(
+        &mut self,
+        vis: &Vis,
+        name: &str,
+        typexpr: Option<&Expression>,
+        sep: char,
+        value: &Expression,
+    ) {
+        self.vis(vis);
+        self.write(name);
+
+        match typexpr {
+            Some(typexpr) => {
+                self.write(" : ");
+                let follow = (sep == '=').then_some(Precedence::Assignment);
+                self.expr(typexpr, ExprCtx::TYPE.followed_by(follow));
+                write!(self.out, " {sep} ").unwrap();
+            }
+            None => write!(self.out, " :{sep} ").unwrap(),
+        }
+
+        self.expr(value, ExprCtx::FULL);
+
+        if !value.is_expr_with_block() {
+            self.write(";");
+        }
+    }
+
+    fn item(&mut self, item: &Item) {
+        match item {
+            Item::GlobalConst {
+                vis,
+                name,
+                typexpr,
+                value,
+                ..
+            } => self.global_def(vis, name, typexpr.as_ref(), ':', value),
+            Item::GlobalVar {
+                vis,
+                name,
+                typexpr,
+                value,
+                ..
+            } => self.global_def(vis, name, typexpr.as_ref(), '=', value),
+            Item::GlobalUninit {
+                vis, name, typexpr, ..
+            } => {
+                self.vis(vis);
+                self.write(name);
+                self.write(" : ");
+                self.expr(typexpr, ExprCtx::TYPE);
+                self.write(";");
+            }
+            Item::ExternBlock { abi, items, .. } => {
+                let abi = match abi {
+                    Abi::C => "C",
+                };
+                write!(self.out, "extern \"{abi}\" {{").unwrap();
+
+                if !items.is_empty() {
+                    self.level += 1;
+                    for item in items {
+                        self.newline();
+                        self.item(item);
+                    }
+                    self.level -= 1;
+                    self.newline();
+                }
+
+                self.write("}");
+            }
+            Item::Impl {
+                params,
+                target,
+                defs,
+                ..
+            } => {
+                self.write("impl");
+
+                if !params.is_empty() {
+                    self.list("(", params, ")", false, |f, param| {
+                        f.write(&param.name);
+                        f.write(": ");
+                        f.expr(&param.typexpr, ExprCtx::TYPE);
+                    });
+                }
+
+                self.write(" ");
+                self.expr(target, ExprCtx::TYPE);
+                self.write(" {");
+
+                if !defs.is_empty() {
+                    self.level += 1;
+                    for def in defs {
+                        // NOTE: the visibility of the definition is also the
+                        // one of its item
+                        self.newline();
+                        self.item(&def.item);
+                    }
+                    self.level -= 1;
+                    self.newline();
+                }
+
+                self.write("}");
+            }
+            Item::Directive(Directive::Mod { name, .. }) => {
+                write!(self.out, "#{} {name};", Directive::MOD_NAME).unwrap();
+            }
+            Item::Directive(Directive::Import { path, alias, .. }) => {
+                write!(self.out, "#{} {}", Directive::IMPORT_NAME, path.path).unwrap();
+
+                if let Some(alias) = alias {
+                    write!(self.out, " as {alias}").unwrap();
+                }
+
+                self.write(";");
+            }
+        }
+    }
+
+    fn block(&mut self, block: &Block) {
+        if block.stmts.is_empty() && block.last_expr.is_none() {
+            self.write("{}");
+            return;
+        }
+
+        self.write("{");
+        self.level += 1;
+
+        for (i, stmt) in block.stmts.iter().enumerate() {
+            self.newline();
+            self.stmt(stmt);
+
+            let next_continues = match block.stmts.get(i + 1) {
+                Some(next) => stmt_continues_expr(next),
+                None => block.last_expr.as_deref().is_some_and(expr_continues_expr),
+            };
+
+            let semicolon = match &stmt.stmt {
+                Stmt::VariableDef { .. } => true,
+                Stmt::Defer { expr } => !expr.is_expr_with_block(),
+                // NOTE: an expression with block at the end of a block is
+                // its last expression, and it would be continued by the next
+                // statement if it starts like an operator
+                Stmt::Expression(expr) => {
+                    !expr.is_expr_with_block()
+                        || next_continues
+                        || (i + 1 == block.stmts.len() && block.last_expr.is_none())
+                }
+            };
+
+            if semicolon {
+                self.write(";");
+            }
+        }
+
+        if let Some(last_expr) = &block.last_expr {
+            self.newline();
+            self.expr(last_expr, ExprCtx::FULL);
+        }
+
+        self.level -= 1;
+        self.newline();
+        self.write("}");
+    }
+
+    fn stmt(&mut self, stmt: &Statement) {
+        match &stmt.stmt {
+            Stmt::VariableDef {
+                name,
+                mutable,
+                typexpr: Some(typexpr),
+                value,
+                ..
+            } if starts_like_labeled(typexpr) => {
+                // NOTE: `a : { .. } = ..` would be parsed as a labeled block
+                self.write("let ");
+                if *mutable {
+                    self.write("mut ");
+                }
+                self.write(name);
+                self.write(": ");
+                self.expr(
+                    typexpr,
+                    ExprCtx::TYPE.followed_by(Some(Precedence::Assignment)),
+                );
+                self.write(" = ");
+                self.expr(value, ExprCtx::FULL);
+            }
+            Stmt::VariableDef {
+                name,
+                mutable,
+                typexpr,
+                value,
+                ..
+            } => {
+                let sep = if *mutable { '=' } else { ':' };
+                self.write(name);
+
+                match typexpr {
+                    Some(typexpr) => {
+                        self.write(" : ");
+                        let follow = mutable.then_some(Precedence::Assignment);
+                        self.expr(typexpr, ExprCtx::TYPE.followed_by(follow));
+                        write!(self.out, " {sep} ").unwrap();
+                    }
+                    None => write!(self.out, " :{sep} ").unwrap(),
+                }
+
+                self.expr(value, ExprCtx::FULL);
+            }
+            Stmt::Defer { expr } => {
+                self.write("defer ");
+                self.expr(expr, ExprCtx::FULL);
+            }
+            Stmt::Expression(expr) => self.expr(expr, ExprCtx::FULL),
+        }
+    }
+
+    /// Does the expression need parenthesis to be parsed back the same in
+    /// this context?
+    fn needs_parens(&self, expr: &Expression, ctx: ExprCtx) -> bool {
+        let (left, tail) = expr_precedences(expr);
+
+        left < ctx.min
+            || matches!((ctx.follow, tail), (Some(follow), Some(tail)) if follow >= tail)
+            || (matches!(expr.expr, Expr::StructLit { .. }) && (ctx.typexpr || !self.struct_lit))
+            || (ctx.typexpr
+                && matches!(
+                    expr.expr,
+                    Expr::FunDefinition { .. }
+                        | Expr::FunDeclaration { .. }
+                        | Expr::BlockWithLabel { .. }
+                        | Expr::PredicateLoop { label: Some(_), .. }
+                        | Expr::IteratorLoop { label: Some(_), .. }
+                        | Expr::InfiniteLoop { label: Some(_), .. }
+                ))
+    }
+
+    /// Writes the `.` of a member access or a dereference of `expr`, after a
+    /// space if `expr` is an integer literal, `1.*` is lexed as the float
+    /// `1.` followed by `*`.
+    fn write_dot_after(&mut self, expr: &Expression) {
+        if let Expr::IntLit(_, None) = expr.expr {
+            self.write(" ");
+        }
+
+        self.write(".");
+    }
+
+    fn expr(&mut self, expr: &Expression, ctx: ExprCtx) {
+        if self.needs_parens(expr, ctx) {
+            self.write("(");
+            self.with_struct_lit(true, |f| f.expr(expr, ExprCtx::FULL));
+            self.write(")");
+            return;
+        }
+
+        match &expr.expr {
+            Expr::IntLit(int, suffix) => {
+                write!(self.out, "{int}").unwrap();
+                if let Some(suffix) = suffix {
+                    write!(self.out, "{suffix}").unwrap();
+                }
+            }
+            Expr::BoolLit(b) => write!(self.out, "{b}").unwrap(),
+            Expr::StringLit(s) => {
+                self.write("\"");
+                for c in s.chars() {
+                    escape_char(c, '"', &mut self.out);
+                }
+                self.write("\"");
+            }
+            Expr::CharLit(c) => {
+                self.write("'");
+                escape_char(*c, '\'', &mut self.out);
+                self.write("'");
+            }
+            Expr::FloatLit(float, suffix) => {
+                self.write(&float_lit(*float));
+
+                if let Some(suffix) = suffix {
+                    write!(self.out, "{suffix}").unwrap();
+                }
+            }
+            Expr::Grouping(expr) => {
+                self.write("(");
+                self.with_struct_lit(true, |f| f.expr(expr, ExprCtx::FULL));
+                self.write(")");
+            }
+            Expr::Tuple(exprs) => self.with_struct_lit(true, |f| {
+                if let [expr] = exprs.as_slice() {
+                    f.write("(");
+                    f.expr(expr, ExprCtx::FULL);
+                    f.write(",)");
+                } else {
+                    f.list("(", exprs, ")", false, |f, expr| {
+                        f.expr(expr, ExprCtx::FULL)
+                    });
+                }
+            }),
+            Expr::Ident(id) => self.write(id),
+            Expr::Binary { lhs, op, rhs } => {
+                let pr = op.precedence();
+                let (lhs_min, rhs_min) = match pr.associativity() {
+                    Associativity::LeftToRight => (pr, pr.next()),
+                    _ => (pr.next(), pr),
+                };
+
+                self.expr(
+                    lhs,
+                    ExprCtx {
+                        min: lhs_min,
+                        follow: Some(pr),
+                        typexpr: ctx.typexpr,
+                    },
+                );
+                write!(self.out, " {op} ").unwrap();
+                self.expr(rhs, ExprCtx::new(rhs_min, ctx.follow));
+            }
+            Expr::Unary {
+                op: UnaryOp::Dereference,
+                expr,
+            } => {
+                self.expr(
+                    expr,
+                    ExprCtx {
+                        min: Precedence::Call,
+                        follow: Some(Precedence::Primary),
+                        typexpr: ctx.typexpr,
+                    },
+                );
+                self.write_dot_after(expr);
+                self.write("*");
+            }
+            Expr::Unary { op, expr } => {
+                write!(self.out, "{op}").unwrap();
+                self.expr(expr, ExprCtx::new(Precedence::Unary, ctx.follow));
+            }
+            Expr::Cast { expr, typexpr } => {
+                self.expr(
+                    expr,
+                    ExprCtx {
+                        min: Precedence::Cast,
+                        follow: Some(Precedence::Cast),
+                        typexpr: ctx.typexpr,
+                    },
+                );
+                self.write(" as ");
+                self.expr(
+                    typexpr,
+                    ExprCtx {
+                        min: Precedence::Cast.next(),
+                        follow: ctx.follow,
+                        typexpr: true,
+                    },
+                );
+            }
+            Expr::Borrow { mutable, expr } => {
+                self.write(if *mutable { "&mut " } else { "&" });
+                self.expr(expr, ExprCtx::FULL.followed_by(ctx.follow));
+            }
+            Expr::Range {
+                start,
+                end,
+                inclusive,
+            } => {
+                if let Some(start) = start {
+                    self.expr(
+                        start,
+                        ExprCtx {
+                            min: Precedence::Range.next(),
+                            follow: Some(Precedence::Range),
+                            typexpr: ctx.typexpr,
+                        },
+                    );
+                }
+
+                match (end, inclusive) {
+                    (None, _) => self.write(".."),
+                    (Some(_), false) => self.write("..<"),
+                    (Some(_), true) => self.write("..="),
+                }
+
+                if let Some(end) = end {
+                    self.expr(end, ExprCtx::new(Precedence::Range.next(), ctx.follow));
+                }
+            }
+            Expr::FunCall {
+                callee,
+                args,
+                named_args,
+            } => {
+                self.expr(
+                    callee,
+                    ExprCtx {
+                        min: Precedence::Call,
+                        follow: Some(Precedence::Call),
+                        typexpr: ctx.typexpr,
+                    },
+                );
+
+                let args: Vec<CallArg> = args
+                    .iter()
+                    .map(CallArg::Positional)
+                    .chain(named_args.iter().map(CallArg::Named))
+                    .collect();
+
+                self.with_struct_lit(true, |f| {
+                    f.list("(", &args, ")", false, |f, arg| match arg {
+                        CallArg::Positional(expr) => f.expr(expr, ExprCtx::FULL),
+                        CallArg::Named(named) => {
+                            f.write(&named.name);
+                            f.write(": ");
+                            f.expr(&named.value, ExprCtx::FULL);
+                        }
+                    })
+                });
+            }
+            Expr::If(if_expr) => self.if_expr(if_expr),
+            Expr::IfThenElse {
+                cond,
+                true_val,
+                false_val,
+            } => {
+                self.write("if ");
+                self.with_struct_lit(false, |f| f.expr(cond, ExprCtx::FULL));
+                self.write(" then ");
+                self.expr(true_val, ExprCtx::FULL);
+                self.write(" else ");
+                self.expr(false_val, ExprCtx::FULL.followed_by(ctx.follow));
+            }
+            Expr::Block(block) => self.block(block),
+            Expr::BlockWithLabel { label, block } => {
+                write!(self.out, "{}: ", label.0).unwrap();
+                self.block(block);
+            }
+            Expr::PredicateLoop { label, cond, body } => {
+                self.label(label);
+                self.write("while ");
+                self.with_struct_lit(false, |f| f.expr(cond, ExprCtx::FULL));
+                self.write(" ");
+                self.block(body);
+            }
+            Expr::IteratorLoop {
+                label,
+                variable,
+                iterator,
+                body,
+                ..
+            } => {
+                self.label(label);
+                write!(self.out, "for {variable} in ").unwrap();
+                self.with_struct_lit(false, |f| f.expr(iterator, ExprCtx::FULL));
+                self.write(" ");
+                self.block(body);
+            }
+            Expr::InfiniteLoop { label, body } => {
+                self.label(label);
+                self.write("loop ");
+                self.block(body);
+            }
+            Expr::Return { expr } => {
+                self.write("return");
+
+                if let Some(expr) = expr {
+                    self.write(" ");
+                    self.expr(expr, ExprCtx::FULL.followed_by(ctx.follow));
+                }
+            }
+            Expr::Break { label, expr } => {
+                self.write("break");
+
+                if let Some(label) = label {
+                    write!(self.out, " :{label}").unwrap();
+                }
+
+                if let Some(expr) = expr {
+                    self.write(" ");
+                    self.expr(expr, ExprCtx::FULL.followed_by(ctx.follow));
+                }
+            }
+            Expr::Continue { label } => {
+                self.write("continue");
+
+                if let Some(label) = label {
+                    write!(self.out, " :{label}").unwrap();
+                }
+            }
+            Expr::Null => self.write("null"),
+            Expr::Comptime(expr) => {
+                self.write("comptime ");
+                self.expr(expr, ExprCtx::FULL.followed_by(ctx.follow));
+            }
+            Expr::MemberAccess { expr, member } => {
+                self.expr(
+                    expr,
+                    ExprCtx {
+                        min: Precedence::Call,
+                        follow: Some(Precedence::MemberAccess),
+                        typexpr: ctx.typexpr,
+                    },
+                );
+                self.write_dot_after(expr);
+                self.write(member);
+            }
+            Expr::StructLit { typexpr, fields } => {
+                self.expr(
+                    typexpr,
+                    ExprCtx::new(Precedence::Call, Some(Precedence::Call)),
+                );
+                self.write(" ");
+                self.with_struct_lit(true, |f| {
+                    f.list("{", fields, "}", true, |f, field| {
+                        f.write(&field.name);
+                        f.write(": ");
+                        f.expr(&field.value, ExprCtx::FULL);
+                    })
+                });
+            }
+            Expr::Orb => self.write("orb"),
+            Expr::Match { expr, arms } => {
+                self.write("match ");
+                self.with_struct_lit(false, |f| f.expr(expr, ExprCtx::FULL));
+                self.write(" {");
+
+                if !arms.is_empty() {
+                    self.level += 1;
+                    for (i, arm) in arms.iter().enumerate() {
+                        self.newline();
+                        self.match_arm(arm, arms.get(i + 1));
+                    }
+                    self.level -= 1;
+                    self.newline();
+                }
+
+                self.write("}");
+            }
+            Expr::FunDefinition {
+                args,
+                rettypexpr,
+                body,
+            } => {
+                let ret_type = |f: &mut SourceFormatter| {
+                    if let Some(ret) = rettypexpr {
+                        f.write(" -> ");
+                        f.expr(ret, ExprCtx::TYPE);
+                    }
+                };
+                // NOTE: keep the room for the return type and the ` {` of
+                // the body on the line of the arguments
+                let reserve = self.render(true, ret_type).chars().count() + 2;
+
+                self.write("fun");
+                self.list_reserving("(", args, ")", false, reserve, |f, arg| f.arg(arg));
+
+                ret_type(self);
+                self.write(" ");
+                self.block(body);
+            }
+            Expr::FunDeclaration { args, rettypexpr } => {
+                self.write("fun");
+                self.list("(", args, ")", false, |f, arg| f.expr(arg, ExprCtx::TYPE));
+
+                if let Some(ret) = rettypexpr {
+                    self.write(" -> ");
+                    self.expr(ret, ExprCtx::TYPE.followed_by(ctx.follow));
+                }
+            }
+            Expr::PointerType { mutable, typexpr } => {
+                self.write(if *mutable { "*mut " } else { "*" });
+                self.expr(typexpr, ExprCtx::TYPE.followed_by(ctx.follow));
+            }
+            Expr::FunPtrType { args, ret } => {
+                self.write("*fun");
+                self.list("(", args, ")", false, |f, arg| f.expr(arg, ExprCtx::TYPE));
+
+                if let Some(ret) = ret {
+                    self.write(" -> ");
+                    self.expr(ret, ExprCtx::TYPE.followed_by(ctx.follow));
+                }
+            }
+            Expr::Struct { fields } => {
+                self.write("struct ");
+                self.list("{", fields, "}", true, |f, field| {
+                    f.write(&field.name);
+                    f.write(": ");
+                    f.expr(&field.typexpr, ExprCtx::TYPE);
+                });
+            }
+            Expr::Enum { variants } => {
+                self.write("enum ");
+                self.list("{", variants, "}", true, |f, variant| {
+                    f.write(&variant.name);
+
+                    if let Some(value) = &variant.value {
+                        f.write(" = ");
+                        f.with_struct_lit(true, |f| f.expr(value, ExprCtx::FULL));
+                    }
+                });
+            }
+        }
+    }
+
+    fn label(&mut self, label: &Option<(String, lunc_utils::Span)>) {
+        if let Some((label, _)) = label {
+            write!(self.out, "{label}: ").unwrap();
+        }
+    }
+
+    fn if_expr(&mut self, if_expr: &IfExpression) {
+        self.write("if ");
+        self.with_struct_lit(false, |f| f.expr(&if_expr.cond, ExprCtx::FULL));
+        self.write(" ");
+        self.block(&if_expr.body);
+
+        match if_expr.else_br.as_deref() {
+            Some(Else::IfExpr(else_if)) => {
+                self.write(" else ");
+                self.if_expr(else_if);
+            }
+            Some(Else::Block(block)) => {
+                self.write(" else ");
+                self.block(block);
+            }
+            None => {}
+        }
+    }
+
+    fn match_arm(&mut self, arm: &MatchArm, next: Option<&MatchArm>) {
+        for (i, pat) in arm.pats.iter().enumerate() {
+            if i != 0 {
+                self.write(" | ");
+            }
+
+            // NOTE: the patterns are parsed with a precedence higher than the
+            // bitwise or, that separates them
+            let follow = (i + 1 != arm.pats.len()).then_some(Precedence::BitwiseOr);
+            self.expr(pat, ExprCtx::new(Precedence::BitwiseXor, follow));
+        }
+
+        self.write(" => ");
+        self.with_struct_lit(true, |f| f.expr(&arm.body, ExprCtx::FULL));
+
+        let next_continues = next
+            .and_then(|next| next.pats.first())
+            .is_some_and(expr_continues_expr);
+
+        if !arm.body.is_expr_with_block() || next_continues {
+            self.write(",");
+        }
+    }
+
+    fn arg(&mut self, arg: &Arg) {
+        if arg.comptime {
+            self.write("comptime ");
+        }
+
+        self.write(&arg.name);
+        self.write(": ");
+
+        let follow = arg.default.as_ref().map(|_| Precedence::Assignment);
+        self.expr(&arg.typexpr, ExprCtx::TYPE.followed_by(follow));
+
+        if let Some(default) = &arg.default {
+            self.write(" = ");
+            self.expr(default, ExprCtx::FULL);
+        }
+    }
+}
+
+impl ToSource for Module {
+    fn fmt_source(&self, f: &mut SourceFormatter) {
+        let has_decls = self.package.is_some() || !self.imports.is_empty();
+
+        if let Some(package) = &self.package {
+            write!(f.out, "{} {};", PackageDecl::KEYWORD, package.name).unwrap();
+        }
+
+        for (i, ImportDecl { path, alias, .. }) in self.imports.iter().enumerate() {
+            // NOTE: the imports are grouped together, the package declaration
+            // is separated from them by an empty line
+            if i == 0 && self.package.is_some() {
+                f.write("\n");
+            }
+            if i != 0 || self.package.is_some() {
+                f.newline();
+            }
+
+            write!(f.out, "{} \"", ImportDecl::KEYWORD).unwrap();
+            for c in path.chars() {
+                escape_char(c, '"', &mut f.out);
+            }
+            f.write("\"");
+
+            if let Some(alias) = alias {
+                write!(f.out, " as {alias}").unwrap();
+            }
+
+            f.write(";");
+        }
+
+        if has_decls && !self.items.is_empty() {
+            f.write("\n");
+            f.newline();
+        }
+
+        for (i, item) in self.items.iter().enumerate() {
+            if i != 0 {
+                // NOTE: the directives are grouped together, the other items
+                // are separated by an empty line
+                let grouped = matches!(
+                    (&self.items[i - 1], item),
+                    (Item::Directive(_), Item::Directive(_))
+                );
+
+                if !grouped {
+                    f.write("\n");
+                }
+                f.newline();
+            }
+
+            f.item(item);
+        }
+
+        if has_decls || !self.items.is_empty() {
+            f.newline();
+        }
+    }
+}
+
+impl ToSource for Item {
+    fn fmt_source(&self, f: &mut SourceFormatter) {
+        f.item(self);
+    }
+}
+
+impl ToSource for Block {
+    fn fmt_source(&self, f: &mut SourceFormatter) {
+        f.block(self);
+    }
+}
+
+impl ToSource for Statement {
+    fn fmt_source(&self, f: &mut SourceFormatter) {
+        f.stmt(self);
+    }
+}
+
+impl ToSource for Expression {
+    fn fmt_source(&self, f: &mut SourceFormatter) {
+        f.expr(self, ExprCtx::FULL);
+    }
+}
+
+/// Returns the precedence of the expression as seen from its left, and the
+/// lowest precedence of an operator that its rightmost operand would take if
+/// it followed the expression, `None` if it takes no operator.
+fn expr_precedences(expr: &Expression) -> (Precedence, Option<Precedence>) {
+    match &expr.expr {
+        Expr::Binary { op, .. } => {
+            let pr = op.precedence();
+            let tail = match pr.associativity() {
+                Associativity::LeftToRight => pr.next(),
+                _ => pr,
+            };
+
+            (pr, Some(tail))
+        }
+        Expr::Cast { .. } => (Precedence::Cast, Some(Precedence::Cast.next())),
+        Expr::Range { start, end, .. } => {
+            let left = if start.is_some() {
+                Precedence::Range
+            } else {
+                Precedence::Primary
+            };
+
+            (left, end.as_ref().map(|_| Precedence::Range.next()))
+        }
+        Expr::Unary {
+            op: UnaryOp::Dereference,
+            ..
+        }
+        | Expr::FunCall { .. }
+        | Expr::MemberAccess { .. }
+        | Expr::StructLit { .. } => (Precedence::Call, None),
+        Expr::Unary { .. } => (Precedence::Primary, Some(Precedence::Unary)),
+        Expr::Borrow { .. }
+        | Expr::Comptime(_)
+        | Expr::IfThenElse { .. }
+        | Expr::Return { expr: Some(_) }
+        | Expr::Break { expr: Some(_), .. } => (Precedence::Primary, Some(HIGHEST_PRECEDENCE)),
+        Expr::PointerType { .. }
+        | Expr::FunPtrType { ret: Some(_), .. }
+        | Expr::FunDeclaration {
+            rettypexpr: Some(_),
+            ..
+        } => (Precedence::Primary, Some(Precedence::LogicalOr)),
+        _ => (Precedence::Primary, None),
+    }
+}
+
+/// Returns the leftmost operand of the expression, the one printed first.
+fn leftmost(expr: &Expression) -> &Expression {
+    match &expr.expr {
+        Expr::Binary { lhs: expr, .. }
+        | Expr::Cast { expr, .. }
+        | Expr::Range {
+            start: Some(expr), ..
+        }
+        | Expr::FunCall { callee: expr, .. }
+        | Expr::MemberAccess { expr, .. }
+        | Expr::StructLit { typexpr: expr, .. }
+        | Expr::Unary {
+            op: UnaryOp::Dereference,
+            expr,
+        } => leftmost(expr),
+        _ => expr,
+    }
+}
+
+/// Does the expression start with a token that would continue an expression
+/// with block before it, like `(` that would call it?
+fn expr_continues_expr(expr: &Expression) -> bool {
+    matches!(
+        leftmost(expr).expr,
+        Expr::Grouping(_)
+            | Expr::Tuple(_)
+            | Expr::Unary {
+                op: UnaryOp::Negation,
+                ..
+            }
+            | Expr::Borrow { .. }
+            | Expr::Range { start: None, .. }
+            | Expr::PointerType { .. }
+            | Expr::FunPtrType { .. }
+    )
+}
+
+/// Like [`expr_continues_expr`] but for a statement.
+fn stmt_continues_expr(stmt: &Statement) -> bool {
+    matches!(&stmt.stmt, Stmt::Expression(expr) if expr_continues_expr(expr))
+}
+
+/// Does the type expression starts like a labeled expression would after the
+/// name of a short variable definition, see [`Parser::is_short_variable_def`]
+///
+/// [`Parser::is_short_variable_def`]: crate::Parser::is_short_variable_def
+fn starts_like_labeled(typexpr: &Expression) -> bool {
+    matches!(
+        leftmost(typexpr).expr,
+        Expr::Block(_)
+            | Expr::PredicateLoop { label: None, .. }
+            | Expr::IteratorLoop { label: None, .. }
+            | Expr::InfiniteLoop { label: None, .. }
+    )
+}
+
+/// Formats a float so that the lexer reads it back to the same value, in
+/// decimal if it can, or else as an hexadecimal float.
+fn float_lit(float: f64) -> String {
+    if float.is_infinite() {
+        // NOTE: it is how the lexer produces an infinite float
+        return "1e999".to_string();
+    }
+
+    // NOTE: the lexer isn't always exact, so we try the shortest decimal
+    // first and then more and more precise ones. The debug format always has
+    // a `.` or an exponent.
+    let shortest = format!("{float:?}");
+    let candidates =
+        std::iter::once(shortest).chain((1..=17).map(|prec| format!("{float:.prec$e}")));
+
+    for decimal in candidates {
+        if lex_float(&decimal) == Some(float.to_bits()) {
+            return decimal;
+        }
+    }
+
+    let bits = float.to_bits();
+    let exponent = ((bits >> 52) & 0x7ff) as i32;
+    let mantissa = bits & ((1 << 52) - 1);
+
+    if exponent == 0 {
+        // subnormal float
+        format!("0x0.{mantissa:013x}p-1022")
+    } else {
+        format!("0x1.{mantissa:013x}p{}", exponent - 1023)
+    }
+}
+
+/// Lexes a float literal and returns the bits of its value.
+fn lex_float(source: &str) -> Option<u64> {
+    let sink = DiagnosticSink::new();
+    let fid = sink.register_file(String::new(), source.to_string());
+    let tokenstream = Lexer::new(sink.clone(), source.to_string(), fid).produce()?;
+
+    match tokenstream.get(0).map(|tok| &tok.tt) {
+        Some(TokenType::FloatLit(float)) if !sink.failed() => Some(float.to_bits()),
+        _ => None,
+    }
+}
+
+/// Pushes the character `c` of a literal delimited by `quote` to `out`,
+/// escaped if needed.
+fn escape_char(c: char, quote: char, out: &mut String) {
+    match c {
+        '\\' => out.push_str("\\\\"),
+        '\0' => out.push_str("\\0"),
+        '\n' => out.push_str("\\n"),
+        '\r' => out.push_str("\\r"),
+        '\t' => out.push_str("\\t"),
+        '\x0B' => out.push_str("\\v"),
+        '\x0C' => out.push_str("\\f"),
+        '\x07' => out.push_str("\\a"),
+        '\x08' => out.push_str("\\b"),
+        '\x1B' => out.push_str("\\e"),
+        // NOTE: there is no escape for the quotes, so we use their hex escape
+        c if c == quote => write!(out, "\\x{:02x}", c as u32).unwrap(),
+        c if c.is_control() => write!(out, "\\u{{{:x}}}", c as u32).unwrap(),
+        c => out.push(c),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path, thread};
+
+    use lunc_utils::{Span, pretty::PrettyDump};
+
+    use super::*;
+    use crate::{expr::BinOp, parse};
+
+    /// Removes the locations from a dump of the AST, they change when the
+    /// source is formatted.
+    fn strip_locs(dump: &str) -> String {
+        let mut out = String::new();
+        let mut rest = dump;
+
+        while let Some(i) = rest.find(" (fid = ") {
+            let head = &rest[..i];
+            let start = head
+                .trim_end_matches(|c: char| c.is_ascii_digit() || c == '.')
+                .len();
+            out.push_str(&head[..start]);
+
+            let end = i + rest[i..].find(')').unwrap() + 1;
+            rest = &rest[end..];
+        }
+        out.push_str(rest);
+
+        out
+    }
+
+    fn dump(module: &Module) -> String {
+        let mut out = Vec::new();
+        module.dump_to(&mut out);
+
+        strip_locs(&String::from_utf8(out).unwrap())
+    }
+
+    fn lun_files(dir: &Path, files: &mut Vec<std::path::PathBuf>) {
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+
+            if path.is_dir() {
+                lun_files(&path, files);
+            } else if path.extension().is_some_and(|ext| ext == "lun") {
+                files.push(path);
+            }
+        }
+    }
+
+    fn expr(expr: Expr) -> Expression {
+        Expression {
+            expr,
+            loc: Span::ZERO,
+        }
+    }
+
+    fn binary(lhs: Expr, op: BinOp, rhs: Expr) -> Expr {
+        Expr::Binary {
+            lhs: Box::new(expr(lhs)),
+            op,
+            rhs: Box::new(expr(rhs)),
+        }
+    }
+
+    /// Parenthesis are added where the AST can't be printed without them.
+    #[test]
+    fn needed_parens() {
+        let int = |i| Expr::IntLit(i, None);
+        let id = |id: &str| Expr::Ident(id.to_string());
+
+        let mul = binary(binary(int(1), BinOp::Add, int(2)), BinOp::Mul, int(3));
+        assert_eq!(expr(mul).to_string(), "(1 + 2) * 3");
+
+        let sub = binary(int(1), BinOp::Sub, binary(int(2), BinOp::Sub, int(3)));
+        assert_eq!(expr(sub).to_string(), "1 - (2 - 3)");
+
+        let assign = binary(
+            id("a"),
+            BinOp::Assignment,
+            binary(id("b"), BinOp::Assignment, id("c")),
+        );
+        assert_eq!(expr(assign).to_string(), "a = b = c");
+
+        let call = Expr::FunCall {
+            callee: Box::new(expr(Expr::Unary {
+                op: UnaryOp::Negation,
+                expr: Box::new(expr(id("f"))),
+            })),
+            args: Vec::new(),
+            named_args: Vec::new(),
+        };
+        assert_eq!(expr(call).to_string(), "(-f)()");
+
+        let deref = binary(
+            id("a"),
+            BinOp::Add,
+            Expr::Unary {
+                op: UnaryOp::Dereference,
+                expr: Box::new(expr(int(1))),
+            },
+        );
+        assert_eq!(expr(deref).to_string(), "a + 1 .*");
+    }
+
+    /// Every test that parses must be parsed back to the same AST once
+    /// formatted.
+    #[test]
+    fn round_trip_tests() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests");
+        let mut files = Vec::new();
+        lun_files(&dir, &mut files);
+        files.sort();
+
+        for path in files {
+            let source = fs::read_to_string(&path).unwrap();
+
+            // the compiler parses on the main thread, give the test the same
+            // stack size
+            thread::Builder::new()
+                .stack_size(8 * 1024 * 1024)
+                .spawn(move || {
+                    let Ok(module) = parse(&source) else {
+                        return;
+                    };
+
+                    let formatted = module.to_string();
+                    let reparsed = parse(&formatted).unwrap_or_else(|_| {
+                        panic!(
+                            "{} failed to parse once formatted:\n{formatted}",
+                            path.display()
+                        )
+                    });
+
+                    assert_eq!(
+                        dump(&module),
+                        dump(&reparsed),
+                        "{} changed once formatted:\n{formatted}",
+                        path.display()
+                    );
+                    assert_eq!(
+                        formatted,
+                        reparsed.to_string(),
+                        "formatting {} is not idempotent",
+                        path.display()
+                    );
+                })
+                .unwrap()
+                .join()
+                .unwrap();
+        }
+    }
+}
@@ -7,7 +7,7 @@ use lunc_utils::opt_unreachable;
 
 use crate::{
     directive::{Directive, parse_import_directive, parse_mod_directive},
-    expr::parse_typexpr,
+    expr::{Arg, parse_typexpr},
 };
 
 use super::*;
@@ -21,14 +21,30 @@ pub enum Vis {
 }
 
 /// Lun program.
+///
+/// `[ package ] ( import )* ( item )*`
 #[derive(Debug, Clone)]
 pub struct Module {
+    pub package: Option<PackageDecl>,
+    pub imports: Vec<ImportDecl>,
     pub items: Vec<Item>,
     pub fid: FileId,
 }
 
 impl AstNode for Module {
     fn parse(parser: &mut Parser) -> Result<Self, Diagnostic> {
+        let package = if parser.is_package_decl() {
+            Some(parse!(parser => PackageDecl))
+        } else {
+            None
+        };
+
+        let mut imports = Vec::new();
+
+        while parser.is_import_decl() {
+            imports.push(parse!(parser => ImportDecl));
+        }
+
         let mut items = Vec::new();
 
         loop {
@@ -40,12 +56,113 @@ impl AstNode for Module {
         }
 
         Ok(Module {
+            package,
+            imports,
             items,
             fid: parser.fid,
         })
     }
 }
 
+/// Package declaration, the name of the module of a file when it is imported.
+///
+/// `"package" ident ";"`
+#[derive(Debug, Clone)]
+pub struct PackageDecl {
+    pub name: String,
+    pub loc: Span,
+}
+
+impl PackageDecl {
+    /// The contextual keyword of the declaration.
+    pub const KEYWORD: &str = "package";
+}
+
+impl AstNode for PackageDecl {
+    fn parse(parser: &mut Parser) -> Result<Self, Diagnostic> {
+        // TEST: n/a
+        let (_, lo) = expect_token!(parser => [Ident(id), (), if id.as_str() == PackageDecl::KEYWORD], Ident(String::new()));
+
+        // TEST: n/a
+        let (name, _) = expect_token!(parser => [Ident(id), id.clone()], Ident(String::new()));
+
+        // TEST: no. 1
+        let (_, hi) = expect_token!(parser => [Punct(Punctuation::Semicolon), ()], Punct(Punctuation::Semicolon));
+
+        Ok(PackageDecl {
+            name,
+            loc: Span::from_ends(lo, hi),
+        })
+    }
+}
+
+/// Import declaration, loads the file at `path`, relative to the file of the
+/// declaration, as a module named after its package.
+///
+/// `"import" string [ "as" ident ] ";"`
+#[derive(Debug, Clone)]
+pub struct ImportDecl {
+    pub path: String,
+    pub alias: Option<String>,
+    /// name of the package of the imported file, it is set once the file is
+    /// loaded by the driver.
+    pub package: Option<String>,
+    pub loc: Span,
+}
+
+impl ImportDecl {
+    /// The contextual keyword of the declaration.
+    pub const KEYWORD: &str = "import";
+}
+
+impl AstNode for ImportDecl {
+    fn parse(parser: &mut Parser) -> Result<Self, Diagnostic> {
+        // TEST: n/a
+        let (_, lo) = expect_token!(parser => [Ident(id), (), if id.as_str() == ImportDecl::KEYWORD], Ident(String::new()));
+
+        // TEST: n/a
+        let (path, _) =
+            expect_token!(parser => [StringLit(s), s.clone()], StringLit(String::new()));
+
+        let alias = if let Some(Kw(Keyword::As)) = parser.peek_tt() {
+            parser.pop();
+            // TEST: no. 1
+            let alias =
+                expect_token!(noloc: parser => [Ident(id), id.clone()], Ident(String::new()));
+
+            Some(alias)
+        } else {
+            None
+        };
+
+        // TEST: no. 2
+        let (_, hi) = expect_token!(parser => [Punct(Punctuation::Semicolon), ()], Punct(Punctuation::Semicolon));
+
+        Ok(ImportDecl {
+            path,
+            alias,
+            package: None,
+            loc: Span::from_ends(lo, hi),
+        })
+    }
+}
+
+impl Parser {
+    /// Is it a package declaration, `package` is only a keyword when it is
+    /// followed by the name of the package.
+    pub fn is_package_decl(&self) -> bool {
+        matches!(self.peek_tt(), Some(Ident(id)) if id == PackageDecl::KEYWORD)
+            && matches!(self.nth_tt(1), Some(Ident(_)))
+    }
+
+    /// Is it an import declaration, `import` is only a keyword when it is
+    /// followed by the path of the file.
+    pub fn is_import_decl(&self) -> bool {
+        matches!(self.peek_tt(), Some(Ident(id)) if id == ImportDecl::KEYWORD)
+            && matches!(self.nth_tt(1), Some(StringLit(_)))
+    }
+}
+
 /// ABI names usable in an extern block
 #[derive(Debug, Clone, Default)]
 pub enum Abi {
@@ -69,9 +186,10 @@ impl FromStr for Abi {
 pub enum Item {
     /// Global constant.
     ///
-    /// `ident ":" expression? ":" exprWithBlock`
-    /// `ident ":" expression? ":" exprWithoutBlock ";"`
+    /// `"pub"? ident ":" expression? ":" exprWithBlock`
+    /// `"pub"? ident ":" expression? ":" exprWithoutBlock ";"`
     GlobalConst {
+        vis: Vis,
         name: String,
         name_loc: Span,
         typexpr: Option<Expression>,
@@ -80,9 +198,10 @@ pub enum Item {
     },
     /// Global variable.
     ///
-    /// `ident ":" expression? "=" exprWithBlock`
-    /// `ident ":" expression? "=" exprWithoutBlock ";"`
+    /// `"pub"? ident ":" expression? "=" exprWithBlock`
+    /// `"pub"? ident ":" expression? "=" exprWithoutBlock ";"`
     GlobalVar {
+        vis: Vis,
         name: String,
         name_loc: Span,
         typexpr: Option<Expression>,
@@ -91,8 +210,9 @@ pub enum Item {
     },
     /// Global uninitialized
     ///
-    /// `ident ":" expression ";"`
+    /// `"pub"? ident ":" expression ";"`
     GlobalUninit {
+        vis: Vis,
         name: String,
         name_loc: Span,
         typexpr: Expression,
@@ -106,16 +226,43 @@ pub enum Item {
         items: Vec<Item>,
         loc: Span,
     },
+    /// Impl block, attaches definitions to a type.
+    ///
+    /// `"impl" ( "(" ( ident ":" expr ),* ")" )? expr "{" ( "pub"? item )* "}"`
+    Impl {
+        params: Vec<Arg>,
+        target: Expression,
+        defs: Vec<ImplDef>,
+        loc: Span,
+    },
     /// A directive, always starts with `#`
     Directive(Directive),
 }
 
+/// A definition inside of an impl block, with its visibility.
+#[derive(Debug, Clone)]
+pub struct ImplDef {
+    pub vis: Vis,
+    pub item: Item,
+}
+
 impl AstNode for Item {
     fn parse(parser: &mut Parser) -> Result<Self, Diagnostic> {
         match parser.peek_tt() {
-            Some(Ident(_)) => parse_global_item(parser),
+            Some(Ident(_)) => parse_global_item(parser, Vis::Private),
+            Some(Kw(Keyword::Pub)) => parse_pub_item(parser),
             Some(Punct(Punctuation::Hashtag)) => parse_directive_item(parser),
             Some(Kw(Keyword::Extern)) => parse_extern_block_item(parser),
+            Some(Kw(Keyword::Impl)) => parse_impl_item(parser),
+            Some(tt) if starts_statement(tt) => {
+                let t = parser.peek_tok().unwrap().clone();
+                // TEST: no. 3
+                Err(StatementAtTopLevel {
+                    found: t.tt,
+                    loc: t.loc,
+                }
+                .into_diag())
+            }
             Some(_) => {
                 let t = parser.peek_tok().unwrap().clone();
                 // TEST: no. 1
@@ -126,7 +273,61 @@ impl AstNode for Item {
     }
 }
 
-pub fn parse_global_item(parser: &mut Parser) -> Result<Item, Diagnostic> {
+/// Returns true if the token type can only start a statement or an expression,
+/// used to emit a targeted diagnostic when code is written outside of a
+/// function body.
+fn starts_statement(tt: &TokenType) -> bool {
+    matches!(
+        tt,
+        Kw(Keyword::Let
+            | Keyword::Return
+            | Keyword::Break
+            | Keyword::Continue
+            | Keyword::Defer
+            | Keyword::If
+            | Keyword::While
+            | Keyword::For
+            | Keyword::Loop
+            | Keyword::Match
+            | Keyword::Fun
+            | Keyword::True
+            | Keyword::False
+            | Keyword::Null)
+            | IntLit(_)
+            | SpecializedIntLit { .. }
+            | FloatLit(_)
+            | SpecializedFloatLit { .. }
+            | StringLit(_)
+            | SpecializedStringLit { .. }
+            | CharLit(_)
+            | Punct(
+                Punctuation::LBrace
+                    | Punctuation::LParen
+                    | Punctuation::Minus
+                    | Punctuation::Bang
+                    | Punctuation::Star
+                    | Punctuation::Ampsand
+            )
+    )
+}
+
+/// Parses a global definition prefixed by `pub`.
+pub fn parse_pub_item(parser: &mut Parser) -> Result<Item, Diagnostic> {
+    // TEST: n/a
+    expect_token!(parser => [Kw(Keyword::Pub), ()], Kw(Keyword::Pub));
+
+    match parser.peek_tt() {
+        Some(Ident(_)) => parse_global_item(parser, Vis::Public),
+        Some(_) => {
+            let t = parser.peek_tok().unwrap().clone();
+            // TEST: no. 5
+            Err(ExpectedToken::new("definition", t.tt, None::<String>, t.loc).into_diag())
+        }
+        None => Err(parser.eof_diag()),
+    }
+}
+
+pub fn parse_global_item(parser: &mut Parser, vis: Vis) -> Result<Item, Diagnostic> {
     // TEST: n/a
     let (name, lo) = expect_token!(parser => [Ident(id), id.clone()], Ident(String::new()));
 
@@ -159,6 +360,7 @@ pub fn parse_global_item(parser: &mut Parser) -> Result<Item, Diagnostic> {
             let hi = expect_token!(parser => [Punct(Punctuation::Semicolon), ()], Punctuation::Semicolon).1;
 
             return Ok(Item::GlobalUninit {
+                vis,
                 name,
                 name_loc: lo.clone(),
                 typexpr,
@@ -186,6 +388,7 @@ pub fn parse_global_item(parser: &mut Parser) -> Result<Item, Diagnostic> {
 
     if is_const {
         Ok(Item::GlobalConst {
+            vis,
             name,
             name_loc: lo,
             typexpr,
@@ -194,6 +397,7 @@ pub fn parse_global_item(parser: &mut Parser) -> Result<Item, Diagnostic> {
         })
     } else {
         Ok(Item::GlobalVar {
+            vis,
             name,
             name_loc: lo,
             typexpr,
@@ -275,3 +479,89 @@ pub fn parse_extern_block_item(parser: &mut Parser) -> Result<Item, Diagnostic>
         loc: Span::from_ends(lo, hi),
     })
 }
+
+pub fn parse_impl_item(parser: &mut Parser) -> Result<Item, Diagnostic> {
+    // TEST: n/a
+    let (_, lo) = expect_token!(parser => [Kw(Keyword::Impl), ()], Kw(Keyword::Impl));
+
+    let mut params = Vec::new();
+
+    if let Some(Punct(Punctuation::LParen)) = parser.peek_tt() {
+        parser.pop();
+
+        loop {
+            if let Some(Punct(Punctuation::RParen)) = parser.peek_tt() {
+                break;
+            }
+
+            // TEST: no. 1
+            let (name, lo_param) =
+                expect_token!(parser => [Ident(id), id.clone()], Ident(String::new()));
+
+            // TEST: no. 2
+            expect_token!(parser => [Punct(Punctuation::Colon), ()], Punct(Punctuation::Colon));
+
+            let typexpr = parse!(@fn parser => parse_typexpr);
+
+            params.push(Arg {
+                // NOTE: the parameters of an impl block are always known at
+                // compile-time
+                comptime: true,
+                name,
+                name_loc: lo_param.clone(),
+                typexpr: typexpr.clone(),
+                default: None,
+                loc: Span::from_ends(lo_param, typexpr.loc),
+            });
+
+            // TEST: n/a
+            expect_token!(parser => [Punct(Punctuation::Comma), (); Punct(Punctuation::RParen), (), in break], Punct(Punctuation::Comma));
+        }
+
+        // TEST: n/a
+        expect_token!(parser => [Punct(Punctuation::RParen), ()], Punct(Punctuation::RParen));
+    }
+
+    let target = parse!(@fn parser => parse_typexpr);
+
+    // TEST: no. 3
+    expect_token!(parser => [Punct(Punctuation::LBrace), ()], Punct(Punctuation::LBrace));
+
+    let mut defs = Vec::new();
+
+    loop {
+        let vis = match parser.peek_tt() {
+            Some(Punct(Punctuation::RBrace)) => break,
+            Some(Kw(Keyword::Pub)) => {
+                parser.pop();
+                Vis::Public
+            }
+            _ => Vis::Private,
+        };
+
+        let item = match parser.peek_tt() {
+            Some(Ident(_)) => parse!(@fn parser => parse_global_item, vis.clone()),
+            Some(_) => {
+                let t = parser.peek_tok().unwrap().clone();
+                // TEST: no. 4
+                return Err(
+                    ExpectedToken::new("definition", t.tt, None::<String>, t.loc).into_diag(),
+                );
+            }
+            None => return Err(parser.eof_diag()),
+        };
+
+        defs.push(ImplDef { vis, item });
+    }
+
+    // TEST: n/a
+    let (_, hi) =
+        expect_token!(parser => [Punct(Punctuation::RBrace), ()], Punct(Punctuation::RBrace));
+
+    Ok(Item::Impl {
+        params,
+        target,
+        defs,
+        loc: Span::from_ends(lo, hi),
+    })
+}
@@ -3,12 +3,13 @@
     html_logo_url = "https://raw.githubusercontent.com/lunprog/lun/main/logo/logo_no_bg_black.png"
 )]
 
-use std::fmt::Debug;
+use std::{fmt::Debug, mem};
 
 use diags::*;
 use expr::Expression;
 use item::Module;
 use lunc_diag::{Diagnostic, DiagnosticSink, FileId, ReachedEOF, ToDiagnostic};
+use lunc_lexer::Lexer;
 
 use lunc_utils::{
     Span,
@@ -22,6 +23,7 @@ use lunc_utils::{
 pub mod diags;
 pub mod directive;
 pub mod expr;
+pub mod format;
 pub mod item;
 pub mod pretty;
 pub mod stmt;
@@ -36,6 +38,12 @@ pub struct Parser {
     sink: DiagnosticSink,
     /// file id of the file we are currently parsing
     fid: FileId,
+    /// are struct literals allowed in the expression we are parsing, see
+    /// [`Parser::with_struct_lit`]
+    struct_lit: bool,
+    /// how many expressions and blocks we are currently nested in, see
+    /// [`Parser::nested`]
+    depth: usize,
 }
 
 impl Parser {
@@ -46,9 +54,70 @@ impl Parser {
             ti: 0,
             sink,
             fid,
+            struct_lit: true,
+            depth: 0,
         }
     }
 
+    /// Calls `f` with struct literals allowed or not, and then restores the
+    /// previous state.
+    ///
+    /// Struct literals are not allowed in the condition of an `if` or a
+    /// `while` and in the iterator of a `for`, because in `if a { .. }` the
+    /// body of the if would be parsed as a struct literal.
+    pub fn with_struct_lit<T>(&mut self, allowed: bool, f: impl FnOnce(&mut Parser) -> T) -> T {
+        let previous = mem::replace(&mut self.struct_lit, allowed);
+        let res = f(self);
+        self.struct_lit = previous;
+
+        res
+    }
+
+    /// Calls `f` one level of nesting deeper, fails instead if the parser is
+    /// already nested [`MAX_EXPR_DEPTH`] times, so that a deeply nested input
+    /// is an error and doesn't overflow the stack.
+    ///
+    /// [`MAX_EXPR_DEPTH`]: expr::MAX_EXPR_DEPTH
+    pub fn nested<T>(
+        &mut self,
+        f: impl FnOnce(&mut Parser) -> Result<T, Diagnostic>,
+    ) -> Result<T, Diagnostic> {
+        self.deeper()?;
+        let res = f(self);
+        self.depth -= 1;
+
+        res
+    }
+
+    /// Increases the nesting depth by one level, fails if the parser is
+    /// already nested [`MAX_EXPR_DEPTH`] times.
+    ///
+    /// [`MAX_EXPR_DEPTH`]: expr::MAX_EXPR_DEPTH
+    pub fn deeper(&mut self) -> Result<(), Diagnostic> {
+        if self.depth >= expr::MAX_EXPR_DEPTH {
+            let loc = match self.peek_tok() {
+                Some(tok) => tok.loc.clone(),
+                None => return Err(self.eof_diag()),
+            };
+
+            return Err(ExpressionTooDeeplyNested {
+                max: expr::MAX_EXPR_DEPTH,
+                loc,
+            }
+            .into_diag());
+        }
+
+        self.depth += 1;
+
+        Ok(())
+    }
+
+    /// Are struct literals allowed at this point?
+    #[inline]
+    pub fn struct_lit_allowed(&self) -> bool {
+        self.struct_lit
+    }
+
     /// Pops a tokens of the stream
     ///
     /// If there is no more tokens in the stream, it will not increment the
@@ -84,11 +153,15 @@ impl Parser {
         self.peek_tok().map(|t| &t.tt)
     }
 
-    /// Returns true if the next token the end of a statement or chunk.
+    /// Returns true if the next token the end of a statement or chunk, like
+    /// the comma that ends the arm of a match.
     pub fn is_stmt_end(&self) -> bool {
         matches!(
             self.peek_tt(),
-            Some(Kw(Keyword::Else) | Punct(Punctuation::Semicolon | Punctuation::RBrace))
+            Some(
+                Kw(Keyword::Else)
+                    | Punct(Punctuation::Semicolon | Punctuation::RBrace | Punctuation::Comma)
+            )
         )
     }
 
@@ -122,6 +195,26 @@ impl Parser {
     }
 }
 
+/// Lexes and parses `source` as a single module.
+///
+/// Unlike [`Parser::produce`] it doesn't need a sink, the diagnostics are
+/// returned if the lexing or the parsing failed. This function never panics,
+/// whatever the input is.
+pub fn parse(source: &str) -> Result<Module, Vec<Diagnostic>> {
+    let sink = DiagnosticSink::new();
+    let fid = sink.register_file("<input>".to_string(), source.to_string());
+
+    let mut lexer = Lexer::new(sink.clone(), source.to_string(), fid);
+    let module = lexer
+        .produce()
+        .and_then(|tokstream| Parser::new(tokstream, sink.clone(), fid).produce());
+
+    match module {
+        Some(module) if !sink.failed() => Ok(module),
+        _ => Err(sink.diagnostics()),
+    }
+}
+
 /// A node of the AST that can be parsed.
 pub trait AstNode: Debug + PrettyDump {
     /// parse the node with the given parser and returns the node.
@@ -206,3 +299,33 @@ macro_rules! parse {
         $parsing_fn($parser $(, $arg)*)?
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path, thread};
+
+    use super::*;
+
+    /// Inputs that used to crash the parser, they must now fail with
+    /// diagnostics instead.
+    #[test]
+    fn fuzz_regressions() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../fuzz/regressions");
+
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            let source = fs::read_to_string(&path).unwrap();
+
+            // the compiler parses on the main thread, give the test the same
+            // stack size
+            let res = thread::Builder::new()
+                .stack_size(8 * 1024 * 1024)
+                .spawn(move || parse(&source).map(|_| ()))
+                .unwrap()
+                .join()
+                .unwrap_or_else(|_| panic!("parser panicked on {}", path.display()));
+
+            assert!(res.is_err(), "{} parsed without errors", path.display());
+        }
+    }
+}
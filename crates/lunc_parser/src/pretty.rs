@@ -9,14 +9,54 @@ use lunc_utils::{
 
 use crate::{
     directive::{Directive, QualifiedPath},
-    expr::{Arg, BinOp, Else, Expr, Expression, IfExpression, UnaryOp},
-    item::{Abi, Item, Module},
+    expr::{
+        Arg, BinOp, Else, EnumVariant, Expr, Expression, FieldValue, IfExpression, MatchArm,
+        NamedArg, StructField, UnaryOp,
+    },
+    item::{Abi, ImplDef, ImportDecl, Item, Module, PackageDecl, Vis},
     stmt::{Block, Statement, Stmt},
 };
 
 impl PrettyDump for Module {
     fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
-        self.items.as_slice().try_dump(ctx)
+        // NOTE: a module without declarations is dumped as its items
+        if self.package.is_none() && self.imports.is_empty() {
+            return self.items.as_slice().try_dump(ctx);
+        }
+
+        ctx.pretty_struct("Module")
+            .field("package", &self.package)
+            .field("imports", self.imports.as_slice())
+            .field("items", self.items.as_slice())
+            .finish()?;
+
+        Ok(())
+    }
+}
+
+impl PrettyDump for PackageDecl {
+    fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
+        let PackageDecl { name, loc } = self;
+
+        ctx.pretty_struct("Package").field("name", name).finish()?;
+        ctx.print_loc(loc)
+    }
+}
+
+impl PrettyDump for ImportDecl {
+    fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
+        let ImportDecl {
+            path,
+            alias,
+            package: _,
+            loc,
+        } = self;
+
+        ctx.pretty_struct("Import")
+            .field("path", path)
+            .field("alias", alias)
+            .finish()?;
+        ctx.print_loc(loc)
     }
 }
 
@@ -24,6 +64,7 @@ impl PrettyDump for Item {
     fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
         match self {
             Item::GlobalConst {
+                vis,
                 name,
                 name_loc,
                 typexpr,
@@ -31,6 +72,7 @@ impl PrettyDump for Item {
                 loc,
             } => {
                 ctx.pretty_struct("GlobalConst")
+                    .field("vis", vis)
                     .field("name", (name, name_loc))
                     .field("typexpr", typexpr)
                     .field("value", value)
@@ -40,6 +82,7 @@ impl PrettyDump for Item {
                 Ok(())
             }
             Item::GlobalVar {
+                vis,
                 name,
                 name_loc,
                 typexpr,
@@ -47,6 +90,7 @@ impl PrettyDump for Item {
                 loc,
             } => {
                 ctx.pretty_struct("GlobalVar")
+                    .field("vis", vis)
                     .field("name", (name, name_loc))
                     .field("typexpr", typexpr)
                     .field("value", value)
@@ -56,12 +100,14 @@ impl PrettyDump for Item {
                 Ok(())
             }
             Item::GlobalUninit {
+                vis,
                 name,
                 name_loc,
                 typexpr,
                 loc,
             } => {
                 ctx.pretty_struct("GlobalUninit")
+                    .field("vis", vis)
                     .field("name", (name, name_loc))
                     .field("typexpr", typexpr)
                     .finish()?;
@@ -78,11 +124,48 @@ impl PrettyDump for Item {
 
                 Ok(())
             }
+            Item::Impl {
+                params,
+                target,
+                defs,
+                loc,
+            } => {
+                ctx.pretty_struct("Impl")
+                    .field("params", params.as_slice())
+                    .field("target", target)
+                    .field("defs", defs.as_slice())
+                    .finish()?;
+                ctx.print_loc(loc)?;
+
+                Ok(())
+            }
             Item::Directive(directive) => directive.try_dump(ctx),
         }
     }
 }
 
+impl PrettyDump for ImplDef {
+    fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
+        let ImplDef { vis, item } = self;
+
+        ctx.pretty_struct("ImplDef")
+            .field("vis", vis)
+            .field("item", item)
+            .finish()?;
+
+        Ok(())
+    }
+}
+
+impl PrettyDump for Vis {
+    fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
+        match self {
+            Vis::Private => write!(ctx.out, "private"),
+            Vis::Public => write!(ctx.out, "public"),
+        }
+    }
+}
+
 impl PrettyDump for Abi {
     fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
         match self {
@@ -103,16 +186,25 @@ impl PrettyDump for Expr {
     fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
         let out = &mut ctx.out;
         match self {
-            Expr::IntLit(i) => write!(out, "integer {i}"),
+            Expr::IntLit(i, None) => write!(out, "integer {i}"),
+            Expr::IntLit(i, Some(suffix)) => write!(out, "integer {i}{suffix}"),
             Expr::BoolLit(b) => write!(out, "boolean {b}"),
             Expr::StringLit(s) => write!(out, "string {s:?}"),
             Expr::CharLit(c) => write!(out, "character {c:?}"),
-            Expr::FloatLit(f) => write!(out, "float {f:.}"),
+            Expr::FloatLit(f, None) => write!(out, "float {f:.}"),
+            Expr::FloatLit(f, Some(suffix)) => write!(out, "float {f:.}{suffix}"),
             Expr::Grouping(e) => {
                 ctx.pretty_struct("Grouping").field("expr", e).finish()?;
 
                 Ok(())
             }
+            Expr::Tuple(exprs) => {
+                ctx.pretty_struct("Tuple")
+                    .field("exprs", exprs.as_slice())
+                    .finish()?;
+
+                Ok(())
+            }
             Expr::Ident(id) => write!(out, "ident {id}"),
             Expr::Binary { lhs, op, rhs } => {
                 ctx.pretty_struct("Binary")
@@ -131,6 +223,14 @@ impl PrettyDump for Expr {
 
                 Ok(())
             }
+            Expr::Cast { expr, typexpr } => {
+                ctx.pretty_struct("Cast")
+                    .field("expr", expr)
+                    .field("typexpr", typexpr)
+                    .finish()?;
+
+                Ok(())
+            }
             Expr::Borrow { mutable, expr } => {
                 ctx.pretty_struct("Borrow")
                     .field("mutable", mutable)
@@ -139,10 +239,28 @@ impl PrettyDump for Expr {
 
                 Ok(())
             }
-            Expr::FunCall { callee, args } => {
+            Expr::Range {
+                start,
+                end,
+                inclusive,
+            } => {
+                ctx.pretty_struct("Range")
+                    .field("start", start)
+                    .field("end", end)
+                    .field("inclusive", inclusive)
+                    .finish()?;
+
+                Ok(())
+            }
+            Expr::FunCall {
+                callee,
+                args,
+                named_args,
+            } => {
                 ctx.pretty_struct("FunCall")
                     .field("callee", callee)
                     .field("args", args.as_slice())
+                    .field("named_args", named_args.as_slice())
                     .finish()?;
 
                 Ok(())
@@ -193,6 +311,7 @@ impl PrettyDump for Expr {
             Expr::IteratorLoop {
                 label,
                 variable,
+                variable_loc: _,
                 iterator,
                 body,
                 loc: _,
@@ -248,6 +367,11 @@ impl PrettyDump for Expr {
             Expr::Null => {
                 write!(ctx.out, "Null")
             }
+            Expr::Comptime(expr) => {
+                ctx.pretty_struct("Comptime").field("expr", expr).finish()?;
+
+                Ok(())
+            }
             Expr::MemberAccess { expr, member } => {
                 ctx.pretty_struct("MemberAccess")
                     .field("expr", expr)
@@ -256,9 +380,25 @@ impl PrettyDump for Expr {
 
                 Ok(())
             }
+            Expr::StructLit { typexpr, fields } => {
+                ctx.pretty_struct("StructLit")
+                    .field("typexpr", typexpr)
+                    .field("fields", fields.as_slice())
+                    .finish()?;
+
+                Ok(())
+            }
             Expr::Orb => {
                 write!(ctx.out, "Orb")
             }
+            Expr::Match { expr, arms } => {
+                ctx.pretty_struct("Match")
+                    .field("expr", expr)
+                    .field("arms", arms.as_slice())
+                    .finish()?;
+
+                Ok(())
+            }
             Expr::FunDefinition {
                 args,
                 rettypexpr,
@@ -296,6 +436,20 @@ impl PrettyDump for Expr {
 
                 Ok(())
             }
+            Expr::Struct { fields } => {
+                ctx.pretty_struct("Struct")
+                    .field("fields", fields.as_slice())
+                    .finish()?;
+
+                Ok(())
+            }
+            Expr::Enum { variants } => {
+                ctx.pretty_struct("Enum")
+                    .field("variants", variants.as_slice())
+                    .finish()?;
+
+                Ok(())
+            }
         }
     }
 }
@@ -303,15 +457,109 @@ impl PrettyDump for Expr {
 impl PrettyDump for Arg {
     fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
         let Arg {
+            comptime,
             name,
             name_loc,
             typexpr,
+            default,
             loc,
         } = self;
 
         ctx.pretty_struct("Arg")
+            .field("comptime", comptime)
             .field("name", (name, name_loc))
             .field("typexpr", typexpr)
+            .field("default", default)
+            .finish()?;
+        ctx.print_loc(loc)?;
+
+        Ok(())
+    }
+}
+
+impl PrettyDump for NamedArg {
+    fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
+        let NamedArg {
+            name,
+            name_loc,
+            value,
+            loc,
+        } = self;
+
+        ctx.pretty_struct("NamedArg")
+            .field("name", (name, name_loc))
+            .field("value", value)
+            .finish()?;
+        ctx.print_loc(loc)?;
+
+        Ok(())
+    }
+}
+
+impl PrettyDump for StructField {
+    fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
+        let StructField {
+            name,
+            name_loc,
+            typexpr,
+            loc,
+        } = self;
+
+        ctx.pretty_struct("StructField")
+            .field("name", (name, name_loc))
+            .field("typexpr", typexpr)
+            .finish()?;
+        ctx.print_loc(loc)?;
+
+        Ok(())
+    }
+}
+
+impl PrettyDump for EnumVariant {
+    fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
+        let EnumVariant {
+            name,
+            name_loc,
+            value,
+            loc,
+        } = self;
+
+        ctx.pretty_struct("EnumVariant")
+            .field("name", (name, name_loc))
+            .field("value", value)
+            .finish()?;
+        ctx.print_loc(loc)?;
+
+        Ok(())
+    }
+}
+
+impl PrettyDump for MatchArm {
+    fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
+        let MatchArm { pats, body, loc } = self;
+
+        ctx.pretty_struct("MatchArm")
+            .field("pats", pats.as_slice())
+            .field("body", body)
+            .finish()?;
+        ctx.print_loc(loc)?;
+
+        Ok(())
+    }
+}
+
+impl PrettyDump for FieldValue {
+    fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
+        let FieldValue {
+            name,
+            name_loc,
+            value,
+            loc,
+        } = self;
+
+        ctx.pretty_struct("FieldValue")
+            .field("name", (name, name_loc))
+            .field("value", value)
             .finish()?;
         ctx.print_loc(loc)?;
 
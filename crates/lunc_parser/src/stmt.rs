@@ -2,7 +2,7 @@
 
 use lunc_utils::opt_unreachable;
 
-use crate::expr::parse_typexpr;
+use crate::expr::{Expr, parse_typexpr};
 
 use super::*;
 
@@ -16,6 +16,13 @@ pub struct Block {
 
 impl AstNode for Block {
     fn parse(parser: &mut Parser) -> Result<Self, Diagnostic> {
+        parser.nested(Block::parse_unlimited)
+    }
+}
+
+impl Block {
+    /// Parses a block, without increasing the nesting depth.
+    fn parse_unlimited(parser: &mut Parser) -> Result<Self, Diagnostic> {
         let mut stmts = Vec::new();
 
         // TEST: no. 1
@@ -63,8 +70,12 @@ impl AstNode for Block {
                     // block
                     stmts.push(stmt.clone());
 
-                    // TEST: no. 2
-                    expect_token!(parser => [Punct(Punctuation::Semicolon), ()], Punct(Punctuation::Semicolon));
+                    // a defer with a block expression inside can end the
+                    // block without a semicolon
+                    if !stmt.is_defer_with_block() {
+                        // TEST: no. 2
+                        expect_token!(parser => [Punct(Punctuation::Semicolon), ()], Punct(Punctuation::Semicolon));
+                    }
                 }
                 (false, true) => {
                     // here we have a statement expression, we require a
@@ -90,8 +101,12 @@ impl AstNode for Block {
                 }
                 (false, false) => {
                     // if the statement is a defer with a block expression
-                    // inside we don't expect a semicolon
-                    if !matches!(stmt.stmt, Stmt::Defer { ref expr } if expr.is_expr_with_block()) {
+                    // inside the semicolon is optional
+                    if stmt.is_defer_with_block() {
+                        if let Some(Punct(Punctuation::Semicolon)) = parser.peek_tt() {
+                            parser.pop();
+                        }
+                    } else {
                         // TEST: no. 4
                         expect_token!(parser => [Punct(Punctuation::Semicolon), ()], Punct(Punctuation::Semicolon));
                     }
@@ -124,6 +139,12 @@ impl Statement {
     pub fn is_expr(&self) -> bool {
         matches!(self.stmt, Stmt::Expression(_))
     }
+
+    /// Is this statement a defer of an expression with a block, like
+    /// `defer { .. }`? It doesn't need a semicolon after it.
+    pub fn is_defer_with_block(&self) -> bool {
+        matches!(self.stmt, Stmt::Defer { ref expr } if expr.is_expr_with_block())
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -133,6 +154,8 @@ pub enum Stmt {
     /// `"let" "mut"? ident [ ":" expr ] "=" expr`
     /// `ident ":" [ expr ] ":" expr ";"`
     /// `ident ":" [ expr ] "=" expr ";"`
+    ///
+    /// prefixed by `"comptime"`, the value is a comptime expression.
     VariableDef {
         name: String,
         name_loc: Span,
@@ -154,6 +177,9 @@ impl AstNode for Statement {
     fn parse(parser: &mut Parser) -> Result<Self, Diagnostic> {
         match parser.peek_tt() {
             Some(Kw(Keyword::Let)) => parse_variable_def_stmt(parser),
+            Some(Kw(Keyword::Comptime)) if parser.is_comptime_variable_def() => {
+                parse_comptime_variable_stmt(parser)
+            }
             Some(Kw(Keyword::Defer)) => parse_defer_statement(parser),
             Some(Ident(_)) if parser.is_short_variable_def() => parse_short_variable_stmt(parser),
             Some(_) => {
@@ -171,9 +197,23 @@ impl AstNode for Statement {
 
 impl Parser {
     pub fn is_short_variable_def(&self) -> bool {
-        matches!(self.nth_tt(1), Some(Punct(Punctuation::Colon)))
+        self.is_short_variable_def_at(0)
+    }
+
+    /// Is it a variable definition prefixed by `comptime`, `comptime a :: ..`
+    pub fn is_comptime_variable_def(&self) -> bool {
+        match self.nth_tt(1) {
+            Some(Kw(Keyword::Let)) => true,
+            Some(Ident(_)) => self.is_short_variable_def_at(1),
+            _ => false,
+        }
+    }
+
+    /// Is the identifier at `n` the start of a short variable definition
+    fn is_short_variable_def_at(&self, n: usize) -> bool {
+        matches!(self.nth_tt(n + 1), Some(Punct(Punctuation::Colon)))
             && !matches!(
-                self.nth_tt(2),
+                self.nth_tt(n + 2),
                 Some(
                     Kw(Keyword::While | Keyword::For | Keyword::Loop) | Punct(Punctuation::LBrace)
                 )
@@ -261,6 +301,47 @@ pub fn parse_short_variable_stmt(parser: &mut Parser) -> Result<Statement, Diagn
     })
 }
 
+/// `"comptime" ( variable_def | short_variable_def )`
+///
+/// the value of the variable is evaluated at compile-time, like `a := comptime
+/// expr`. A comptime variable is defined with `let` like any other variable,
+/// `comptime let a = expr` or `comptime a :: expr`, there is no `var` keyword.
+pub fn parse_comptime_variable_stmt(parser: &mut Parser) -> Result<Statement, Diagnostic> {
+    // TEST: n/a
+    let ((), lo) = expect_token!(parser => [Kw(Keyword::Comptime), ()], Kw(Keyword::Comptime));
+
+    let Statement { stmt, loc } = match parser.peek_tt() {
+        Some(Kw(Keyword::Let)) => parse_variable_def_stmt(parser)?,
+        _ => parse_short_variable_stmt(parser)?,
+    };
+
+    let Stmt::VariableDef {
+        name,
+        name_loc,
+        mutable,
+        typexpr,
+        value,
+    } = stmt
+    else {
+        // SAFETY: both functions only parse variable definitions
+        opt_unreachable!()
+    };
+
+    Ok(Statement {
+        stmt: Stmt::VariableDef {
+            name,
+            name_loc,
+            mutable,
+            typexpr,
+            value: Box::new(Expression {
+                loc: value.loc.clone(),
+                expr: Expr::Comptime(value),
+            }),
+        },
+        loc: Span::from_ends(lo, loc),
+    })
+}
+
 /// parses a defer statement
 pub fn parse_defer_statement(parser: &mut Parser) -> Result<Statement, Diagnostic> {
     // TEST: n/a
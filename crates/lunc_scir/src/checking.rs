@@ -1,19 +1,27 @@
 //! Checks for the SCIR like typechecking, safety checks etc
 
-use std::iter::{self, zip};
+use std::{
+    iter::{self, zip},
+    mem,
+};
 
 use lunc_diag::{ToDiagnostic, feature_todo};
+use lunc_dsir::diags::PrivateDefinition;
 use lunc_utils::{
-    opt_unreachable,
+    DEFAULT_MAX_LEVENSHTEIN_DISTANCE, opt_unreachable, suggest,
     symbol::{Signedness, Typeness},
 };
 
 use crate::diags::{
-    ArityDoesntMatch, BorrowMutWhenNotDefinedMut, BreakUseAnImplicitLabelInBlock,
-    BreakWithValueUnsupported, CallRequiresFuncType, CantContinueABlock, CantResolveComptimeValue,
-    ExpectedPlaceExpression, ExpectedTypeFoundExpr, FunctionInGlobalMut,
-    ItemNotAllowedInExternBlock, LabelKwOutsideLoopOrBlock, MismatchedTypes, OutsideExternBlock,
-    TypeAnnotationsNeeded, UseOfUndefinedLabel, WUnreachableCode, WUnusedLabel,
+    ArgumentGivenMultipleTimes, ArityDoesntMatch, AssociatedItemDefinedMultipleTimes,
+    BorrowMutWhenNotDefinedMut, BreakUseAnImplicitLabelInBlock, CallRequiresFuncType,
+    CantContinueABlock, CantResolveComptimeValue, DuplicateDiscriminant, ExpectedPlaceExpression,
+    ExpectedTypeFoundExpr, FieldDefinedMultipleTimes, FunctionInGlobalMut, InvalidCast,
+    ItemNotAllowedInExternBlock, ItemNotAllowedInImpl, JumpOutOfDefer, LabelKwOutsideLoopOrBlock,
+    MismatchedTypes, MissingFieldsInLiteral, NoFieldOnType, NonExhaustiveMatch,
+    NullOfNonPointerType, OutsideExternBlock, RecursiveStruct, ReturnInDefer,
+    TypeAnnotationsNeeded, UnknownNamedArg, UseOfUndefinedLabel, VariantDefinedMultipleTimes,
+    WUnreachableCode, WUnusedLabel,
 };
 
 use super::*;
@@ -33,7 +41,13 @@ pub enum NoreturnPos {
 
 impl SemaChecker {
     pub fn ck_mod(&mut self, module: &mut ScModule) {
+        // NOTE: the global definitions were already checked, see
+        // `ck_global_defs`
         for item in &mut module.items {
+            if let ScItem::GlobalDef { .. } = item {
+                continue;
+            }
+
             match self.ck_item(item) {
                 Ok(()) => {}
                 Err(d) => self.sink.emit(d),
@@ -41,6 +55,135 @@ impl SemaChecker {
         }
     }
 
+    /// Recursively checks the global definitions of modules. They are checked
+    /// before the functions, so that the globals a function body or the
+    /// default value of an argument refers to are typed, wherever they are
+    /// defined.
+    pub fn ck_global_defs(&mut self, items: &mut [ScItem]) {
+        for item in items {
+            match item {
+                ScItem::GlobalDef { .. } => match self.ck_item(item) {
+                    Ok(()) => {}
+                    Err(d) => self.sink.emit(d),
+                },
+                ScItem::Module { module, sym, .. } => {
+                    let module_path = mem::replace(&mut self.module_path, sym.path());
+                    self.ck_global_defs(&mut module.items);
+                    self.module_path = module_path;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Recursively binds the struct and enum types defined in constant global
+    /// definitions to their symbol, the fields and variants are checked later
+    /// in the pre check of the global definition.
+    pub fn bind_struct_defs(&mut self, items: &mut [ScItem]) {
+        for item in items {
+            match item {
+                ScItem::GlobalDef {
+                    name,
+                    mutable: false,
+                    value,
+                    sym: symref,
+                    ..
+                } => match &mut value.expr {
+                    ScExpr::Struct {
+                        fields: _,
+                        index: index @ None,
+                    } => {
+                        let idx = self.define_struct(Some(name.clone()), value.loc.clone());
+                        *index = Some(idx);
+
+                        symref.set_typ(Type::Type);
+                        symref.set_value(Some(ValueExpr::Type(self.struct_type(idx))));
+                    }
+                    ScExpr::Enum {
+                        variants: _,
+                        index: index @ None,
+                    } => {
+                        let idx = self.define_enum(Some(name.clone()), value.loc.clone());
+                        *index = Some(idx);
+
+                        symref.set_typ(Type::Type);
+                        symref.set_value(Some(ValueExpr::Type(self.enum_type(idx))));
+                    }
+                    _ => {}
+                },
+                ScItem::Module { module, .. } => self.bind_struct_defs(&mut module.items),
+                _ => {}
+            }
+        }
+    }
+
+    /// Reports the struct types that contain themselves by value, directly or
+    /// through other structs and tuples, they would have an infinite size. A
+    /// pointer field breaks the cycle.
+    pub fn ck_recursive_structs(&mut self) {
+        let mut reported = Vec::new();
+
+        for index in 0..self.structs.len() {
+            if reported.contains(&index) {
+                continue;
+            }
+
+            let mut cycle = Vec::new();
+
+            if !self.struct_cycle(index, index, &mut Vec::new(), &mut cycle) {
+                continue;
+            }
+
+            reported.extend(cycle.iter().map(|(idx, _)| *idx));
+
+            let Some(loc) = self.structs[index].loc.clone() else {
+                continue;
+            };
+
+            self.sink.emit(RecursiveStruct {
+                name: self.struct_type(index).to_string(),
+                cycle: cycle
+                    .into_iter()
+                    .map(|(idx, field)| (self.struct_type(idx).to_string(), field))
+                    .collect(),
+                loc,
+            });
+        }
+    }
+
+    /// Searches a path of by-value fields from the struct `from` to the struct
+    /// `to`, every step of the path is pushed to `path` as the struct and the
+    /// name of its field.
+    fn struct_cycle(
+        &self,
+        from: usize,
+        to: usize,
+        visited: &mut Vec<usize>,
+        path: &mut Vec<(usize, String)>,
+    ) -> bool {
+        for (name, typ) in &self.structs[from].fields {
+            for index in contained_structs(typ) {
+                path.push((from, name.clone()));
+
+                if index == to {
+                    return true;
+                }
+
+                if !visited.contains(&index) {
+                    visited.push(index);
+
+                    if self.struct_cycle(index, to, visited, path) {
+                        return true;
+                    }
+                }
+
+                path.pop();
+            }
+        }
+
+        false
+    }
+
     /// Recursively pre check modules, it is used to add types and everything
     /// to global definitions and functions, but does not type check the body
     /// of functions
@@ -97,6 +240,11 @@ impl SemaChecker {
 
                 Ok(())
             }
+            ScItem::FunDefinition { sym, .. } if self.is_generic(sym) => {
+                // NOTE: the signature of a generic function is checked for
+                // each of its instantiations.
+                Ok(())
+            }
             ScItem::FunDefinition {
                 name: _,
                 name_loc: _,
@@ -137,82 +285,19 @@ impl SemaChecker {
                     None
                 };
 
-                // collect the arguments types
-                let mut args_typ = Vec::new();
-
-                for ScArg {
-                    typexpr: typexpr_arg,
-                    sym: symref,
-                    ..
-                } in args
-                {
-                    match self.ck_expr(typexpr_arg, Some(Type::Type)) {
-                        Ok(()) => {}
-                        Err(d) => self.sink.emit(d),
-                    }
-
-                    let value_typ_arg = match self.evaluate_expr(typexpr_arg) {
-                        Ok(typ) => typ,
-                        Err((loc, note)) => {
-                            self.sink.emit(CantResolveComptimeValue {
-                                note,
-                                loc_expr: typexpr_arg.loc.clone().unwrap(),
-                                loc: loc.clone(),
-                            });
-
-                            ValueExpr::Type(Type::Void)
-                        }
-                    };
-
-                    let arg_typ = match value_typ_arg.as_type() {
-                        Some(typ) => typ,
-                        None => {
-                            self.sink.emit(ExpectedTypeFoundExpr {
-                                loc: typexpr_arg.loc.clone().unwrap(),
-                            });
-
-                            Type::Void
-                        }
-                    };
-
-                    args_typ.push(arg_typ.clone());
-
-                    symref.set_typ(arg_typ);
-                }
-
-                // evaluate the return type expression
-                let ret_typ = if let Some(ret_typexpr) = rettypexpr {
-                    match self.ck_expr(ret_typexpr, Some(Type::Type)) {
-                        Ok(()) => {}
-                        Err(d) => self.sink.emit(d),
-                    }
-
-                    let value_typ_ret = match self.evaluate_expr(ret_typexpr) {
-                        Ok(typ) => typ,
-                        Err((loc, note)) => {
-                            self.sink.emit(CantResolveComptimeValue {
-                                note,
-                                loc_expr: ret_typexpr.loc.clone().unwrap(),
-                                loc: loc.clone(),
-                            });
-
-                            ValueExpr::Type(Type::Void)
-                        }
-                    };
-
-                    match value_typ_ret.as_type() {
-                        Some(typ) => typ,
-                        None => {
-                            self.sink.emit(ExpectedTypeFoundExpr {
-                                loc: ret_typexpr.loc.clone().unwrap(),
-                            });
-
-                            Type::Void
-                        }
-                    }
-                } else {
-                    Type::Void
-                };
+                let (args_typ, ret_typ) = self.ck_fun_signature(args, rettypexpr);
+
+                // NOTE: the default values are checked when they are first
+                // needed, after the global definitions they may refer to, see
+                // `ck_global_defs`.
+                self.fun_params.push(FunParams {
+                    sym: sym.clone(),
+                    params: args
+                        .iter()
+                        .map(|arg| (arg.name.clone(), arg.default.clone()))
+                        .collect(),
+                    checked: false,
+                });
 
                 let typ = if let Some(ref typ) = typexpr_as_type {
                     typ.clone()
@@ -353,14 +438,135 @@ impl SemaChecker {
 
                 Ok(())
             }
-            ScItem::Module { module, .. } => {
+            ScItem::Module { module, sym, .. } => {
+                let module_path = mem::replace(&mut self.module_path, sym.path());
                 self.pre_ck_module(module);
+                self.module_path = module_path;
+
                 Ok(())
             }
             ScItem::ExternBlock { items, .. } => {
                 self.pre_ck_items(items);
                 Ok(())
             }
+            ScItem::Impl {
+                params,
+                target,
+                defs,
+                loc,
+            } => {
+                // impl block pre ck
+
+                // only constant definitions are allowed in an impl block
+                defs.retain(|def| {
+                    if let ScItem::GlobalDef { mutable: true, .. } = def.item {
+                        self.sink.emit(ItemNotAllowedInImpl {
+                            item: "mutable global definition",
+                            loc: def.item.loc(),
+                            impl_loc: loc.clone().unwrap(),
+                        });
+
+                        false
+                    } else {
+                        true
+                    }
+                });
+
+                if !params.is_empty() {
+                    // NOTE: the definitions of a generic impl block are checked
+                    // for each type it is instantiated for.
+                    self.bind_generic_impl(params, target, defs, loc);
+
+                    return Ok(());
+                }
+
+                // we typecheck and evaluate the target of the impl block
+                self.ck_expr(target, Some(Type::Type))?;
+
+                if target.typ != Type::Type {
+                    self.sink.emit(ExpectedTypeFoundExpr {
+                        loc: target.loc.clone().unwrap(),
+                    })
+                }
+
+                let typ = {
+                    let value = self.evaluate_expr(target).map_err(|(loc, note)| {
+                        CantResolveComptimeValue {
+                            note,
+                            loc_expr: target.loc.clone().unwrap(),
+                            loc,
+                        }
+                        .into_diag()
+                    })?;
+
+                    value.as_type().unwrap_or(Type::Void)
+                };
+
+                self.bind_assoc_items(&typ, defs);
+
+                Ok(())
+            }
+        }
+    }
+
+    /// Pre checks the definitions of an impl block and associates them to the
+    /// type `typ`.
+    pub fn bind_assoc_items(&mut self, typ: &Type, defs: &mut [ScImplDef]) {
+        for ScImplDef { vis, item: def } in defs {
+            match self.pre_ck_item(def) {
+                Ok(()) => {}
+                Err(d) => self.sink.emit(d),
+            }
+
+            let (name, name_loc, sym) = match def {
+                ScItem::GlobalDef {
+                    name,
+                    name_loc,
+                    sym,
+                    ..
+                }
+                | ScItem::GlobalUninit {
+                    name,
+                    name_loc,
+                    sym,
+                    ..
+                }
+                | ScItem::FunDefinition {
+                    name,
+                    name_loc,
+                    sym,
+                    ..
+                }
+                | ScItem::FunDeclaration {
+                    name,
+                    name_loc,
+                    sym,
+                    ..
+                } => (name, name_loc, sym),
+                // NOTE: the parser only produces global definitions
+                // inside of an impl block
+                _ => continue,
+            };
+
+            if let Some(first) = self.assoc_item(typ, name) {
+                self.sink.emit(AssociatedItemDefinedMultipleTimes {
+                    name: name.clone(),
+                    typ: typ.clone(),
+                    loc_first: first.loc.clone().unwrap(),
+                    loc: name_loc.clone().unwrap(),
+                });
+
+                continue;
+            }
+
+            self.assoc_items.push(AssocItem {
+                typ: typ.clone(),
+                name: name.clone(),
+                sym: sym.clone(),
+                vis: vis.clone(),
+                module: self.module_path.clone(),
+                loc: name_loc.clone(),
+            });
         }
     }
 
@@ -373,7 +579,7 @@ impl SemaChecker {
             name_loc: _,
             mutable: _,
             typexpr,
-            value: _,
+            value,
             loc: _,
             sym: symref,
         } = global_def
@@ -385,6 +591,21 @@ impl SemaChecker {
 
         // global def pre ck
 
+        // struct and enum definitions were bound before the pre check, we
+        // check their fields and variants now so that the type is complete
+        // before any body is checked
+        if let ScExpr::Struct {
+            fields: _,
+            index: Some(_),
+        }
+        | ScExpr::Enum {
+            variants: _,
+            index: Some(_),
+        } = value.expr
+        {
+            return self.ck_expr(value, Some(Type::Type));
+        }
+
         // we typecheck the type expression
         if let Some(typexpr) = &mut **typexpr {
             self.ck_expr(typexpr, Some(Type::Type))?;
@@ -439,6 +660,18 @@ impl SemaChecker {
                 return;
             }
 
+            if let Some(last) = &found.last_expr
+                && let ScExpr::Null = last.expr
+            {
+                self.sink.emit(NullOfNonPointerType {
+                    expected: expected.clone(),
+                    due_to: due_to.into(),
+                    loc: last.loc.clone().unwrap(),
+                });
+
+                return;
+            }
+
             self.sink.emit(MismatchedTypes {
                 expected: vec![expected.clone()],
                 found: found.typ.clone(),
@@ -464,6 +697,18 @@ impl SemaChecker {
                 return;
             }
 
+            if let ScExpr::Null = found.expr
+                && *expected != Type::Unknown
+            {
+                self.sink.emit(NullOfNonPointerType {
+                    expected: expected.clone(),
+                    due_to: due_to.into(),
+                    loc: found.loc.clone().unwrap(),
+                });
+
+                return;
+            }
+
             let mut notes = note.into().as_slice().to_vec();
 
             // special case for if, we add a note.
@@ -484,6 +729,36 @@ impl SemaChecker {
                 ));
             }
 
+            // special case for functions, we add a note about what differs.
+            if let (
+                Type::FunPtr {
+                    args: expected_args,
+                    ret: expected_ret,
+                },
+                Type::FunPtr { args, ret },
+            ) = (expected, &found.typ)
+            {
+                if expected_args.len() != args.len() {
+                    notes.push(format!(
+                        "the function takes {} arguments but {} are expected",
+                        args.len(),
+                        expected_args.len()
+                    ));
+                } else if let Some((i, (expected_arg, arg))) = zip(expected_args, args)
+                    .enumerate()
+                    .find(|(_, (expected_arg, arg))| expected_arg != arg)
+                {
+                    notes.push(format!(
+                        "the argument #{} of the function has type '{arg}' but '{expected_arg}' is expected",
+                        i + 1
+                    ));
+                } else if expected_ret != ret {
+                    notes.push(format!(
+                        "the function returns '{ret}' but '{expected_ret}' is expected"
+                    ));
+                }
+            }
+
             self.sink.emit(MismatchedTypes {
                 expected: vec![expected.clone()],
                 found: found.typ.clone(),
@@ -499,8 +774,12 @@ impl SemaChecker {
     #[must_use]
     pub fn apply_typ_on_expr(expr: &mut ScExpression, typ: Type) -> Option<()> {
         match &mut expr.expr {
-            ScExpr::IntLit(_) => {}
-            ScExpr::FloatLit(_) => {}
+            // the type of a suffixed literal can't change
+            ScExpr::IntLit(_, Some(_)) | ScExpr::FloatLit(_, Some(_)) => return None,
+            ScExpr::IntLit(_, None) => {}
+            ScExpr::CharLit(_) if typ.is_int() => {}
+            ScExpr::Null if matches!(typ, Type::Ptr { .. }) => {}
+            ScExpr::FloatLit(_, None) => {}
             ScExpr::Ident(symref) if symref.typeness() == Typeness::Implicit => {
                 symref.inspect_mut(|sym| {
                     sym.typ = typ.clone();
@@ -514,6 +793,19 @@ impl SemaChecker {
             ScExpr::Unary { op: _, expr } | ScExpr::Borrow { mutable: _, expr } => {
                 Self::apply_typ_on_expr(expr, typ.clone())?;
             }
+            ScExpr::Tuple(exprs) => {
+                let Type::Tuple(typs) = &typ else {
+                    return None;
+                };
+
+                if typs.len() != exprs.len() {
+                    return None;
+                }
+
+                for (exp, typ) in exprs.iter_mut().zip(typs) {
+                    Self::apply_typ_on_expr(exp, typ.clone())?;
+                }
+            }
             ScExpr::If {
                 cond: _,
                 then_br,
@@ -525,6 +817,13 @@ impl SemaChecker {
                     Self::apply_typ_on_expr(else_br, typ.clone())?;
                 }
             }
+            ScExpr::Match { expr: _, arms } => {
+                for arm in arms {
+                    if arm.body.typ != Type::Noreturn {
+                        Self::apply_typ_on_expr(&mut arm.body, typ.clone())?;
+                    }
+                }
+            }
             ScExpr::Block {
                 block,
                 label: _,
@@ -538,6 +837,7 @@ impl SemaChecker {
                                     label: _,
                                     expr: Some(expr),
                                     index: index_break,
+                                    desugared: _,
                                 },
                             typ: _,
                             loc: _,
@@ -579,11 +879,13 @@ impl SemaChecker {
                 // we check the value of the definition
                 self.ck_expr(value, typ.clone())?;
 
-                // we check the type of the value
-                if value.typ == Type::Unknown {
+                // we check the type of the value, `null` needs a pointer type
+                if value.typ == Type::Unknown || (value.typ == Type::Null && typ.is_none()) {
                     self.sink.emit(TypeAnnotationsNeeded {
                         loc: value.loc.clone().unwrap(),
                     });
+
+                    return Ok(());
                 } else if let Some(typ) = &typ
                     && &value.typ != typ
                 {
@@ -601,6 +903,13 @@ impl SemaChecker {
                     symref.set_typ(value.typ.clone());
                 }
 
+                // NOTE: a comptime expression is replaced by its value once
+                // checked, if it is still there it failed to evaluate and it
+                // was already reported.
+                if let ScExpr::Comptime(_) = value.expr {
+                    return Ok(());
+                }
+
                 // we evaluate the value of the global def
                 let value_expr = {
                     self.evaluate_expr(value).map_err(|(loc, note)| {
@@ -638,11 +947,16 @@ impl SemaChecker {
 
                 Ok(())
             }
+            ScItem::FunDefinition { sym, .. } if self.is_generic(sym) => {
+                // NOTE: the body of a generic function is checked for each of
+                // its instantiations.
+                Ok(())
+            }
             ScItem::FunDefinition {
                 name: _,
                 name_loc: _,
                 typexpr: _,
-                args: _,
+                args,
                 rettypexpr,
                 body,
                 defined_mut,
@@ -657,30 +971,22 @@ impl SemaChecker {
                     })
                 }
 
-                // set the function return type
-
-                self.fun_retty = sym.typ().as_fun_ptr().map(|t| t.1).unwrap_or(Type::Void);
-                // unwrap with a dummy if the type was defined to a non-fnptr.
-
-                self.fun_retty_loc = rettypexpr.as_ref().and_then(|typexpr| typexpr.loc.clone());
-
-                // check the body of the function
-                self.ck_block(body, Some(self.fun_retty.clone()))?;
+                // the arguments get their checked default values
+                if let Some(FunParams { params, .. }) = self.checked_fun_params(sym) {
+                    for (arg, (_, default)) in zip(args.iter_mut(), params) {
+                        arg.default = default;
+                    }
+                }
 
-                // check the type of the block of the function
-                self.block_typeck(
-                    &self.fun_retty.clone(),
-                    body,
-                    self.fun_retty_loc.clone(),
-                    None,
-                    body.last_expr
-                        .as_ref()
-                        .map(|expr| expr.loc.clone())
-                        .unwrap_or(body.loc.clone())
-                        .unwrap(),
-                );
+                // the return type of the function, unwrap with a dummy if the
+                // type was defined to a non-fnptr.
+                let ret_typ = sym.typ().as_fun_ptr().map(|t| t.1).unwrap_or(Type::Void);
+                let ret_typ_loc = rettypexpr.as_ref().and_then(|typexpr| typexpr.loc.clone());
 
-                Ok(())
+                // NOTE: the body is checked with its own return type and labels
+                // because the function may be a definition of a generic impl
+                // block instantiated while checking another function.
+                self.ck_fun_body(body, ret_typ, ret_typ_loc)
             }
             ScItem::FunDeclaration {
                 defined_mut, loc, ..
@@ -715,8 +1021,10 @@ impl SemaChecker {
 
                 Ok(())
             }
-            ScItem::Module { module, .. } => {
+            ScItem::Module { module, sym, .. } => {
+                let module_path = mem::replace(&mut self.module_path, sym.path());
                 self.ck_mod(module);
+                self.module_path = module_path;
 
                 Ok(())
             }
@@ -765,6 +1073,14 @@ impl SemaChecker {
                                 extern_block_loc: loc.clone().unwrap(),
                             });
                         }
+                        ScItem::Impl { .. } => {
+                            self.sink.emit(ItemNotAllowedInExternBlock {
+                                item: "impl block",
+                                note: None,
+                                loc: item.loc(),
+                                extern_block_loc: loc.clone().unwrap(),
+                            });
+                        }
                     }
                 }
 
@@ -779,33 +1095,236 @@ impl SemaChecker {
 
                 self.container = container;
 
+                Ok(())
+            }
+            ScItem::Impl {
+                params,
+                target: _,
+                defs,
+                loc: _,
+            } => {
+                // NOTE: generic impl blocks are checked for each type they
+                // are instantiated for.
+                if !params.is_empty() {
+                    return Ok(());
+                }
+
+                for ScImplDef { item: def, .. } in defs {
+                    match self.ck_item(def) {
+                        Ok(()) => {}
+                        Err(d) => self.sink.emit(d),
+                    }
+                }
+
                 Ok(())
             }
         }
     }
 
-    pub fn ck_expr(
+    /// Checks and evaluates the types of the arguments and the return type of
+    /// a function, returns the types of the arguments and the return type.
+    fn ck_fun_signature(
         &mut self,
-        expr: &mut ScExpression,
-        mut coerce_to: Option<Type>,
-    ) -> Result<(), Diagnostic> {
-        if let Some(Type::Unknown) = coerce_to {
-            coerce_to = None;
+        args: &mut [ScArg],
+        rettypexpr: &mut Option<Box<ScExpression>>,
+    ) -> (Vec<Type>, Type) {
+        // collect the arguments types
+        let mut args_typ = Vec::new();
+
+        for ScArg {
+            typexpr: typexpr_arg,
+            sym: symref,
+            ..
+        } in args
+        {
+            let arg_typ = self.ck_typexpr(typexpr_arg);
+
+            args_typ.push(arg_typ.clone());
+
+            symref.set_typ(arg_typ);
         }
 
-        match &mut expr.expr {
-            ScExpr::IntLit(_) => {
-                if let Some(coercion) = coerce_to
-                    && coercion.is_int()
-                {
-                    expr.typ = coercion;
-                } else {
-                    expr.typ = Type::I32;
+        // evaluate the return type expression
+        let ret_typ = if let Some(ret_typexpr) = rettypexpr {
+            self.ck_typexpr(ret_typexpr)
+        } else {
+            Type::Void
+        };
+
+        (args_typ, ret_typ)
+    }
+
+    /// Returns the parameters of the function definition with the symbol
+    /// `sym`, if any. Their default values are checked against the type of
+    /// their parameter and replaced by their value computed at compile-time
+    /// the first time they are needed.
+    fn checked_fun_params(&mut self, sym: &Symbol) -> Option<FunParams> {
+        let idx = self
+            .fun_params
+            .iter()
+            .position(|fun| fun.sym.object_eq(sym))?;
+
+        if !self.fun_params[idx].checked {
+            self.fun_params[idx].checked = true;
+
+            let mut params = mem::take(&mut self.fun_params[idx].params);
+            let args_ty = sym.typ().as_fun_ptr().map(|(args, _)| args);
+
+            for ((_, default), typ) in zip(&mut params, args_ty.unwrap_or_default()) {
+                if let Some(default) = default {
+                    self.ck_arg_default(default, typ);
                 }
             }
-            ScExpr::BoolLit(_) => {
-                expr.typ = Type::Bool;
-            }
+
+            self.fun_params[idx].params = params;
+        }
+
+        Some(self.fun_params[idx].clone())
+    }
+
+    /// Checks the default value of an argument of type `typ` and replaces it
+    /// by its value computed at compile-time.
+    fn ck_arg_default(&mut self, default: &mut ScExpression, typ: Type) {
+        match self.ck_expr(default, Some(typ.clone())) {
+            Ok(()) => {}
+            Err(d) => {
+                self.sink.emit(d);
+                return;
+            }
+        }
+
+        self.expr_typeck(&typ, default, None, None);
+
+        match self.evaluate_expr(default) {
+            Ok(value) => {
+                if let Some(lit) =
+                    Self::value_to_expr(value, default.typ.clone(), default.loc.clone())
+                {
+                    *default = lit;
+                }
+            }
+            Err((loc, note)) => self.sink.emit(CantResolveComptimeValue {
+                note: note.or_else(|| {
+                    Some(
+                        "the default value of an argument must be known at compile-time"
+                            .to_string(),
+                    )
+                }),
+                loc_expr: default.loc.clone().unwrap(),
+                loc,
+            }),
+        }
+    }
+
+    /// Checks and evaluates a type expression, returns the type it evaluates
+    /// to or `void` if it can't be evaluated to a type.
+    pub fn ck_typexpr(&mut self, typexpr: &mut ScExpression) -> Type {
+        match self.ck_expr(typexpr, Some(Type::Type)) {
+            Ok(()) => {}
+            Err(d) => self.sink.emit(d),
+        }
+
+        let value_typ = match self.evaluate_expr(typexpr) {
+            Ok(typ) => typ,
+            Err((loc, note)) => {
+                self.sink.emit(CantResolveComptimeValue {
+                    note,
+                    loc_expr: typexpr.loc.clone().unwrap(),
+                    loc: loc.clone(),
+                });
+
+                ValueExpr::Type(Type::Void)
+            }
+        };
+
+        match value_typ.as_type() {
+            Some(typ) => typ,
+            None => {
+                self.sink.emit(ExpectedTypeFoundExpr {
+                    loc: typexpr.loc.clone().unwrap(),
+                });
+
+                Type::Void
+            }
+        }
+    }
+
+    /// Checks the body of a function returning `ret_typ`, in its own function
+    /// context, labels and the return type of the enclosing function are not
+    /// visible.
+    pub fn ck_fun_body(
+        &mut self,
+        body: &mut ScBlock,
+        ret_typ: Type,
+        ret_typ_loc: OSpan,
+    ) -> Result<(), Diagnostic> {
+        let fun_retty = mem::replace(&mut self.fun_retty, ret_typ.clone());
+        let fun_retty_loc = mem::replace(&mut self.fun_retty_loc, ret_typ_loc);
+        let label_stack = mem::take(&mut self.label_stack);
+        let defer_loc = self.defer_loc.take();
+
+        let res = self.ck_block(body, Some(ret_typ.clone()));
+
+        if res.is_ok() {
+            self.block_typeck(
+                &ret_typ,
+                body,
+                self.fun_retty_loc.clone(),
+                None,
+                body.last_expr
+                    .as_ref()
+                    .map(|expr| expr.loc.clone())
+                    .unwrap_or(body.loc.clone())
+                    .unwrap(),
+            );
+        }
+
+        self.fun_retty = fun_retty;
+        self.fun_retty_loc = fun_retty_loc;
+        self.label_stack = label_stack;
+        self.defer_loc = defer_loc;
+
+        res
+    }
+
+    pub fn ck_expr(
+        &mut self,
+        expr: &mut ScExpression,
+        mut coerce_to: Option<Type>,
+    ) -> Result<(), Diagnostic> {
+        if let Some(Type::Unknown) = coerce_to {
+            coerce_to = None;
+        }
+
+        match &mut expr.expr {
+            ScExpr::IntLit(_, Some(suffix)) => {
+                expr.typ = match suffix {
+                    IntSuffix::I8 => Type::I8,
+                    IntSuffix::I16 => Type::I16,
+                    IntSuffix::I32 => Type::I32,
+                    IntSuffix::I64 => Type::I64,
+                    IntSuffix::I128 => Type::I128,
+                    IntSuffix::Isz => Type::Isz,
+                    IntSuffix::U8 => Type::U8,
+                    IntSuffix::U16 => Type::U16,
+                    IntSuffix::U32 => Type::U32,
+                    IntSuffix::U64 => Type::U64,
+                    IntSuffix::U128 => Type::U128,
+                    IntSuffix::Usz => Type::Usz,
+                };
+            }
+            ScExpr::IntLit(_, None) => {
+                if let Some(coercion) = coerce_to
+                    && coercion.is_int()
+                {
+                    expr.typ = coercion;
+                } else {
+                    expr.typ = Type::I32;
+                }
+            }
+            ScExpr::BoolLit(_) => {
+                expr.typ = Type::Bool;
+            }
             ScExpr::StringLit(_) => {
                 // NOTE: string literals have a `*str` type.
 
@@ -815,16 +1334,55 @@ impl SemaChecker {
                 };
             }
             ScExpr::CharLit(_) => {
-                expr.typ = Type::Char;
+                // NOTE: a character literal can be used as an integer, its
+                // value is its codepoint.
+                if let Some(coercion) = coerce_to
+                    && coercion.is_int()
+                {
+                    expr.typ = coercion;
+                } else {
+                    expr.typ = Type::Char;
+                }
+            }
+            ScExpr::FloatLit(_, Some(suffix)) => {
+                expr.typ = match suffix {
+                    FloatSuffix::F16 => Type::F16,
+                    FloatSuffix::F32 => Type::F32,
+                    FloatSuffix::F64 => Type::F64,
+                    FloatSuffix::F128 => Type::F128,
+                };
             }
-            ScExpr::FloatLit(_) => {
+            ScExpr::FloatLit(_, None) => {
                 if let Some(coercion) = coerce_to
                     && coercion.is_float()
                 {
                     expr.typ = coercion;
                 } else {
-                    expr.typ = Type::F32;
+                    expr.typ = Type::F64;
+                }
+            }
+            ScExpr::Tuple(exprs) if exprs.is_empty() => {
+                expr.typ = Type::Void;
+            }
+            ScExpr::Tuple(exprs) => {
+                let coercions = match coerce_to {
+                    Some(Type::Tuple(typs)) if typs.len() == exprs.len() => {
+                        typs.into_iter().map(Some).collect()
+                    }
+                    Some(Type::Type) => vec![Some(Type::Type); exprs.len()],
+                    _ => vec![None; exprs.len()],
+                };
+
+                for (exp, coercion) in exprs.iter_mut().zip(coercions) {
+                    self.ck_expr(exp, coercion)?;
                 }
+
+                // a tuple of types is a tuple type, `(i32, bool)`
+                expr.typ = if exprs.iter().all(|exp| exp.typ == Type::Type) {
+                    Type::Type
+                } else {
+                    Type::Tuple(exprs.iter().map(|exp| exp.typ.clone()).collect())
+                };
             }
             ScExpr::Ident(symref) => {
                 expr.typ = symref.typ();
@@ -856,17 +1414,70 @@ impl SemaChecker {
 
                 expr.typ = Type::Void;
             }
+            ScExpr::Binary {
+                lhs,
+                op: op @ (BinOp::LogicalAnd | BinOp::LogicalOr),
+                rhs,
+            } => {
+                // NOTE: logical operators are not bitwise operators, they
+                // short-circuit so their operands must be booleans.
+                let note = format!("the operands of the logical operator '{op}' must be booleans");
+
+                self.ck_expr(lhs, Some(Type::Bool))?;
+                self.ck_expr(rhs, Some(Type::Bool))?;
+
+                self.expr_typeck(&Type::Bool, lhs, None, note.clone());
+                self.expr_typeck(&Type::Bool, rhs, None, note);
+
+                expr.typ = Type::Bool;
+            }
+            ScExpr::Binary {
+                lhs,
+                op: op @ (BinOp::BitwiseAnd | BinOp::BitwiseOr | BinOp::BitwiseXor),
+                rhs,
+            } => {
+                self.ck_expr(lhs, coerce_to.clone())?;
+                self.ck_expr(rhs, coerce_to)?;
+
+                self.ck_int_operand(op, lhs);
+                self.ck_int_operand(op, rhs);
+
+                if lhs.typ.is_int() && rhs.typ.is_int() && lhs.typ != rhs.typ {
+                    self.expr_typeck(&lhs.typ, rhs, None, None);
+                }
+
+                expr.typ = lhs.typ.clone();
+            }
+            ScExpr::Binary {
+                lhs,
+                op: op @ (BinOp::Shl | BinOp::Shr),
+                rhs,
+            } => {
+                // NOTE: the amount of a shift can be an integer of any type
+                self.ck_expr(lhs, coerce_to)?;
+                self.ck_expr(rhs, None)?;
+
+                self.ck_int_operand(op, lhs);
+                self.ck_int_operand(op, rhs);
+
+                expr.typ = lhs.typ.clone();
+            }
             ScExpr::Binary { lhs, op, rhs } => {
+                // the operands of a comparison don't have the type of its
+                // result
+                let coerce_to = if op.is_relational() { None } else { coerce_to };
+
                 self.ck_expr(lhs, coerce_to.clone())?;
                 self.ck_expr(rhs, coerce_to)?;
 
-                if lhs.typ != Type::Unknown && lhs.typ != rhs.typ {
+                // NOTE: `null == ptr` coerces `null` like `ptr == null`
+                if lhs.typ != Type::Unknown && lhs.typ != Type::Null && lhs.typ != rhs.typ {
                     self.expr_typeck(&lhs.typ, rhs, None, None);
                 } else if lhs.typ != rhs.typ {
                     self.expr_typeck(&rhs.typ, lhs, None, None);
                 }
 
-                expr.typ = if op.is_relational() || op.is_logical() {
+                expr.typ = if op.is_relational() {
                     Type::Bool
                 } else if let Type::Unknown = lhs.typ {
                     rhs.typ.clone()
@@ -874,6 +1485,44 @@ impl SemaChecker {
                     lhs.typ.clone()
                 };
             }
+            ScExpr::Cast { expr: exp, typexpr } => {
+                let typ = self.ck_typexpr(typexpr);
+
+                self.ck_expr(exp, None)?;
+
+                // an untyped literal or `null` takes the type it is cast to
+                // if it can, like `5000000000 as i64`, `'c' as u32` or
+                // `null as *u8`, an integer literal that doesn't fit in the
+                // type is truncated. An integer literal cast to a character
+                // is a byte, like `65 as char`.
+                let lit_typ = match (&exp.expr, &typ) {
+                    (ScExpr::IntLit(_, None), Type::Char) => Type::U8,
+                    _ => typ.clone(),
+                };
+
+                let coerce = match (&exp.expr, lit_typ.integer_range(&self.target)) {
+                    (ScExpr::IntLit(i, None), Some(range)) => {
+                        i128::try_from(*i).is_ok_and(|i| range.contains(&i))
+                    }
+                    (ScExpr::CharLit(c), Some(range)) => range.contains(&(*c as i128)),
+                    (ScExpr::FloatLit(_, None) | ScExpr::Null, _) => true,
+                    _ => false,
+                };
+
+                if coerce && exp.typ.can_coerce(&lit_typ) {
+                    let _ = Self::apply_typ_on_expr(exp, lit_typ);
+                }
+
+                if exp.typ != Type::Unknown && !exp.typ.can_cast(&typ) {
+                    self.sink.emit(InvalidCast {
+                        from: exp.typ.clone(),
+                        to: typ.clone(),
+                        loc: expr.loc.clone().unwrap(),
+                    });
+                }
+
+                expr.typ = typ;
+            }
             ScExpr::Unary { op, expr: exp } => match op {
                 UnaryOp::Negation => {
                     self.ck_expr(exp, coerce_to)?;
@@ -917,11 +1566,25 @@ impl SemaChecker {
                     }
                 }
                 UnaryOp::Not => {
-                    self.ck_expr(exp, Some(Type::Bool))?;
+                    // `!` is the logical not of a boolean and the bitwise not
+                    // of an integer
+                    self.ck_expr(exp, coerce_to.or(Some(Type::Bool)))?;
 
-                    self.expr_typeck(&Type::Bool, exp, None, None);
+                    if exp.typ.is_int() {
+                        expr.typ = exp.typ.clone();
+                    } else {
+                        if !matches!(exp.typ, Type::Bool | Type::Noreturn) {
+                            self.sink.emit(MismatchedTypes {
+                                expected: vec!["bool", "integer"],
+                                found: exp.typ.clone(),
+                                due_to: None,
+                                notes: vec![],
+                                loc: exp.loc.clone().unwrap(),
+                            });
+                        }
 
-                    expr.typ = Type::Bool;
+                        expr.typ = Type::Bool;
+                    }
                 }
                 UnaryOp::Dereference => {
                     // NOTE: here we tell the type checker `we if you have no idea try to coerce the expression to *T`.
@@ -973,8 +1636,103 @@ impl SemaChecker {
                     typ: Box::new(exp.typ.clone()),
                 };
             }
-            ScExpr::FunCall { callee, args } => {
-                self.ck_expr(callee, None)?;
+            ScExpr::Range {
+                start,
+                end,
+                inclusive: _,
+            } => {
+                let inner_coerce = if let Some(Type::Range(typ)) = &coerce_to {
+                    Some((**typ).clone())
+                } else {
+                    None
+                };
+
+                if let Some(start) = start {
+                    self.ck_expr(start, inner_coerce.clone())?;
+                }
+
+                if let Some(end) = end {
+                    let end_coerce = start
+                        .as_ref()
+                        .map(|s| s.typ.clone())
+                        .or(inner_coerce.clone());
+                    self.ck_expr(end, end_coerce)?;
+                }
+
+                if let (Some(start), Some(end)) = (start.as_mut(), end.as_mut()) {
+                    // NOTE: a start without an explicit type takes the type of
+                    // the end, like `0` in `0..<n`
+                    let start_follows_end = start.typ != end.typ
+                        && start.typ != Type::Unknown
+                        && end.typ != Type::Unknown
+                        && start.typ.can_coerce(&end.typ)
+                        && Self::apply_typ_on_expr(start, end.typ.clone()).is_some();
+
+                    if !start_follows_end {
+                        self.expr_typeck(&start.typ, end, start.loc.clone(), None);
+                    }
+                }
+
+                // the type of the bounds of the range
+                let bound = start.as_ref().or(end.as_ref());
+
+                let inner = match bound {
+                    Some(bound) if !bound.typ.is_int() && bound.typ != Type::Unknown => {
+                        self.sink.emit(MismatchedTypes {
+                            expected: vec!["integer"],
+                            found: bound.typ.clone(),
+                            due_to: None,
+                            notes: vec!["the bounds of a range must be integers".to_string()],
+                            loc: bound.loc.clone().unwrap(),
+                        });
+
+                        bound.typ.clone()
+                    }
+                    Some(bound) => bound.typ.clone(),
+                    None => inner_coerce.unwrap_or(Type::I32),
+                };
+
+                expr.typ = Type::Range(Box::new(inner));
+            }
+            ScExpr::FunCall {
+                callee,
+                args,
+                named_args,
+            } => {
+                if let Some(arg) = named_args.first()
+                    && let ScExpr::Ident(sym) | ScExpr::QualifiedPath { sym, .. } = &callee.expr
+                    && self.is_generic(sym)
+                {
+                    return Err(feature_todo! {
+                        feature: "named arguments in calls to generic functions",
+                        label: "named argument",
+                        loc: arg.loc.clone().unwrap(),
+                    });
+                }
+
+                // a call to a generic function is replaced by a call to its
+                // instance
+                self.instantiate(callee, args, expr.loc.clone().unwrap())?;
+
+                // a method call `recv.method(args..)` is checked like a call
+                // to `T.method(recv, args..)`, the receiver is already checked
+                let is_method_call =
+                    if let ScExpr::MemberAccess { expr: recv, member } = &mut callee.expr {
+                        self.ck_expr(recv, None)?;
+                        self.instantiate_impls(&recv.typ, callee.loc.clone().unwrap());
+
+                        if let Some(sym) = self.method(&recv.typ, member) {
+                            self.ck_assoc_vis(&recv.typ, member, callee.loc.clone().unwrap());
+                            self.lower_method_call(callee, args, sym);
+                            true
+                        } else {
+                            self.ck_member_access(callee)?;
+                            false
+                        }
+                    } else {
+                        self.ck_expr(callee, None)?;
+                        false
+                    };
 
                 let Type::FunPtr {
                     args: args_ty,
@@ -988,6 +1746,12 @@ impl SemaChecker {
                     .into_diag());
                 };
 
+                if !self.resolve_call_args(callee, args, named_args) {
+                    expr.typ = (**ret_ty).clone();
+
+                    return Ok(());
+                }
+
                 let mut args_ty: Vec<Type> = args_ty.clone();
 
                 if args_ty.len() != args.len() {
@@ -995,6 +1759,7 @@ impl SemaChecker {
                     self.sink.emit(ArityDoesntMatch {
                         expected: args_ty.len(),
                         got: args.len(),
+                        missing: Vec::new(),
                         loc: callee.loc.clone().unwrap(),
                     });
 
@@ -1005,8 +1770,10 @@ impl SemaChecker {
                     }
                 }
 
-                for (arg, aty) in zip(args, args_ty) {
-                    self.ck_expr(arg, Some(aty.clone()))?;
+                for (i, (arg, aty)) in zip(args, args_ty).enumerate() {
+                    if !(is_method_call && i == 0) {
+                        self.ck_expr(arg, Some(aty.clone()))?;
+                    }
 
                     self.expr_typeck(&aty, arg, None, None);
                 }
@@ -1027,7 +1794,22 @@ impl SemaChecker {
                 if let Some(else_br) = else_br {
                     self.ck_expr(else_br, Some(then_br.typ.clone()))?;
 
-                    self.expr_typeck(&then_br.typ, else_br, None, None);
+                    // point to the value of the then branch
+                    let then_loc = match &then_br.expr {
+                        ScExpr::Block { block, .. } => block
+                            .last_expr
+                            .as_ref()
+                            .and_then(|last| last.loc.clone())
+                            .or(then_br.loc.clone()),
+                        _ => then_br.loc.clone(),
+                    };
+
+                    self.expr_typeck(
+                        &then_br.typ,
+                        else_br,
+                        then_loc,
+                        "the branches of an if expression must have the same type".to_string(),
+                    );
 
                     expr.typ = then_br.typ.clone();
                 } else {
@@ -1078,12 +1860,11 @@ impl SemaChecker {
                     body.stmts.first(),
                     Some(ScStatement {
                         stmt: ScStmt::Expression(ScExpression {
-                            expr: ScExpr::If { .. },
-                            typ: _,
-                            loc: None
+                            expr: ScExpr::If { then_br, .. },
+                            ..
                         }),
-                        loc: None
-                    })
+                        ..
+                    }) if matches!(then_br.expr, ScExpr::Break { desugared: true, .. })
                 );
 
                 let kind = if is_predicate_loop {
@@ -1115,6 +1896,13 @@ impl SemaChecker {
                 };
             }
             ScExpr::Return { expr: exp } => {
+                if let Some(defer_loc) = self.defer_loc.clone() {
+                    self.sink.emit(ReturnInDefer {
+                        loc_defer: defer_loc,
+                        loc: expr.loc.clone().unwrap(),
+                    });
+                }
+
                 if let Some(exp) = exp {
                     self.ck_expr(exp, Some(self.fun_retty.clone()))?;
 
@@ -1141,14 +1929,12 @@ impl SemaChecker {
                 label,
                 expr: exp,
                 index,
+                desugared,
             } => {
                 // assign loop index
-                let (typ, kind) = if let Some(label) = label {
+                let typ = if let Some(label) = label {
                     let Some(LabelInfo {
-                        index: idx,
-                        typ,
-                        kind,
-                        ..
+                        index: idx, typ, ..
                     }) = self.label_stack.get_by_name(&label)
                     else {
                         return Err(UseOfUndefinedLabel {
@@ -1161,7 +1947,7 @@ impl SemaChecker {
 
                     *index = Some(*idx);
 
-                    (typ.clone(), kind.clone())
+                    typ.clone()
                 } else {
                     let Some(LabelInfo {
                         index: idx,
@@ -1186,34 +1972,39 @@ impl SemaChecker {
 
                     *index = Some(*idx);
 
-                    (typ.clone(), kind.clone())
+                    typ.clone()
                 };
 
+                self.ck_jump_out_of_defer("break", index.unwrap(), expr.loc.clone());
+
                 // we indicate that we used this label inside a break.
                 self.label_stack.set_breaked_out(index.unwrap());
 
                 if let Some(exp) = exp {
                     self.ck_expr(exp, None)?;
 
-                    if !kind.can_have_val() {
-                        self.sink.emit(BreakWithValueUnsupported {
-                            loc: expr.loc.clone().unwrap(),
-                        })
-                    } else if typ == Type::Unknown {
+                    if typ == Type::Unknown {
                         let info = self
                             .label_stack
                             .get_mut_by_idx(index.unwrap())
                             .expect("should've get a label info");
 
                         info.typ = exp.typ.clone();
+                        info.typ_loc = exp.loc.clone();
                     } else {
                         let info = self
                             .label_stack
                             .get_by_idx(index.unwrap())
                             .expect("should've get a label info");
 
-                        self.expr_typeck(&info.typ.clone(), exp, None, None);
+                        let (typ, typ_loc) = (info.typ.clone(), info.typ_loc.clone());
+
+                        self.expr_typeck(&typ, exp, typ_loc, None);
                     }
+                } else if *desugared {
+                    // NOTE: it is the `break` of the condition of a loop, added
+                    // by the desugaring. It doesn't give a type to the loop,
+                    // its type comes from the `break`s of the user.
                 } else if typ == Type::Unknown {
                     let info = self
                         .label_stack
@@ -1221,7 +2012,8 @@ impl SemaChecker {
                         .expect("should've get a label info");
 
                     info.typ = Type::Void;
-                } else {
+                    info.typ_loc = expr.loc.clone();
+                } else if typ != Type::Void {
                     let info = self
                         .label_stack
                         .get_by_idx(index.unwrap())
@@ -1231,7 +2023,7 @@ impl SemaChecker {
                     self.sink.emit(MismatchedTypes {
                         expected: vec![info.typ.clone()],
                         found: Type::Void,
-                        due_to: None,
+                        due_to: info.typ_loc.clone(),
                         notes: vec![],
                         loc: expr.loc.clone().unwrap(),
                     });
@@ -1290,45 +2082,56 @@ impl SemaChecker {
                     }
                 }
 
+                if let Some(index) = index {
+                    self.ck_jump_out_of_defer("continue", *index, expr.loc.clone());
+                }
+
                 expr.typ = Type::Noreturn;
             }
             ScExpr::Null => {
-                self.sink.emit(feature_todo! {
-                    feature: "optional types",
-                    label: "'null' represent not having a value",
-                    loc: expr.loc.clone().unwrap()
-                });
-
-                expr.typ = Type::Void;
+                // NOTE: `null` takes the pointer type it is coerced to, if it
+                // has none it is coerced later, see `expr_typeck`.
+                expr.typ = match coerce_to {
+                    Some(ptr @ Type::Ptr { .. }) => ptr,
+                    _ => Type::Null,
+                };
             }
-            ScExpr::MemberAccess { expr: _, member: _ } => {
-                self.sink.emit(feature_todo! {
-                    feature: "field access",
-                    label: "field access and struct type definition",
-                    loc: expr.loc.clone().unwrap()
-                });
-
-                expr.typ = Type::Void;
+            ScExpr::Comptime(exp) => {
+                self.ck_expr(exp, coerce_to)?;
+
+                // NOTE: the expression is typed before it is evaluated, so that
+                // a comptime expression that can't be evaluated is kept
+                // unevaluated with the type of its expression.
+                expr.typ = exp.typ.clone();
+
+                match self.evaluate_expr(exp) {
+                    // the comptime expression is replaced by the literal of its
+                    // value, values without a literal form (types, void) are
+                    // replaced by the checked expression itself.
+                    Ok(value) => {
+                        *expr = match Self::value_to_expr(value, exp.typ.clone(), expr.loc.clone())
+                        {
+                            Some(lit) => lit,
+                            None => (**exp).clone(),
+                        };
+                    }
+                    Err((loc, note)) => self.sink.emit(CantResolveComptimeValue {
+                        note,
+                        loc_expr: exp.loc.clone().unwrap(),
+                        loc,
+                    }),
+                }
             }
-            ScExpr::QualifiedPath {
-                path: _,
-                sym: symref,
+            ScExpr::MemberAccess {
+                expr: exp,
+                member: _,
             } => {
-                expr.typ = symref.typ();
-            }
-            ScExpr::Underscore => {
-                // NOTE: we keep the typ unknown because the underscore
-                // expression is only valid in lhs of assignment and we
-                // have a special case for it.
+                self.ck_expr(exp, None)?;
+
+                self.ck_member_access(expr)?;
             }
-            ScExpr::PointerType {
-                mutable: _,
-                typexpr,
-            } => {
-                match self.ck_expr(typexpr, Some(Type::Type)) {
-                    Ok(()) => {}
-                    Err(d) => self.sink.emit(d),
-                }
+            ScExpr::StructLit { typexpr, fields } => {
+                self.ck_expr(typexpr, Some(Type::Type))?;
 
                 if typexpr.typ != Type::Type {
                     self.sink.emit(ExpectedTypeFoundExpr {
@@ -1336,29 +2139,167 @@ impl SemaChecker {
                     });
                 }
 
-                expr.typ = Type::Type;
-            }
-            ScExpr::FunPtrType { args, ret } => {
-                for arg in args {
-                    match self.ck_expr(arg, Some(Type::Type)) {
-                        Ok(()) => {}
-                        Err(d) => self.sink.emit(d),
+                let typ = match self.evaluate_expr(typexpr) {
+                    Ok(value) => value.as_type().unwrap_or(Type::Void),
+                    Err((loc, note)) => {
+                        return Err(CantResolveComptimeValue {
+                            note,
+                            loc_expr: typexpr.loc.clone().unwrap(),
+                            loc,
+                        }
+                        .into_diag());
                     }
+                };
 
-                    if arg.typ != Type::Type {
-                        self.sink.emit(ExpectedTypeFoundExpr {
-                            loc: arg.loc.clone().unwrap(),
-                        });
+                let Some(info) = self.struct_info(&typ).cloned() else {
+                    return Err(MismatchedTypes {
+                        expected: vec!["struct"],
+                        found: typ,
+                        due_to: None,
+                        notes: vec![
+                            "only struct types can be used in a struct literal".to_string(),
+                        ],
+                        loc: typexpr.loc.clone().unwrap(),
                     }
-                }
+                    .into_diag());
+                };
 
-                if let Some(ret) = ret {
-                    match self.ck_expr(ret, Some(Type::Type)) {
-                        Ok(()) => {}
-                        Err(d) => self.sink.emit(d),
+                let mut defined: Vec<(&str, OSpan)> = Vec::new();
+
+                for field in fields.iter_mut() {
+                    if let Some((_, loc_first)) =
+                        defined.iter().find(|(name, _)| *name == field.name)
+                    {
+                        self.sink.emit(FieldDefinedMultipleTimes {
+                            field: field.name.clone(),
+                            loc_first: loc_first.clone().unwrap(),
+                            loc: field.name_loc.clone().unwrap(),
+                        });
                     }
 
-                    if ret.typ != Type::Type {
+                    defined.push((&field.name, field.name_loc.clone()));
+
+                    let Some(field_typ) = info.field(&field.name) else {
+                        self.sink.emit(NoFieldOnType {
+                            kind: "field",
+                            field: field.name.clone(),
+                            typ: typ.clone(),
+                            note: info
+                                .suggest_field(&field.name)
+                                .map(|suggested| format!("did you mean '{suggested}'?")),
+                            loc: field.name_loc.clone().unwrap(),
+                        });
+
+                        // we still check the value to report its errors
+                        self.ck_expr(&mut field.value, None)?;
+                        continue;
+                    };
+
+                    self.ck_expr(&mut field.value, Some(field_typ.clone()))?;
+
+                    self.expr_typeck(field_typ, &mut field.value, None, None);
+                }
+
+                let missing = info
+                    .fields
+                    .iter()
+                    .filter(|(name, _)| !defined.iter().any(|(def, _)| def == name))
+                    .map(|(name, _)| name.clone())
+                    .collect::<Vec<_>>();
+
+                if !missing.is_empty() {
+                    self.sink.emit(MissingFieldsInLiteral {
+                        fields: missing,
+                        typ: typ.clone(),
+                        loc: expr.loc.clone().unwrap(),
+                    });
+                }
+
+                expr.typ = typ;
+            }
+            ScExpr::QualifiedPath {
+                path: _,
+                sym: symref,
+            } => {
+                expr.typ = symref.typ();
+            }
+            ScExpr::FunDefinition {
+                args,
+                rettypexpr,
+                body,
+            } => {
+                if let Some(arg) = args.iter().find(|arg| arg.comptime) {
+                    self.sink.emit(feature_todo! {
+                        feature: "comptime parameters in function expressions",
+                        label: "only global function definitions can be generic",
+                        loc: arg.loc.clone().unwrap(),
+                    });
+                }
+
+                if let Some(default) = args.iter().find_map(|arg| arg.default.as_ref()) {
+                    self.sink.emit(feature_todo! {
+                        feature: "default values in function expressions",
+                        label: "only global function definitions can have default values",
+                        loc: default.loc.clone().unwrap(),
+                    });
+                }
+
+                let (args_typ, ret_typ) = self.ck_fun_signature(args, rettypexpr);
+
+                self.ck_fun_body(
+                    body,
+                    ret_typ.clone(),
+                    rettypexpr.as_ref().and_then(|typexpr| typexpr.loc.clone()),
+                )?;
+
+                expr.typ = Type::FunPtr {
+                    args: args_typ,
+                    ret: Box::new(ret_typ),
+                };
+            }
+            ScExpr::Underscore => {
+                // NOTE: we keep the typ unknown because the underscore
+                // expression is only valid in lhs of assignment and we
+                // have a special case for it.
+            }
+            ScExpr::PointerType {
+                mutable: _,
+                typexpr,
+            } => {
+                match self.ck_expr(typexpr, Some(Type::Type)) {
+                    Ok(()) => {}
+                    Err(d) => self.sink.emit(d),
+                }
+
+                if typexpr.typ != Type::Type {
+                    self.sink.emit(ExpectedTypeFoundExpr {
+                        loc: typexpr.loc.clone().unwrap(),
+                    });
+                }
+
+                expr.typ = Type::Type;
+            }
+            ScExpr::FunPtrType { args, ret } => {
+                for arg in args {
+                    match self.ck_expr(arg, Some(Type::Type)) {
+                        Ok(()) => {}
+                        Err(d) => self.sink.emit(d),
+                    }
+
+                    if arg.typ != Type::Type {
+                        self.sink.emit(ExpectedTypeFoundExpr {
+                            loc: arg.loc.clone().unwrap(),
+                        });
+                    }
+                }
+
+                if let Some(ret) = ret {
+                    match self.ck_expr(ret, Some(Type::Type)) {
+                        Ok(()) => {}
+                        Err(d) => self.sink.emit(d),
+                    }
+
+                    if ret.typ != Type::Type {
                         self.sink.emit(ExpectedTypeFoundExpr {
                             loc: ret.loc.clone().unwrap(),
                         });
@@ -1367,6 +2308,217 @@ impl SemaChecker {
 
                 expr.typ = Type::Type;
             }
+            ScExpr::Struct { .. } if expr.typ == Type::Type => {
+                // NOTE: the struct was already checked in the pre check of
+                // its global definition.
+            }
+            ScExpr::Struct { fields, index } => {
+                let index =
+                    *index.get_or_insert_with(|| self.define_struct(None, expr.loc.clone()));
+
+                let mut fields_typ: Vec<(String, Type)> = Vec::new();
+                let mut defined: Vec<(&str, OSpan)> = Vec::new();
+
+                for field in fields.iter_mut() {
+                    if let Some((_, loc_first)) =
+                        defined.iter().find(|(name, _)| *name == field.name)
+                    {
+                        self.sink.emit(FieldDefinedMultipleTimes {
+                            field: field.name.clone(),
+                            loc_first: loc_first.clone().unwrap(),
+                            loc: field.name_loc.clone().unwrap(),
+                        });
+                    }
+
+                    defined.push((&field.name, field.name_loc.clone()));
+
+                    match self.ck_expr(&mut field.typexpr, Some(Type::Type)) {
+                        Ok(()) => {}
+                        Err(d) => self.sink.emit(d),
+                    }
+
+                    let value_typ_field = match self.evaluate_expr(&field.typexpr) {
+                        Ok(typ) => typ,
+                        Err((loc, note)) => {
+                            self.sink.emit(CantResolveComptimeValue {
+                                note,
+                                loc_expr: field.typexpr.loc.clone().unwrap(),
+                                loc: loc.clone(),
+                            });
+
+                            ValueExpr::Type(Type::Void)
+                        }
+                    };
+
+                    let field_typ = match value_typ_field.as_type() {
+                        Some(typ) => typ,
+                        None => {
+                            self.sink.emit(ExpectedTypeFoundExpr {
+                                loc: field.typexpr.loc.clone().unwrap(),
+                            });
+
+                            Type::Void
+                        }
+                    };
+
+                    fields_typ.push((field.name.clone(), field_typ));
+                }
+
+                self.structs[index].fields = fields_typ;
+
+                expr.typ = Type::Type;
+            }
+            ScExpr::Enum { .. } if expr.typ == Type::Type => {
+                // NOTE: the enum was already checked in the pre check of its
+                // global definition.
+            }
+            ScExpr::Enum { variants, index } => {
+                let index = *index.get_or_insert_with(|| self.define_enum(None, expr.loc.clone()));
+
+                let mut variants_info: Vec<(String, i128)> = Vec::new();
+                let mut defined: Vec<(&str, i128, OSpan)> = Vec::new();
+                // the discriminant of a variant without an explicit one is the
+                // discriminant of the previous variant plus one
+                let mut next = 0;
+
+                for variant in variants.iter_mut() {
+                    let redefined = if let Some((_, _, loc_first)) =
+                        defined.iter().find(|(name, _, _)| *name == variant.name)
+                    {
+                        self.sink.emit(VariantDefinedMultipleTimes {
+                            variant: variant.name.clone(),
+                            loc_first: loc_first.clone().unwrap(),
+                            loc: variant.name_loc.clone().unwrap(),
+                        });
+
+                        true
+                    } else {
+                        false
+                    };
+
+                    let discriminant = match &mut variant.value {
+                        Some(value) => self.ck_discriminant(value).unwrap_or(next),
+                        None => next,
+                    };
+
+                    if let Some((name_first, _, loc_first)) =
+                        defined.iter().find(|(_, first, _)| *first == discriminant)
+                    {
+                        self.sink.emit(DuplicateDiscriminant {
+                            discriminant,
+                            variant_first: name_first.to_string(),
+                            loc_first: loc_first.clone().unwrap(),
+                            variant: variant.name.clone(),
+                            loc: variant.loc.clone().unwrap(),
+                        });
+                    }
+
+                    defined.push((&variant.name, discriminant, variant.loc.clone()));
+
+                    if !redefined {
+                        variants_info.push((variant.name.clone(), discriminant));
+                    }
+
+                    next = discriminant.wrapping_add(1);
+                }
+
+                self.enums[index].variants = variants_info;
+
+                expr.typ = Type::Type;
+            }
+            ScExpr::Match { expr: exp, arms } => {
+                self.ck_expr(exp, None)?;
+
+                // the type of the match is the type of the first arm that
+                // doesn't diverge
+                let mut typ: Option<(Type, OSpan)> = None;
+                let mut values = Vec::new();
+
+                for arm in arms.iter_mut() {
+                    for pat in &mut arm.pats {
+                        if pat.is_underscore() {
+                            continue;
+                        }
+
+                        match self.ck_expr(pat, Some(exp.typ.clone())) {
+                            Ok(()) => {}
+                            Err(d) => {
+                                self.sink.emit(d);
+                                continue;
+                            }
+                        }
+
+                        self.expr_typeck(
+                            &exp.typ,
+                            pat,
+                            exp.loc.clone(),
+                            "the patterns of a match must have the type of the matched expression"
+                                .to_string(),
+                        );
+
+                        match self.evaluate_expr(pat) {
+                            Ok(value) => values.push(value),
+                            Err((loc, note)) => self.sink.emit(CantResolveComptimeValue {
+                                note,
+                                loc_expr: pat.loc.clone().unwrap(),
+                                loc,
+                            }),
+                        }
+                    }
+
+                    let coerce_to = typ
+                        .as_ref()
+                        .map(|(typ, _)| typ.clone())
+                        .or(coerce_to.clone());
+
+                    match self.ck_expr(&mut arm.body, coerce_to) {
+                        Ok(()) => {}
+                        Err(d) => {
+                            self.sink.emit(d);
+                            continue;
+                        }
+                    }
+
+                    match &typ {
+                        // NOTE: an arm that diverges fits any type
+                        _ if arm.body.typ == Type::Noreturn => {}
+                        Some((typ, typ_loc)) => self.expr_typeck(
+                            typ,
+                            &mut arm.body,
+                            typ_loc.clone(),
+                            "the arms of a match expression must have the same type".to_string(),
+                        ),
+                        None => {
+                            // point to the value of the arm
+                            let loc = match &arm.body.expr {
+                                ScExpr::Block { block, .. } => block
+                                    .last_expr
+                                    .as_ref()
+                                    .and_then(|last| last.loc.clone())
+                                    .or(arm.body.loc.clone()),
+                                _ => arm.body.loc.clone(),
+                            };
+
+                            typ = Some((arm.body.typ.clone(), loc));
+                        }
+                    }
+                }
+
+                let has_wildcard = arms
+                    .iter()
+                    .flat_map(|arm| &arm.pats)
+                    .any(ScExpression::is_underscore);
+
+                if !has_wildcard {
+                    self.ck_match_exhaustiveness(exp, &values);
+                }
+
+                expr.typ = match typ {
+                    Some((typ, _)) => typ,
+                    None if arms.is_empty() => Type::Void,
+                    None => Type::Noreturn,
+                };
+            }
             ScExpr::Poisoned { diag } => {
                 self.sink.emit(diag.take().unwrap());
 
@@ -1379,22 +2531,472 @@ impl SemaChecker {
         Ok(())
     }
 
+    /// Checks that the already checked `operand` of the bitwise or shift
+    /// operator `op` is an integer.
+    fn ck_int_operand(&mut self, op: &BinOp, operand: &ScExpression) {
+        if operand.typ.is_int() || operand.typ == Type::Noreturn {
+            return;
+        }
+
+        let mut notes = vec![format!("the operands of '{op}' must be integers")];
+
+        match (op, &operand.typ) {
+            (BinOp::BitwiseAnd, Type::Bool) => {
+                notes.push("use the logical operator 'and' on booleans".to_string())
+            }
+            (BinOp::BitwiseOr, Type::Bool) => {
+                notes.push("use the logical operator 'or' on booleans".to_string())
+            }
+            _ => {}
+        }
+
+        self.sink.emit(MismatchedTypes {
+            expected: vec!["integer"],
+            found: operand.typ.clone(),
+            due_to: None,
+            notes,
+            loc: operand.loc.clone().unwrap(),
+        });
+    }
+
+    /// Checks the explicit discriminant `value` of an enum variant and returns
+    /// it, returns `None` if it isn't an integer known at compile-time.
+    fn ck_discriminant(&mut self, value: &mut ScExpression) -> Option<i128> {
+        match self.ck_expr(value, Some(Type::Isz)) {
+            Ok(()) => {}
+            Err(d) => {
+                self.sink.emit(d);
+                return None;
+            }
+        }
+
+        if !value.typ.is_int() {
+            self.sink.emit(MismatchedTypes {
+                expected: vec!["integer"],
+                found: value.typ.clone(),
+                due_to: None,
+                notes: vec!["the discriminant of a variant must be an integer".to_string()],
+                loc: value.loc.clone().unwrap(),
+            });
+
+            return None;
+        }
+
+        match self.evaluate_expr(value) {
+            Ok(value) => value.as_i128(),
+            Err((loc, note)) => {
+                self.sink.emit(CantResolveComptimeValue {
+                    note,
+                    loc_expr: value.loc.clone().unwrap(),
+                    loc,
+                });
+
+                None
+            }
+        }
+    }
+
+    /// Checks that the `values` of the patterns of a match without a wildcard
+    /// cover every value of the already checked matched expression `exp`.
+    fn ck_match_exhaustiveness(&mut self, exp: &ScExpression, values: &[ValueExpr]) {
+        let missing: Vec<String> = match &exp.typ {
+            Type::Enum { .. } => {
+                let Some(info) = self.enum_info(&exp.typ) else {
+                    return;
+                };
+
+                info.variants
+                    .iter()
+                    .filter(|(_, discriminant)| {
+                        !values
+                            .iter()
+                            .any(|value| value.as_i128() == Some(*discriminant))
+                    })
+                    .map(|(name, _)| name.clone())
+                    .collect()
+            }
+            Type::Bool => [true, false]
+                .into_iter()
+                .filter(|b| !values.iter().any(|value| value.as_bool() == Some(*b)))
+                .map(|b| b.to_string())
+                .collect(),
+            // NOTE: the matched expression never produces a value or was
+            // already reported
+            Type::Noreturn | Type::Unknown => return,
+            // the other types have too many values to be covered without a
+            // wildcard
+            _ => {
+                self.sink.emit(NonExhaustiveMatch {
+                    missing: Vec::new(),
+                    typ: exp.typ.clone(),
+                    loc: exp.loc.clone().unwrap(),
+                });
+
+                return;
+            }
+        };
+
+        if !missing.is_empty() {
+            self.sink.emit(NonExhaustiveMatch {
+                missing,
+                typ: exp.typ.clone(),
+                loc: exp.loc.clone().unwrap(),
+            });
+        }
+    }
+
+    /// Checks a member access whose inner expression was already checked, it
+    /// is either a field access on a struct or an access to an associated
+    /// item of a type, like `Point.new`.
+    fn ck_member_access(&mut self, expr: &mut ScExpression) -> Result<(), Diagnostic> {
+        let ScExpr::MemberAccess { expr: exp, member } = &mut expr.expr else {
+            // SAFETY: it is the caller's responsibility to call this function
+            // with a member access
+            opt_unreachable!()
+        };
+
+        if exp.typ == Type::Type {
+            // associated item
+            let typ = self
+                .evaluate_expr(exp)
+                .map_err(|(loc, note)| {
+                    CantResolveComptimeValue {
+                        note,
+                        loc_expr: exp.loc.clone().unwrap(),
+                        loc,
+                    }
+                    .into_diag()
+                })?
+                .as_type()
+                .unwrap_or(Type::Void);
+
+            self.instantiate_impls(&typ, expr.loc.clone().unwrap());
+
+            if self
+                .enum_info(&typ)
+                .and_then(|info| info.variant(member))
+                .is_some()
+            {
+                // variant of an enum, `Color.Red`
+                expr.typ = typ;
+                return Ok(());
+            }
+
+            let Some(AssocItem { sym, .. }) = self.assoc_item(&typ, member) else {
+                if let Some(info) = self.enum_info(&typ) {
+                    let note = info
+                        .suggest_variant(member)
+                        .map(|suggested| format!("did you mean '{suggested}'?"));
+
+                    self.sink.emit(NoFieldOnType {
+                        kind: "variant",
+                        field: member.clone(),
+                        typ,
+                        note,
+                        loc: expr.loc.clone().unwrap(),
+                    });
+
+                    // we set a dummy type
+                    expr.typ = Type::Void;
+                    return Ok(());
+                }
+
+                let names: Vec<&str> = self
+                    .assoc_items
+                    .iter()
+                    .filter(|item| item.typ == typ)
+                    .map(|item| item.name.as_str())
+                    .collect();
+
+                let note = suggest(member, &names, DEFAULT_MAX_LEVENSHTEIN_DISTANCE)
+                    .map(|suggested| format!("did you mean '{suggested}'?"));
+
+                self.sink.emit(NoFieldOnType {
+                    kind: "associated item",
+                    field: member.clone(),
+                    typ,
+                    note,
+                    loc: expr.loc.clone().unwrap(),
+                });
+
+                // we set a dummy type
+                expr.typ = Type::Void;
+                return Ok(());
+            };
+
+            let sym = sym.clone();
+
+            self.ck_assoc_vis(&typ, member, expr.loc.clone().unwrap());
+
+            expr.typ = sym.typ();
+            expr.expr = ScExpr::Ident(sym);
+
+            return Ok(());
+        }
+
+        if let Type::Tuple(typs) = &exp.typ {
+            // positional field of a tuple, `t.0`
+            expr.typ = match member.parse::<usize>().ok().and_then(|idx| typs.get(idx)) {
+                Some(typ) => typ.clone(),
+                None => {
+                    self.sink.emit(NoFieldOnType {
+                        kind: "field",
+                        field: member.clone(),
+                        typ: exp.typ.clone(),
+                        note: Some(format!(
+                            "the fields of a tuple are its indices, from 0 to {}",
+                            typs.len() - 1
+                        )),
+                        loc: expr.loc.clone().unwrap(),
+                    });
+
+                    // we set a dummy type
+                    Type::Void
+                }
+            };
+
+            return Ok(());
+        }
+
+        expr.typ = match self
+            .struct_info(&exp.typ)
+            .and_then(|info| info.field(member))
+        {
+            Some(typ) => typ.clone(),
+            None => {
+                let note = self
+                    .method(&exp.typ, member)
+                    .map(|_| format!("'{member}' is a method, it can only be called"))
+                    .or_else(|| {
+                        self.struct_info(&exp.typ)?
+                            .suggest_field(member)
+                            .map(|suggested| format!("did you mean '{suggested}'?"))
+                    });
+
+                self.sink.emit(NoFieldOnType {
+                    kind: "field",
+                    field: member.clone(),
+                    typ: exp.typ.clone(),
+                    note,
+                    loc: expr.loc.clone().unwrap(),
+                });
+
+                // we set a dummy type
+                Type::Void
+            }
+        };
+
+        Ok(())
+    }
+
+    /// Checks that the item `name` associated to `typ` can be used from the
+    /// module we are currently in. A private associated item can only be used
+    /// inside of the module of its impl block and its submodules.
+    fn ck_assoc_vis(&mut self, typ: &Type, name: &str, loc: Span) {
+        let Some(item) = self.assoc_item(typ, name) else {
+            return;
+        };
+
+        if item.vis == Vis::Public
+            || self
+                .module_path
+                .as_slice()
+                .starts_with(item.module.as_slice())
+        {
+            return;
+        }
+
+        self.sink.emit(PrivateDefinition {
+            name: format!("{typ}.{name}"),
+            loc_def: item.loc.clone(),
+            loc,
+        });
+    }
+
+    /// Reports a `break` or `continue` inside of a defer statement to the
+    /// label at `index`, if the label is outside of the defer.
+    fn ck_jump_out_of_defer(&mut self, kw: &'static str, index: usize, loc: OSpan) {
+        if let Some(loc_defer) = self.defer_loc.clone()
+            && index < self.defer_first_label
+        {
+            self.sink.emit(JumpOutOfDefer {
+                kw,
+                loc_defer,
+                loc: loc.unwrap(),
+            });
+        }
+    }
+
+    /// Moves the named arguments of a call to the position of their parameter
+    /// and fills the omitted arguments with their default value. Returns
+    /// `false` if an argument is still missing, the missing parameters are
+    /// reported by their name.
+    fn resolve_call_args(
+        &mut self,
+        callee: &ScExpression,
+        args: &mut Vec<ScExpression>,
+        named_args: &mut Vec<ScNamedArg>,
+    ) -> bool {
+        let fun = match &callee.expr {
+            ScExpr::Ident(sym) | ScExpr::QualifiedPath { sym, .. } => self.checked_fun_params(sym),
+            _ => None,
+        };
+
+        let Some(fun) = fun else {
+            for arg in named_args.drain(..) {
+                self.sink.emit(UnknownNamedArg {
+                    name: arg.name,
+                    fun: None,
+                    loc: arg.loc.unwrap(),
+                });
+            }
+
+            return true;
+        };
+
+        let mut slots: Vec<Option<ScExpression>> = args.drain(..).map(Some).collect();
+
+        if slots.len() < fun.params.len() {
+            slots.resize(fun.params.len(), None);
+        }
+
+        for arg in named_args.drain(..) {
+            let Some(idx) = fun.position(&arg.name) else {
+                self.sink.emit(UnknownNamedArg {
+                    name: arg.name,
+                    fun: fun.sym.loc().map(|loc| (fun.sym.name(), loc)),
+                    loc: arg.loc.unwrap(),
+                });
+
+                continue;
+            };
+
+            if let Some(first) = &slots[idx] {
+                self.sink.emit(ArgumentGivenMultipleTimes {
+                    name: arg.name,
+                    loc_first: first.loc.clone().unwrap(),
+                    loc: arg.loc.unwrap(),
+                });
+
+                continue;
+            }
+
+            slots[idx] = Some(arg.value);
+        }
+
+        // fill the omitted arguments with their default value
+        for (slot, (_, default)) in zip(&mut slots, &fun.params) {
+            if slot.is_none() {
+                *slot = default.clone();
+            }
+        }
+
+        let missing = zip(&slots, &fun.params)
+            .filter(|(slot, _)| slot.is_none())
+            .map(|(_, (name, _))| name.clone())
+            .collect::<Vec<_>>();
+
+        if !missing.is_empty() {
+            self.sink.emit(ArityDoesntMatch {
+                expected: fun.params.len(),
+                got: slots.iter().flatten().count(),
+                missing,
+                loc: callee.loc.clone().unwrap(),
+            });
+
+            return false;
+        }
+
+        *args = slots.into_iter().flatten().collect();
+
+        true
+    }
+
+    /// Turns the `callee` of a method call, `recv.method`, into the method
+    /// `sym` and inserts the already checked receiver as the first argument,
+    /// the receiver is borrowed if the method takes a pointer to it.
+    fn lower_method_call(
+        &mut self,
+        callee: &mut ScExpression,
+        args: &mut Vec<ScExpression>,
+        sym: Symbol,
+    ) {
+        let ScExpr::MemberAccess { expr: recv, .. } =
+            mem::replace(&mut callee.expr, ScExpr::Ident(sym.clone()))
+        else {
+            // SAFETY: it is the caller's responsibility to call this function
+            // with a member access callee
+            opt_unreachable!()
+        };
+
+        let mut recv = *recv;
+
+        if let Some((args_ty, _)) = sym.typ().as_fun_ptr()
+            && let Some(Type::Ptr { mutable, typ }) = args_ty.first()
+            && **typ == recv.typ
+        {
+            if *mutable
+                && let ScExpr::Ident(recv_sym) = &recv.expr
+                && !recv_sym.is_place()
+            {
+                self.sink.emit(BorrowMutWhenNotDefinedMut {
+                    loc_def: recv_sym.loc().unwrap(),
+                    name_def: recv_sym.name(),
+                    loc: recv.loc.clone().unwrap(),
+                })
+            }
+
+            recv = ScExpression {
+                typ: Type::Ptr {
+                    mutable: *mutable,
+                    typ: typ.clone(),
+                },
+                loc: recv.loc.clone(),
+                expr: ScExpr::Borrow {
+                    mutable: *mutable,
+                    expr: Box::new(recv),
+                },
+            };
+        }
+
+        callee.typ = sym.typ();
+        args.insert(0, recv);
+    }
+
     pub fn ck_block(
         &mut self,
         block: &mut ScBlock,
         coerce_to: Option<Type>,
     ) -> Result<(), Diagnostic> {
         // check the statements
+        let mut invalid_for_bounds = false;
         for stmt in &mut block.stmts {
             match self.ck_stmt(stmt) {
                 Ok(()) => {}
                 Err(d) => self.sink.emit(d),
             }
+
+            // NOTE: it is the bounds check of a `for` loop, added by the
+            // desugaring, if the bounds are not integers the loop would only
+            // report the same mistake again.
+            if let ScStmt::BoundsCheck(ScExpression {
+                typ: Type::Range(inner),
+                ..
+            }) = &stmt.stmt
+                && !inner.is_int()
+            {
+                invalid_for_bounds = true;
+                break;
+            }
         }
 
         // check the last expression
         if let Some(expr) = &mut block.last_expr {
-            self.ck_expr(expr, coerce_to)?;
+            if invalid_for_bounds {
+                expr.typ = coerce_to.unwrap_or(Type::Void);
+            } else {
+                self.ck_expr(expr, coerce_to)?;
+            }
         }
 
         // compute if one of the statements or the last expression has
@@ -1440,16 +3042,85 @@ impl SemaChecker {
         Ok(())
     }
 
+    /// Converts a value computed at compile-time to its literal expression,
+    /// returns `None` if the value has no literal form.
+    pub fn value_to_expr(value: ValueExpr, typ: Type, loc: OSpan) -> Option<ScExpression> {
+        let int = |i: i128| {
+            let lit = ScExpression {
+                expr: ScExpr::IntLit(i.unsigned_abs(), None),
+                typ: typ.clone(),
+                loc: loc.clone(),
+            };
+
+            if i < 0 {
+                ScExpr::Unary {
+                    op: UnaryOp::Negation,
+                    expr: Box::new(lit),
+                }
+            } else {
+                lit.expr
+            }
+        };
+
+        let expr = match value {
+            ValueExpr::Boolean(b) => ScExpr::BoolLit(b),
+            ValueExpr::I8(i) => int(i as i128),
+            ValueExpr::I16(i) => int(i as i128),
+            ValueExpr::I32(i) => int(i as i128),
+            ValueExpr::I64(i) => int(i as i128),
+            ValueExpr::I128(i) => int(i),
+            ValueExpr::U8(u) => ScExpr::IntLit(u as u128, None),
+            ValueExpr::U16(u) => ScExpr::IntLit(u as u128, None),
+            ValueExpr::U32(u) => ScExpr::IntLit(u as u128, None),
+            ValueExpr::U64(u) => ScExpr::IntLit(u as u128, None),
+            ValueExpr::U128(u) => ScExpr::IntLit(u, None),
+            ValueExpr::Str(s) => ScExpr::StringLit(s),
+            ValueExpr::Char(c) => ScExpr::CharLit(c),
+            ValueExpr::F32(f) => ScExpr::FloatLit(f as f64, None),
+            ValueExpr::F64(f) => ScExpr::FloatLit(f, None),
+            ValueExpr::Type(_) | ValueExpr::Void => return None,
+        };
+
+        Some(ScExpression { expr, typ, loc })
+    }
+
     pub fn ck_stmt(&mut self, stmt: &mut ScStatement) -> Result<(), Diagnostic> {
         match &mut stmt.stmt {
             ScStmt::VariableDef {
-                name: _,
+                name,
                 name_loc: _,
-                mutable: _,
+                mutable,
                 typexpr,
                 value,
                 sym: symref,
             } => {
+                // a constant local definition of a struct or an enum names the
+                // type and binds it to the symbol before checking the fields,
+                // so that the struct can refer to itself
+                if !*mutable {
+                    match &mut value.expr {
+                        ScExpr::Struct {
+                            fields: _,
+                            index: index @ None,
+                        } => {
+                            let idx = self.define_struct(Some(name.clone()), value.loc.clone());
+                            *index = Some(idx);
+
+                            symref.set_value(Some(ValueExpr::Type(self.struct_type(idx))));
+                        }
+                        ScExpr::Enum {
+                            variants: _,
+                            index: index @ None,
+                        } => {
+                            let idx = self.define_enum(Some(name.clone()), value.loc.clone());
+                            *index = Some(idx);
+
+                            symref.set_value(Some(ValueExpr::Type(self.enum_type(idx))));
+                        }
+                        _ => {}
+                    }
+                }
+
                 // we typecheck the type expression
                 if let Some(typexpr) = typexpr {
                     self.ck_expr(typexpr, Some(Type::Type))?;
@@ -1480,8 +3151,10 @@ impl SemaChecker {
                 // we check the value of the definition
                 self.ck_expr(value, typexpr_as_type.clone())?;
 
-                // we check the type of the value
-                if value.typ == Type::Unknown {
+                // we check the type of the value, `null` needs a pointer type
+                if value.typ == Type::Unknown
+                    || (value.typ == Type::Null && typexpr_as_type.is_none())
+                {
                     self.sink.emit(TypeAnnotationsNeeded {
                         loc: value.loc.clone().unwrap(),
                     });
@@ -1497,8 +3170,39 @@ impl SemaChecker {
 
                 // we finally update the type of the symbol.
                 symref.set_typ(typ);
+
+                // the value of a constant definition is made known if it can
+                // be evaluated at compile-time, so that it can be used in
+                // other compile-time evaluations
+                if !*mutable
+                    && symref.value().is_none()
+                    && let Ok(value_expr) = self.evaluate_expr(value)
+                {
+                    symref.set_value(Some(value_expr));
+                }
+            }
+            ScStmt::Defer { expr } => {
+                let defer_loc = mem::replace(&mut self.defer_loc, stmt.loc.clone());
+                let defer_first_label =
+                    mem::replace(&mut self.defer_first_label, self.label_stack.next_index());
+
+                let res = self.ck_expr(expr, Some(Type::Void));
+
+                self.defer_loc = defer_loc;
+                self.defer_first_label = defer_first_label;
+                res?;
+
+                // NOTE: a `noreturn` deferred expression was already reported
+                if expr.typ != Type::Noreturn {
+                    self.expr_typeck(
+                        &Type::Void,
+                        expr,
+                        None,
+                        "the expression of a defer statement must be of type 'void'".to_string(),
+                    );
+                }
             }
-            ScStmt::Defer { expr } | ScStmt::Expression(expr) => {
+            ScStmt::Expression(expr) | ScStmt::BoundsCheck(expr) => {
                 self.ck_expr(expr, None)?;
             }
         }
@@ -1506,3 +3210,13 @@ impl SemaChecker {
         Ok(())
     }
 }
+
+/// Returns the index of the struct types stored by value in a value of type
+/// `typ`, the struct itself or the structs of a tuple.
+fn contained_structs(typ: &Type) -> Vec<usize> {
+    match typ {
+        Type::Struct { index, .. } => vec![*index],
+        Type::Tuple(typs) => typs.iter().flat_map(contained_structs).collect(),
+        _ => Vec::new(),
+    }
+}
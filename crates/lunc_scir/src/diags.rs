@@ -6,7 +6,7 @@ use std::{
 };
 
 use lunc_diag::{ErrorCode, Label, ToDiagnostic, WarnCode};
-use lunc_utils::{Span, list_fmt};
+use lunc_utils::{Span, list_fmt, list_fmt_with_word, pluralize};
 
 use super::*;
 
@@ -89,19 +89,37 @@ pub struct ArityDoesntMatch {
     pub expected: usize,
     /// how many arguments we got?
     pub got: usize,
+    /// names of the parameters without an argument, empty if they are unknown
+    pub missing: Vec<String>,
     /// location of the callee
     pub loc: Span,
 }
 
 impl ToDiagnostic for ArityDoesntMatch {
     fn into_diag(self) -> Diagnostic {
+        let mut label = Label::primary(self.loc.fid, self.loc);
+
+        if !self.missing.is_empty() {
+            let names = self
+                .missing
+                .iter()
+                .map(|name| format!("`{name}`"))
+                .collect::<Vec<_>>();
+
+            label = label.with_message(format!(
+                "missing the argument{} {}",
+                pluralize(names.len()),
+                list_fmt_with_word(&names, "and")
+            ));
+        }
+
         Diagnostic::error()
             .with_code(ErrorCode::ArityDoesntMatch)
             .with_message(format!(
                 "this function takes {} arguments but {} were provided",
                 self.expected, self.got
             ))
-            .with_label(Label::primary(self.loc.fid, self.loc))
+            .with_label(label)
     }
 }
 
@@ -225,22 +243,6 @@ impl ToDiagnostic for CantContinueABlock {
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct BreakWithValueUnsupported {
-    pub loc: Span,
-}
-
-impl ToDiagnostic for BreakWithValueUnsupported {
-    fn into_diag(self) -> Diagnostic {
-        Diagnostic::error()
-            .with_code(ErrorCode::BreakWithValueUnsupported)
-            .with_message("'break' from a predicate or iterator loop with a value")
-            .with_label(Label::primary(self.loc.fid, self.loc).with_message(
-                "can only 'break' with a value from a labeled block or an infinite loop",
-            ))
-    }
-}
-
 #[derive(Debug, Clone)]
 pub struct WUnreachableCode {
     /// location of the statement that does not return
@@ -424,3 +426,462 @@ impl ToDiagnostic for FunctionInGlobalMut {
             .with_label(Label::primary(self.loc.fid, self.loc))
     }
 }
+
+#[derive(Debug, Clone)]
+pub struct NoFieldOnType {
+    /// either 'field', 'variant' or 'associated item'
+    pub kind: &'static str,
+    /// name of the field
+    pub field: String,
+    /// type of the expression we tried to access the field on
+    pub typ: Type,
+    /// optional note
+    pub note: Option<String>,
+    /// location of the field
+    pub loc: Span,
+}
+
+impl ToDiagnostic for NoFieldOnType {
+    fn into_diag(self) -> Diagnostic {
+        Diagnostic::error()
+            .with_code(ErrorCode::NoFieldOnType)
+            .with_message(format!(
+                "no {} '{}' on type '{}'",
+                self.kind, self.field, self.typ
+            ))
+            .with_label(
+                Label::primary(self.loc.fid, self.loc)
+                    .with_message(format!("unknown {}", self.kind)),
+            )
+            .with_notes_iter(self.note)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MissingFieldsInLiteral {
+    /// names of the missing fields
+    pub fields: Vec<String>,
+    /// type of the struct literal
+    pub typ: Type,
+    /// location of the struct literal
+    pub loc: Span,
+}
+
+impl ToDiagnostic for MissingFieldsInLiteral {
+    fn into_diag(self) -> Diagnostic {
+        let fields = self
+            .fields
+            .iter()
+            .map(|field| format!("'{field}'"))
+            .collect::<Vec<_>>();
+
+        Diagnostic::error()
+            .with_code(ErrorCode::MissingFieldsInLiteral)
+            .with_message(format!(
+                "missing field{} {} in literal of type '{}'",
+                pluralize(fields.len()),
+                list_fmt_with_word(&fields, "and"),
+                self.typ
+            ))
+            .with_label(Label::primary(self.loc.fid, self.loc))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FieldDefinedMultipleTimes {
+    /// name of the field
+    pub field: String,
+    /// location of the first definition
+    pub loc_first: Span,
+    /// location of the redefinition
+    pub loc: Span,
+}
+
+impl ToDiagnostic for FieldDefinedMultipleTimes {
+    fn into_diag(self) -> Diagnostic {
+        Diagnostic::error()
+            .with_code(ErrorCode::FieldDefinedMultipleTimes)
+            .with_message(format!("field '{}' is defined multiple times", self.field))
+            .with_label(Label::primary(self.loc.fid, self.loc).with_message("redefined here"))
+            .with_label(
+                Label::secondary(self.loc_first.fid, self.loc_first)
+                    .with_message("first definition here"),
+            )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ItemNotAllowedInImpl {
+    /// name of the item
+    pub item: &'static str,
+    /// location of the item
+    pub loc: Span,
+    /// location of the impl block
+    pub impl_loc: Span,
+}
+
+impl ToDiagnostic for ItemNotAllowedInImpl {
+    fn into_diag(self) -> Diagnostic {
+        Diagnostic::error()
+            .with_code(ErrorCode::ItemNotAllowedInImpl)
+            .with_message(format!(
+                "a {} isn't allowed inside of an impl block.",
+                self.item
+            ))
+            .with_label(Label::primary(self.loc.fid, self.loc).with_message("defined here."))
+            .with_label(
+                Label::secondary(self.impl_loc.fid, self.impl_loc)
+                    .with_message("inside this impl block"),
+            )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AssociatedItemDefinedMultipleTimes {
+    /// name of the associated item
+    pub name: String,
+    /// the type the item is associated to
+    pub typ: Type,
+    /// location of the first definition
+    pub loc_first: Span,
+    /// location of the redefinition
+    pub loc: Span,
+}
+
+impl ToDiagnostic for AssociatedItemDefinedMultipleTimes {
+    fn into_diag(self) -> Diagnostic {
+        Diagnostic::error()
+            .with_code(ErrorCode::AssociatedItemDefinedMultipleTimes)
+            .with_message(format!(
+                "'{}' is defined multiple times for type '{}'",
+                self.name, self.typ
+            ))
+            .with_label(Label::primary(self.loc.fid, self.loc).with_message("redefined here"))
+            .with_label(
+                Label::secondary(self.loc_first.fid, self.loc_first)
+                    .with_message("first definition here"),
+            )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NullOfNonPointerType {
+    /// the type expected instead of a pointer type
+    pub expected: Type,
+    /// location of something that tells why we expect this type
+    pub due_to: OSpan,
+    /// location of the `null` expression
+    pub loc: Span,
+}
+
+impl ToDiagnostic for NullOfNonPointerType {
+    fn into_diag(self) -> Diagnostic {
+        Diagnostic::error()
+            .with_code(ErrorCode::NullOfNonPointerType)
+            .with_message(format!(
+                "'null' can't be a value of type '{}'",
+                self.expected
+            ))
+            .with_label(
+                Label::primary(self.loc.fid, self.loc)
+                    .with_message(format!("expected `{}`, found `null`", self.expected)),
+            )
+            .with_labels_iter(
+                self.due_to
+                    .map(|loc| Label::secondary(loc.fid, loc).with_message("expected due to this")),
+            )
+            .with_note("'null' is only a value of pointer types")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ReturnInDefer {
+    /// location of the defer statement
+    pub loc_defer: Span,
+    /// location of the return expression
+    pub loc: Span,
+}
+
+impl ToDiagnostic for ReturnInDefer {
+    fn into_diag(self) -> Diagnostic {
+        Diagnostic::error()
+            .with_code(ErrorCode::ReturnInDefer)
+            .with_message("can't return from inside a defer statement")
+            .with_label(Label::primary(self.loc.fid, self.loc))
+            .with_label(
+                Label::secondary(self.loc_defer.fid, self.loc_defer)
+                    .with_message("the deferred expression runs when the function exits"),
+            )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct JumpOutOfDefer {
+    /// the keyword of the expression, `break` or `continue`
+    pub kw: &'static str,
+    /// location of the defer statement
+    pub loc_defer: Span,
+    /// location of the break or continue expression
+    pub loc: Span,
+}
+
+impl ToDiagnostic for JumpOutOfDefer {
+    fn into_diag(self) -> Diagnostic {
+        Diagnostic::error()
+            .with_code(ErrorCode::JumpOutOfDefer)
+            .with_message(format!("can't `{}` out of a defer statement", self.kw))
+            .with_label(Label::primary(self.loc.fid, self.loc))
+            .with_label(
+                Label::secondary(self.loc_defer.fid, self.loc_defer)
+                    .with_message("the deferred expression runs when its block exits"),
+            )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VariantDefinedMultipleTimes {
+    /// name of the variant
+    pub variant: String,
+    /// location of the first definition
+    pub loc_first: Span,
+    /// location of the redefinition
+    pub loc: Span,
+}
+
+impl ToDiagnostic for VariantDefinedMultipleTimes {
+    fn into_diag(self) -> Diagnostic {
+        Diagnostic::error()
+            .with_code(ErrorCode::VariantDefinedMultipleTimes)
+            .with_message(format!(
+                "variant '{}' is defined multiple times",
+                self.variant
+            ))
+            .with_label(Label::primary(self.loc.fid, self.loc).with_message("redefined here"))
+            .with_label(
+                Label::secondary(self.loc_first.fid, self.loc_first)
+                    .with_message("first definition here"),
+            )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DuplicateDiscriminant {
+    /// the discriminant shared by the two variants
+    pub discriminant: i128,
+    /// name of the variant that first had the discriminant
+    pub variant_first: String,
+    /// location of the first variant
+    pub loc_first: Span,
+    /// name of the variant with the duplicate discriminant
+    pub variant: String,
+    /// location of the variant with the duplicate discriminant
+    pub loc: Span,
+}
+
+impl ToDiagnostic for DuplicateDiscriminant {
+    fn into_diag(self) -> Diagnostic {
+        Diagnostic::error()
+            .with_code(ErrorCode::DuplicateDiscriminant)
+            .with_message(format!(
+                "discriminant value '{}' is assigned more than once",
+                self.discriminant
+            ))
+            .with_label(Label::primary(self.loc.fid, self.loc).with_message(format!(
+                "'{}' is assigned '{}' here",
+                self.variant, self.discriminant
+            )))
+            .with_label(
+                Label::secondary(self.loc_first.fid, self.loc_first).with_message(format!(
+                    "'{}' is first assigned '{}' here",
+                    self.variant_first, self.discriminant
+                )),
+            )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NonExhaustiveMatch {
+    /// the values not covered by the arms, empty if they can't be listed
+    pub missing: Vec<String>,
+    /// type of the matched expression
+    pub typ: Type,
+    /// location of the matched expression
+    pub loc: Span,
+}
+
+impl ToDiagnostic for NonExhaustiveMatch {
+    fn into_diag(self) -> Diagnostic {
+        let missing = self
+            .missing
+            .iter()
+            .map(|value| format!("'{value}'"))
+            .collect::<Vec<_>>();
+
+        let label = if missing.is_empty() {
+            format!("not every value of type '{}' is covered", self.typ)
+        } else {
+            format!("{} not covered", list_fmt_with_word(&missing, "and"),)
+        };
+
+        Diagnostic::error()
+            .with_code(ErrorCode::NonExhaustiveMatch)
+            .with_message(format!("non-exhaustive match on type '{}'", self.typ))
+            .with_label(Label::primary(self.loc.fid, self.loc).with_message(label))
+            .with_note("add the missing arms or an arm with the pattern '_' to match any value")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UnknownNamedArg {
+    /// name of the argument
+    pub name: String,
+    /// name of the called function and location of its definition, if known
+    pub fun: Option<(String, Span)>,
+    /// location of the named argument
+    pub loc: Span,
+}
+
+impl ToDiagnostic for UnknownNamedArg {
+    fn into_diag(self) -> Diagnostic {
+        let mut diag = Diagnostic::error()
+            .with_code(ErrorCode::UnknownNamedArg)
+            .with_message(format!("unknown argument named '{}'", self.name))
+            .with_label(Label::primary(self.loc.fid, self.loc));
+
+        match self.fun {
+            Some((fun, loc_fun)) => {
+                diag = diag.with_label(
+                    Label::secondary(loc_fun.fid, loc_fun)
+                        .with_message(format!("function '{fun}' defined here")),
+                );
+            }
+            None => {
+                diag = diag.with_note(
+                    "named arguments can only be used when calling a function definition",
+                );
+            }
+        }
+
+        diag
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ArgumentGivenMultipleTimes {
+    /// name of the argument
+    pub name: String,
+    /// location where the argument is first given
+    pub loc_first: Span,
+    /// location where the argument is given again
+    pub loc: Span,
+}
+
+impl ToDiagnostic for ArgumentGivenMultipleTimes {
+    fn into_diag(self) -> Diagnostic {
+        Diagnostic::error()
+            .with_code(ErrorCode::ArgumentGivenMultipleTimes)
+            .with_message(format!("argument '{}' is given multiple times", self.name))
+            .with_label(Label::primary(self.loc.fid, self.loc))
+            .with_label(
+                Label::secondary(self.loc_first.fid, self.loc_first)
+                    .with_message("first given here"),
+            )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct InvalidCast {
+    /// type of the casted expression
+    pub from: Type,
+    /// type the expression is cast to
+    pub to: Type,
+    /// location of the cast expression
+    pub loc: Span,
+}
+
+impl ToDiagnostic for InvalidCast {
+    fn into_diag(self) -> Diagnostic {
+        let mut diag = Diagnostic::error()
+            .with_code(ErrorCode::InvalidCast)
+            .with_message(format!(
+                "invalid cast from '{}' to '{}'",
+                self.from, self.to
+            ))
+            .with_label(Label::primary(self.loc.fid, self.loc));
+
+        if self.from.is_int() && self.to == Type::Bool {
+            diag = diag.with_note("compare the integer with zero instead, like `x != 0`");
+        } else if self.from.is_int() && self.to == Type::Char {
+            diag = diag.with_note(
+                "only `u8` can be cast to `char`, other integers may not be valid characters",
+            );
+        }
+
+        diag
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct InstantiationTooDeep {
+    /// name of the instance that goes over the limit, like `f(64)`
+    pub name: String,
+    /// the maximum depth of the instances
+    pub max: usize,
+    /// location of the call that requires the instance
+    pub loc: Span,
+}
+
+impl ToDiagnostic for InstantiationTooDeep {
+    fn into_diag(self) -> Diagnostic {
+        Diagnostic::error()
+            .with_code(ErrorCode::InstantiationTooDeep)
+            .with_message(format!(
+                "reached the instantiation depth limit while instantiating `{}`",
+                self.name
+            ))
+            .with_label(Label::primary(self.loc.fid, self.loc))
+            .with_note(format!(
+                "the instances of generic functions can't be nested more than {} times",
+                self.max
+            ))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RecursiveStruct {
+    /// name of the struct type
+    pub name: String,
+    /// the fields of the cycle, with the struct they are in, from the struct
+    /// back to itself
+    pub cycle: Vec<(String, String)>,
+    /// location of the struct type expression
+    pub loc: Span,
+}
+
+impl ToDiagnostic for RecursiveStruct {
+    fn into_diag(self) -> Diagnostic {
+        let cycle = self
+            .cycle
+            .iter()
+            .map(|(typ, field)| format!("`{typ}.{field}`"))
+            .collect::<Vec<_>>()
+            .join(" -> ");
+
+        Diagnostic::error()
+            .with_code(ErrorCode::RecursiveStruct)
+            .with_message(format!(
+                "recursive struct `{}` has an infinite size",
+                self.name
+            ))
+            .with_label(Label::primary(self.loc.fid, self.loc))
+            .with_note(format!(
+                "`{}` contains itself through the field{} {cycle}",
+                self.name,
+                pluralize(self.cycle.len())
+            ))
+            .with_note(format!(
+                "help: store a pointer in one of the fields instead, like `*{}`",
+                self.name
+            ))
+    }
+}
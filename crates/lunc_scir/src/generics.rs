@@ -0,0 +1,873 @@
+//! Generic functions, functions with `comptime` parameters. They are not
+//! checked on their own, a generic function is instantiated for each set of
+//! values of its `comptime` arguments and every instance is checked.
+//!
+//! Generic impl blocks, impl blocks with parameters, are instantiated the same
+//! way for each type they are used with.
+
+use std::{iter::zip, mem};
+
+use lunc_diag::Label;
+use lunc_utils::symbol::EffectivePath;
+
+use crate::diags::{ArityDoesntMatch, InstantiationTooDeep};
+
+use super::*;
+
+/// Maximum amount of instances of generic functions checked inside of one
+/// another, it is reached by a generic function that instantiates itself with
+/// new arguments endlessly.
+pub const MAX_INSTANTIATION_DEPTH: usize = 64;
+
+/// A function with at least one `comptime` parameter, its arguments, return
+/// type and body are kept unchecked to be cloned for each instance.
+#[derive(Debug, Clone)]
+pub struct GenericFun {
+    /// symbol of the generic function
+    pub sym: Symbol,
+    pub args: Vec<ScArg>,
+    pub rettypexpr: Option<Box<ScExpression>>,
+    pub body: ScBlock,
+    pub loc: OSpan,
+    /// the instances of the generic function, memoized by the values of their
+    /// `comptime` arguments
+    pub instances: Vec<Instance>,
+}
+
+/// An instance of a [`GenericFun`].
+#[derive(Debug, Clone)]
+pub struct Instance {
+    /// the values of the `comptime` arguments
+    pub values: Vec<ValueExpr>,
+    /// symbol of the instance
+    pub sym: Symbol,
+    /// the checked function definition of the instance, `None` while its body
+    /// is being checked
+    pub item: Option<ScItem>,
+}
+
+/// An impl block with parameters, like `impl(T: type) Vec(T)`, its target is a
+/// call to a generic function with the parameters as arguments. Its target and
+/// definitions are kept unchecked to be cloned for each instance.
+#[derive(Debug, Clone)]
+pub struct GenericImpl {
+    pub params: Vec<ScArg>,
+    pub target: ScExpression,
+    pub defs: Vec<ScImplDef>,
+    pub loc: OSpan,
+    /// index of the generic function called by the target
+    pub fun: usize,
+    /// for each argument of the target, the index of the parameter it is
+    pub target_params: Vec<usize>,
+    /// path of the module of the impl block
+    pub module: EffectivePath,
+    /// the instances of the impl block, memoized by their type
+    pub instances: Vec<ImplInstance>,
+}
+
+/// An instance of a [`GenericImpl`].
+#[derive(Debug, Clone)]
+pub struct ImplInstance {
+    /// the type the impl block was instantiated for
+    pub typ: Type,
+    /// the checked target
+    pub target: ScExpression,
+    /// the checked definitions, empty while they are being checked
+    pub defs: Vec<ScImplDef>,
+}
+
+/// Maps the symbols defined inside of a generic function to the symbols of
+/// one of its instances.
+type SymbolMap = Vec<(Symbol, Symbol)>;
+
+impl SemaChecker {
+    /// Recursively registers the function definitions that have `comptime`
+    /// parameters as generic functions.
+    pub fn bind_generic_funs(&mut self, items: &mut [ScItem]) {
+        for item in items {
+            match item {
+                ScItem::FunDefinition {
+                    args,
+                    rettypexpr,
+                    body,
+                    loc,
+                    sym,
+                    ..
+                } if args.iter().any(|arg| arg.comptime) => {
+                    self.generics.push(GenericFun {
+                        sym: sym.clone(),
+                        args: args.clone(),
+                        rettypexpr: rettypexpr.clone(),
+                        body: body.clone(),
+                        loc: loc.clone(),
+                        instances: Vec::new(),
+                    });
+                }
+                ScItem::Module { module, .. } => self.bind_generic_funs(&mut module.items),
+                _ => {}
+            }
+        }
+    }
+
+    /// Returns the generic function with the symbol `sym`, if it is one.
+    fn generic_index(&self, sym: &Symbol) -> Option<usize> {
+        self.generics.iter().position(|fun| fun.sym.object_eq(sym))
+    }
+
+    /// Is `sym` the symbol of a generic function?
+    pub fn is_generic(&self, sym: &Symbol) -> bool {
+        self.generic_index(sym).is_some()
+    }
+
+    /// Returns the body of the instance with the symbol `sym`, if it is an
+    /// instance that was checked.
+    pub fn instance_body(&self, sym: &Symbol) -> Option<ScBlock> {
+        self.generics
+            .iter()
+            .flat_map(|fun| &fun.instances)
+            .find(|inst| inst.sym.object_eq(sym))
+            .and_then(|inst| match &inst.item {
+                Some(ScItem::FunDefinition { body, .. }) => Some(body.clone()),
+                _ => None,
+            })
+    }
+
+    /// If `callee` is a generic function, the `comptime` arguments are
+    /// evaluated, the function is instantiated with their values if it wasn't
+    /// already and the call is replaced by a call to the instance without the
+    /// `comptime` arguments.
+    pub fn instantiate(
+        &mut self,
+        callee: &mut ScExpression,
+        args: &mut Vec<ScExpression>,
+        call_loc: Span,
+    ) -> Result<(), Diagnostic> {
+        let (ScExpr::Ident(sym) | ScExpr::QualifiedPath { sym, .. }) = &mut callee.expr else {
+            return Ok(());
+        };
+
+        let Some(index) = self.generic_index(sym) else {
+            return Ok(());
+        };
+
+        let GenericFun {
+            sym: generic_sym,
+            args: mut inst_args,
+            rettypexpr: mut inst_rettypexpr,
+            body: mut inst_body,
+            loc,
+            instances: _,
+        } = self.generics[index].clone();
+
+        if inst_args.len() != args.len() {
+            return Err(ArityDoesntMatch {
+                expected: inst_args.len(),
+                got: args.len(),
+                missing: Vec::new(),
+                loc: callee.loc.clone().unwrap(),
+            }
+            .into_diag());
+        }
+
+        // the instance gets its own symbols, they are typed independently of
+        // the other instances
+        let mut map = SymbolMap::new();
+
+        for arg in &mut inst_args {
+            refresh_arg(arg, &mut map);
+        }
+
+        if let Some(rettypexpr) = &mut inst_rettypexpr {
+            refresh_expr(rettypexpr, &mut map);
+        }
+
+        refresh_block(&mut inst_body, &mut map);
+
+        // evaluate the comptime arguments
+        let mut values = Vec::new();
+
+        for (arg, call_arg) in zip(&mut inst_args, args.iter_mut()) {
+            if !arg.comptime {
+                continue;
+            }
+
+            let typ = self.ck_typexpr(&mut arg.typexpr);
+            arg.sym.set_typ(typ.clone());
+
+            self.ck_expr(call_arg, Some(typ.clone()))?;
+            self.expr_typeck(&typ, call_arg, arg.typexpr.loc.clone(), None);
+
+            let value = self.evaluate_expr(call_arg).map_err(|(loc, _)| {
+                CantResolveComptimeValue {
+                    note: Some(format!(
+                        "the argument of the `comptime` parameter `{}` must be known at compile-time",
+                        arg.name
+                    )),
+                    loc_expr: call_arg.loc.clone().unwrap(),
+                    loc,
+                }
+                .into_diag()
+            })?;
+
+            arg.sym.set_value(Some(value.clone()));
+            values.push(value);
+        }
+
+        let inst_sym = match self.generics[index]
+            .instances
+            .iter()
+            .find(|inst| inst.values == values)
+        {
+            Some(inst) => inst.sym.clone(),
+            None => {
+                let name = format!(
+                    "{}({})",
+                    generic_sym.name(),
+                    values
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+
+                if self.instantiation_depth >= MAX_INSTANTIATION_DEPTH {
+                    return Err(InstantiationTooDeep {
+                        name,
+                        max: MAX_INSTANTIATION_DEPTH,
+                        loc: call_loc,
+                    }
+                    .into_diag());
+                }
+
+                let mut path = generic_sym.path();
+                path.pop();
+                path.push(name.clone());
+
+                self.check_instance(
+                    index,
+                    InstanceDef {
+                        name,
+                        path,
+                        values,
+                        args: inst_args,
+                        rettypexpr: inst_rettypexpr,
+                        body: inst_body,
+                        name_loc: generic_sym.loc(),
+                        loc,
+                    },
+                    call_loc,
+                )
+            }
+        };
+
+        // replace the call to the generic function by a call to the instance
+        match &mut callee.expr {
+            ScExpr::Ident(sym) | ScExpr::QualifiedPath { sym, .. } => *sym = inst_sym,
+            _ => opt_unreachable!(),
+        }
+
+        let mut comptime = self.generics[index].args.iter().map(|arg| arg.comptime);
+
+        args.retain(|_| !comptime.next().unwrap_or(false));
+
+        Ok(())
+    }
+
+    /// Checks a new instance of the generic function at `index`, returns the
+    /// symbol of the instance. The diagnostics emitted while checking it point
+    /// to the call site that required the instantiation.
+    fn check_instance(&mut self, index: usize, def: InstanceDef, call_loc: Span) -> Symbol {
+        let InstanceDef {
+            name,
+            path,
+            values,
+            mut args,
+            mut rettypexpr,
+            mut body,
+            name_loc,
+            loc,
+        } = def;
+
+        let start = self.sink.len();
+
+        let mut args_typ = Vec::new();
+
+        for arg in args.iter_mut().filter(|arg| !arg.comptime) {
+            let typ = self.ck_typexpr(&mut arg.typexpr);
+            arg.sym.set_typ(typ.clone());
+
+            args_typ.push(typ);
+        }
+
+        let ret_typ = match &mut rettypexpr {
+            Some(rettypexpr) => self.ck_typexpr(rettypexpr),
+            None => Type::Void,
+        };
+
+        let mut sym = Symbol::function(name.clone(), path, name_loc.clone());
+        sym.set_typ(Type::FunPtr {
+            args: args_typ,
+            ret: Box::new(ret_typ.clone()),
+        });
+
+        // the instance is memoized before checking its body, so that a
+        // recursive call refers to the same instance
+        self.generics[index].instances.push(Instance {
+            values,
+            sym: sym.clone(),
+            item: None,
+        });
+        let inst_index = self.generics[index].instances.len() - 1;
+
+        let ret_typ_loc = rettypexpr.as_ref().and_then(|typexpr| typexpr.loc.clone());
+
+        self.instantiation_depth += 1;
+
+        match self.ck_fun_body(&mut body, ret_typ, ret_typ_loc) {
+            Ok(()) => {}
+            Err(d) => self.sink.emit(d),
+        }
+
+        self.instantiation_depth -= 1;
+
+        self.sink.label_since(
+            start,
+            Label::secondary(call_loc.fid, call_loc)
+                .with_message(format!("required by the instantiation of `{name}` here")),
+        );
+
+        self.generics[index].instances[inst_index].item = Some(ScItem::FunDefinition {
+            name,
+            name_loc,
+            typexpr: Box::new(None),
+            args,
+            rettypexpr,
+            body,
+            defined_mut: false,
+            loc,
+            sym: sym.clone(),
+        });
+
+        sym
+    }
+
+    /// Registers an impl block with parameters as a generic impl block, its
+    /// target must be a call to a generic function that only takes the
+    /// parameters, like `Vec(T)`, so that the values of the parameters can be
+    /// found from a type.
+    pub fn bind_generic_impl(
+        &mut self,
+        params: &[ScArg],
+        target: &ScExpression,
+        defs: &[ScImplDef],
+        loc: &OSpan,
+    ) {
+        let generic_call = match &target.expr {
+            ScExpr::FunCall {
+                callee,
+                args,
+                named_args,
+            } if named_args.is_empty() => match &callee.expr {
+                ScExpr::Ident(sym) | ScExpr::QualifiedPath { sym, .. } => self
+                    .generic_index(sym)
+                    .filter(|&fun| {
+                        let fun_args = &self.generics[fun].args;
+
+                        fun_args.len() == args.len() && fun_args.iter().all(|arg| arg.comptime)
+                    })
+                    .map(|fun| (fun, args)),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        // the index of the parameter passed as each argument of the target,
+        // every parameter must be passed
+        let target_params = generic_call.and_then(|(fun, args)| {
+            let target_params = args
+                .iter()
+                .map(|arg| match &arg.expr {
+                    ScExpr::Ident(sym) => params.iter().position(|param| param.sym.object_eq(sym)),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()?;
+
+            (0..params.len())
+                .all(|param| target_params.contains(&param))
+                .then_some((fun, target_params))
+        });
+
+        let Some((fun, target_params)) = target_params else {
+            self.sink.emit(feature_todo! {
+                feature: "generic impl blocks with a target other than `Generic(T, ..)`",
+                label: "the parameters can't be found from this target",
+                loc: target.loc.clone().unwrap(),
+            });
+
+            return;
+        };
+
+        self.generic_impls.push(GenericImpl {
+            params: params.to_vec(),
+            target: target.clone(),
+            defs: defs.to_vec(),
+            loc: loc.clone(),
+            fun,
+            target_params,
+            module: self.module_path.clone(),
+            instances: Vec::new(),
+        });
+    }
+
+    /// Instantiates the generic impl blocks whose target can be `typ` and that
+    /// weren't already instantiated for it, so that its associated items can
+    /// be looked up. The diagnostics emitted while checking an instance point
+    /// to `loc`, the use of the type that required it.
+    pub fn instantiate_impls(&mut self, typ: &Type, loc: Span) {
+        for index in 0..self.generic_impls.len() {
+            if self.generic_impls[index]
+                .instances
+                .iter()
+                .any(|inst| inst.typ == *typ)
+            {
+                continue;
+            }
+
+            if let Some(values) = self.impl_params_values(index, typ) {
+                self.check_impl_instance(index, typ.clone(), values, loc.clone());
+            }
+        }
+    }
+
+    /// Returns the values of the parameters of the generic impl block at
+    /// `index` for which its target is `typ`, if there are any. The target is
+    /// `typ` if it is the value of an instance of the generic function called
+    /// by the target.
+    fn impl_params_values(&mut self, index: usize, typ: &Type) -> Option<Vec<ValueExpr>> {
+        let GenericImpl {
+            params,
+            fun,
+            target_params,
+            ..
+        } = &self.generic_impls[index];
+
+        let params_len = params.len();
+        let target_params = target_params.clone();
+
+        let instances = self.generics[*fun]
+            .instances
+            .iter()
+            .filter(|inst| inst.item.is_some())
+            .map(|inst| (inst.sym.clone(), inst.values.clone()))
+            .collect::<Vec<_>>();
+
+        for (sym, values) in instances {
+            let call = ScExpression {
+                expr: ScExpr::FunCall {
+                    callee: Box::new(ScExpression {
+                        typ: sym.typ(),
+                        expr: ScExpr::Ident(sym),
+                        loc: None,
+                    }),
+                    args: Vec::new(),
+                    named_args: Vec::new(),
+                },
+                typ: Type::Type,
+                loc: None,
+            };
+
+            if self.evaluate_expr(&call).ok().and_then(ValueExpr::as_type) != Some(typ.clone()) {
+                continue;
+            }
+
+            let mut params_values = vec![None; params_len];
+
+            for (param, value) in zip(&target_params, values) {
+                params_values[*param] = Some(value);
+            }
+
+            return params_values.into_iter().collect();
+        }
+
+        None
+    }
+
+    /// Checks a new instance of the generic impl block at `index` for the type
+    /// `typ`, with the values of its parameters.
+    fn check_impl_instance(
+        &mut self,
+        index: usize,
+        typ: Type,
+        values: Vec<ValueExpr>,
+        use_loc: Span,
+    ) {
+        let GenericImpl {
+            mut params,
+            mut target,
+            mut defs,
+            module,
+            ..
+        } = self.generic_impls[index].clone();
+
+        // the instance gets its own symbols, they are typed independently of
+        // the other instances
+        let mut map = SymbolMap::new();
+
+        for param in &mut params {
+            refresh_arg(param, &mut map);
+        }
+
+        refresh_expr(&mut target, &mut map);
+
+        let mut path = module.clone();
+        path.push(typ.to_string());
+
+        for def in &mut defs {
+            refresh_impl_def(&mut def.item, &path, &mut map);
+        }
+
+        let start = self.sink.len();
+
+        for (param, value) in zip(&mut params, values) {
+            let param_typ = self.ck_typexpr(&mut param.typexpr);
+            param.sym.set_typ(param_typ);
+            param.sym.set_value(Some(value));
+        }
+
+        // the instance is memoized before checking its definitions, so that
+        // they can use the associated items of the type
+        self.generic_impls[index].instances.push(ImplInstance {
+            typ: typ.clone(),
+            target: target.clone(),
+            defs: Vec::new(),
+        });
+        let inst_index = self.generic_impls[index].instances.len() - 1;
+
+        let module_path = mem::replace(&mut self.module_path, module);
+
+        match self.ck_expr(&mut target, Some(Type::Type)) {
+            Ok(()) => {}
+            Err(d) => self.sink.emit(d),
+        }
+
+        self.bind_assoc_items(&typ, &mut defs);
+
+        for ScImplDef { item: def, .. } in &mut defs {
+            match self.ck_item(def) {
+                Ok(()) => {}
+                Err(d) => self.sink.emit(d),
+            }
+        }
+
+        self.module_path = module_path;
+
+        self.sink.label_since(
+            start,
+            Label::secondary(use_loc.fid, use_loc).with_message(format!(
+                "required by the instantiation of `impl {typ}` here"
+            )),
+        );
+
+        self.generic_impls[index].instances[inst_index] = ImplInstance { typ, target, defs };
+    }
+
+    /// Recursively inserts the checked instances of the generic functions
+    /// right after their definition.
+    pub fn insert_instances(&mut self, items: &mut Vec<ScItem>) {
+        let mut i = 0;
+
+        while i < items.len() {
+            match &mut items[i] {
+                ScItem::FunDefinition { sym, .. } => {
+                    if let Some(index) = self.generic_index(sym) {
+                        let instances = self.generics[index]
+                            .instances
+                            .iter()
+                            .filter_map(|inst| inst.item.clone())
+                            .collect::<Vec<_>>();
+                        let count = instances.len();
+
+                        items.splice(i + 1..i + 1, instances);
+                        i += count;
+                    }
+                }
+                ScItem::Impl {
+                    params,
+                    loc: impl_loc,
+                    ..
+                } if !params.is_empty() => {
+                    let impl_loc = impl_loc.clone();
+
+                    let instances = self
+                        .generic_impls
+                        .iter()
+                        .filter(|generic| generic.loc == impl_loc)
+                        .flat_map(|generic| generic.instances.clone())
+                        .map(|inst| ScItem::Impl {
+                            params: Vec::new(),
+                            target: inst.target,
+                            defs: inst.defs,
+                            loc: impl_loc.clone(),
+                        })
+                        .collect::<Vec<_>>();
+                    let count = instances.len();
+
+                    items.splice(i + 1..i + 1, instances);
+                    i += count;
+                }
+                ScItem::Module { module, .. } => self.insert_instances(&mut module.items),
+                _ => {}
+            }
+
+            i += 1;
+        }
+    }
+}
+
+/// The definition of a new instance, before it is checked.
+struct InstanceDef {
+    name: String,
+    path: EffectivePath,
+    values: Vec<ValueExpr>,
+    args: Vec<ScArg>,
+    rettypexpr: Option<Box<ScExpression>>,
+    body: ScBlock,
+    name_loc: OSpan,
+    loc: OSpan,
+}
+
+/// Replaces `sym` by a new symbol with the same internal value and remembers
+/// it in `map`.
+fn fresh_sym(sym: &mut Symbol, map: &mut SymbolMap) {
+    let fresh = Symbol::with_internal(sym.clone_val());
+
+    map.push((sym.clone(), fresh.clone()));
+    *sym = fresh;
+}
+
+/// Replaces the symbol of a definition of a generic impl block by a new symbol
+/// named under `path`, and the symbols defined inside of it.
+fn refresh_impl_def(def: &mut ScItem, path: &EffectivePath, map: &mut SymbolMap) {
+    let sym = match def {
+        ScItem::GlobalDef {
+            typexpr,
+            value,
+            sym,
+            ..
+        } => {
+            if let Some(typexpr) = &mut **typexpr {
+                refresh_expr(typexpr, map);
+            }
+
+            refresh_expr(value, map);
+
+            sym
+        }
+        ScItem::FunDefinition {
+            typexpr,
+            args,
+            rettypexpr,
+            body,
+            sym,
+            ..
+        } => {
+            if let Some(typexpr) = &mut **typexpr {
+                refresh_expr(typexpr, map);
+            }
+
+            for arg in args {
+                refresh_arg(arg, map);
+            }
+
+            if let Some(rettypexpr) = rettypexpr {
+                refresh_expr(rettypexpr, map);
+            }
+
+            refresh_block(body, map);
+
+            sym
+        }
+        // NOTE: the parser only produces global definitions inside of an impl
+        // block
+        _ => return,
+    };
+
+    let mut internal = sym.clone_val();
+    internal.path = path.clone();
+    internal.path.push(internal.name.clone());
+
+    let fresh = Symbol::with_internal(internal);
+
+    map.push((sym.clone(), fresh.clone()));
+    *sym = fresh;
+}
+
+/// Replaces `sym` by its new symbol if it was defined in the generic function.
+fn remap_sym(sym: &mut Symbol, map: &SymbolMap) {
+    if let Some((_, fresh)) = map.iter().find(|(old, _)| old.object_eq(sym)) {
+        *sym = fresh.clone();
+    }
+}
+
+fn refresh_arg(arg: &mut ScArg, map: &mut SymbolMap) {
+    refresh_expr(&mut arg.typexpr, map);
+
+    if let Some(default) = &mut arg.default {
+        refresh_expr(default, map);
+    }
+
+    fresh_sym(&mut arg.sym, map);
+}
+
+fn refresh_block(block: &mut ScBlock, map: &mut SymbolMap) {
+    for stmt in &mut block.stmts {
+        match &mut stmt.stmt {
+            ScStmt::VariableDef {
+                typexpr,
+                value,
+                sym,
+                ..
+            } => {
+                fresh_sym(sym, map);
+
+                if let Some(typexpr) = typexpr {
+                    refresh_expr(typexpr, map);
+                }
+
+                refresh_expr(value, map);
+            }
+            ScStmt::Defer { expr } | ScStmt::Expression(expr) | ScStmt::BoundsCheck(expr) => {
+                refresh_expr(expr, map)
+            }
+        }
+    }
+
+    if let Some(last_expr) = &mut block.last_expr {
+        refresh_expr(last_expr, map);
+    }
+}
+
+fn refresh_expr(expr: &mut ScExpression, map: &mut SymbolMap) {
+    match &mut expr.expr {
+        ScExpr::IntLit(..)
+        | ScExpr::BoolLit(_)
+        | ScExpr::StringLit(_)
+        | ScExpr::CharLit(_)
+        | ScExpr::FloatLit(..)
+        | ScExpr::Continue { .. }
+        | ScExpr::Null
+        | ScExpr::Underscore
+        | ScExpr::Poisoned { .. } => {}
+        ScExpr::Ident(sym) | ScExpr::QualifiedPath { sym, .. } => remap_sym(sym, map),
+        ScExpr::Tuple(exprs) => {
+            for exp in exprs {
+                refresh_expr(exp, map);
+            }
+        }
+        ScExpr::Binary { lhs, op: _, rhs } => {
+            refresh_expr(lhs, map);
+            refresh_expr(rhs, map);
+        }
+        ScExpr::Cast { expr: exp, typexpr } => {
+            refresh_expr(exp, map);
+            refresh_expr(typexpr, map);
+        }
+        ScExpr::Unary { expr: exp, .. }
+        | ScExpr::Borrow { expr: exp, .. }
+        | ScExpr::Comptime(exp)
+        | ScExpr::MemberAccess { expr: exp, .. }
+        | ScExpr::PointerType { typexpr: exp, .. } => refresh_expr(exp, map),
+        ScExpr::Range { start, end, .. } => {
+            for exp in [start, end].into_iter().flatten() {
+                refresh_expr(exp, map);
+            }
+        }
+        ScExpr::FunCall {
+            callee,
+            args,
+            named_args,
+        } => {
+            refresh_expr(callee, map);
+
+            for arg in args {
+                refresh_expr(arg, map);
+            }
+
+            for arg in named_args {
+                refresh_expr(&mut arg.value, map);
+            }
+        }
+        ScExpr::If {
+            cond,
+            then_br,
+            else_br,
+        } => {
+            refresh_expr(cond, map);
+            refresh_expr(then_br, map);
+
+            if let Some(else_br) = else_br {
+                refresh_expr(else_br, map);
+            }
+        }
+        ScExpr::Block { block, .. } | ScExpr::Loop { body: block, .. } => refresh_block(block, map),
+        ScExpr::Return { expr: exp } | ScExpr::Break { expr: exp, .. } => {
+            if let Some(exp) = exp {
+                refresh_expr(exp, map);
+            }
+        }
+        ScExpr::StructLit { typexpr, fields } => {
+            refresh_expr(typexpr, map);
+
+            for field in fields {
+                refresh_expr(&mut field.value, map);
+            }
+        }
+        ScExpr::Match { expr: exp, arms } => {
+            refresh_expr(exp, map);
+
+            for arm in arms {
+                for pat in &mut arm.pats {
+                    refresh_expr(pat, map);
+                }
+
+                refresh_expr(&mut arm.body, map);
+            }
+        }
+        ScExpr::FunDefinition {
+            args,
+            rettypexpr,
+            body,
+        } => {
+            for arg in args {
+                refresh_arg(arg, map);
+            }
+
+            if let Some(rettypexpr) = rettypexpr {
+                refresh_expr(rettypexpr, map);
+            }
+
+            refresh_block(body, map);
+        }
+        ScExpr::FunPtrType { args, ret } => {
+            for arg in args {
+                refresh_expr(arg, map);
+            }
+
+            if let Some(ret) = ret {
+                refresh_expr(ret, map);
+            }
+        }
+        ScExpr::Struct { fields, .. } => {
+            for field in fields {
+                refresh_expr(&mut field.typexpr, map);
+            }
+        }
+        ScExpr::Enum { variants, .. } => {
+            for variant in variants {
+                if let Some(value) = &mut variant.value {
+                    refresh_expr(value, map);
+                }
+            }
+        }
+    }
+}
@@ -3,26 +3,31 @@
     html_logo_url = "https://raw.githubusercontent.com/lunprog/lun/main/logo/logo_no_bg_black.png"
 )]
 
-use std::fmt::Debug;
+use std::{cmp::Ordering, fmt::Debug};
 
 use diags::{CantResolveComptimeValue, ExpectedTypeFoundExpr};
 use lunc_diag::{Diagnostic, DiagnosticSink, FileId, ToDiagnostic, feature_todo};
 use lunc_dsir::{
-    DsArg, DsBlock, DsDirective, DsExpr, DsExpression, DsItem, DsModule, DsStatement, DsStmt,
-    OSpan, QualifiedPath,
+    DsArg, DsBlock, DsDirective, DsEnumVariant, DsExpr, DsExpression, DsFieldValue, DsImplDef,
+    DsItem, DsMatchArm, DsModule, DsNamedArg, DsStatement, DsStmt, DsStructField, OSpan,
+    QualifiedPath,
 };
 use lunc_utils::{
-    FromHigher, Span, lower, opt_unreachable,
-    symbol::{Symbol, Type, ValueExpr},
+    DEFAULT_MAX_LEVENSHTEIN_DISTANCE, FromHigher, Span, lower, opt_unreachable, suggest,
+    symbol::{EffectivePath, Signedness, Symbol, Type, ValueExpr},
     target::{PtrWidth, TargetTriplet},
 };
 
-pub use lunc_dsir::{Abi, BinOp, UnaryOp};
+pub use lunc_dsir::{Abi, BinOp, FloatSuffix, IntSuffix, UnaryOp, Vis};
 
-use crate::diags::OutsideExternBlock;
+use crate::{
+    diags::OutsideExternBlock,
+    generics::{GenericFun, GenericImpl},
+};
 
 pub mod checking;
 pub mod diags;
+pub mod generics;
 pub mod pretty;
 pub mod safety_ck;
 
@@ -144,6 +149,37 @@ pub enum ScItem {
         items: Vec<ScItem>,
         loc: OSpan,
     },
+    /// See [`DsItem::Impl`]
+    ///
+    /// [`DsItem::Impl`]: lunc_dsir::DsItem::Impl
+    Impl {
+        params: Vec<ScArg>,
+        target: ScExpression,
+        defs: Vec<ScImplDef>,
+        loc: OSpan,
+    },
+}
+
+/// See [`DsImplDef`]
+///
+/// [`DsImplDef`]: lunc_dsir::DsImplDef
+#[derive(Debug, Clone)]
+pub struct ScImplDef {
+    pub vis: Vis,
+    pub item: ScItem,
+}
+
+impl FromHigher for ScImplDef {
+    type Higher = DsImplDef;
+
+    fn lower(node: Self::Higher) -> Self {
+        let DsImplDef { vis, item } = node;
+
+        ScImplDef {
+            vis,
+            item: lower(item),
+        }
+    }
 }
 
 impl ScItem {
@@ -155,7 +191,8 @@ impl ScItem {
             | ScItem::Module { loc, .. }
             | ScItem::FunDefinition { loc, .. }
             | ScItem::FunDeclaration { loc, .. }
-            | ScItem::ExternBlock { loc, .. } => loc.clone().unwrap(),
+            | ScItem::ExternBlock { loc, .. }
+            | ScItem::Impl { loc, .. } => loc.clone().unwrap(),
         }
     }
 }
@@ -166,6 +203,7 @@ impl FromHigher for ScItem {
     fn lower(node: Self::Higher) -> Self {
         match node {
             DsItem::GlobalDef {
+                vis: _,
                 name,
                 name_loc,
                 mutable,
@@ -197,6 +235,7 @@ impl FromHigher for ScItem {
                 }
             }
             DsItem::GlobalDef {
+                vis: _,
                 name,
                 name_loc,
                 mutable,
@@ -222,6 +261,7 @@ impl FromHigher for ScItem {
                 }
             }
             DsItem::GlobalDef {
+                vis: _,
                 name,
                 name_loc,
                 mutable,
@@ -239,6 +279,7 @@ impl FromHigher for ScItem {
                 sym: lazy.unwrap_sym(),
             },
             DsItem::GlobalUninit {
+                vis: _,
                 name,
                 name_loc,
                 typexpr,
@@ -267,6 +308,17 @@ impl FromHigher for ScItem {
                 items: lower(items),
                 loc,
             },
+            DsItem::Impl {
+                params,
+                target,
+                defs,
+                loc,
+            } => ScItem::Impl {
+                params: lower(params),
+                target: lower(target),
+                defs: lower(defs),
+                loc,
+            },
             DsItem::Directive(DsDirective::Import { .. } | DsDirective::Mod { .. }) => {
                 unreachable!()
             }
@@ -301,6 +353,7 @@ impl ScExpression {
                     Some("variable isn't mutable".to_string())
                 }
             }
+            ScExpr::MemberAccess { expr, member: _ } => expr.is_place(),
             ScExpr::Unary {
                 op: UnaryOp::Dereference,
                 expr,
@@ -326,11 +379,12 @@ impl FromHigher for ScExpression {
 
     fn lower(node: Self::Higher) -> Self {
         let expr = match node.expr {
-            DsExpr::IntLit(i) => ScExpr::IntLit(i),
+            DsExpr::IntLit(i, suffix) => ScExpr::IntLit(i, suffix),
             DsExpr::BoolLit(b) => ScExpr::BoolLit(b),
             DsExpr::StringLit(str) => ScExpr::StringLit(str),
             DsExpr::CharLit(c) => ScExpr::CharLit(c),
-            DsExpr::FloatLit(f) => ScExpr::FloatLit(f),
+            DsExpr::FloatLit(f, suffix) => ScExpr::FloatLit(f, suffix),
+            DsExpr::Tuple(exprs) => ScExpr::Tuple(lower(exprs)),
             DsExpr::Ident(lazy) => ScExpr::Ident(lazy.unwrap_sym()),
             DsExpr::Binary { lhs, op, rhs } => ScExpr::Binary {
                 lhs: lower(lhs),
@@ -341,13 +395,31 @@ impl FromHigher for ScExpression {
                 op,
                 expr: lower(expr),
             },
+            DsExpr::Cast { expr, typexpr } => ScExpr::Cast {
+                expr: lower(expr),
+                typexpr: lower(typexpr),
+            },
             DsExpr::Borrow { mutable, expr } => ScExpr::Borrow {
                 mutable,
                 expr: lower(expr),
             },
-            DsExpr::FunCall { callee, args } => ScExpr::FunCall {
+            DsExpr::Range {
+                start,
+                end,
+                inclusive,
+            } => ScExpr::Range {
+                start: lower(start),
+                end: lower(end),
+                inclusive,
+            },
+            DsExpr::FunCall {
+                callee,
+                args,
+                named_args,
+            } => ScExpr::FunCall {
                 callee: lower(callee),
                 args: lower(args),
+                named_args: lower(named_args),
             },
             DsExpr::If {
                 cond,
@@ -369,13 +441,19 @@ impl FromHigher for ScExpression {
                 index: None,
             },
             DsExpr::Return { expr } => ScExpr::Return { expr: lower(expr) },
-            DsExpr::Break { label, expr } => ScExpr::Break {
+            DsExpr::Break {
+                label,
+                expr,
+                desugared,
+            } => ScExpr::Break {
                 label,
                 expr: lower(expr),
                 index: None,
+                desugared,
             },
             DsExpr::Continue { label } => ScExpr::Continue { label, index: None },
             DsExpr::Null => ScExpr::Null,
+            DsExpr::Comptime(expr) => ScExpr::Comptime(lower(expr)),
             DsExpr::MemberAccess { expr, member } => ScExpr::MemberAccess {
                 expr: lower(expr),
                 member,
@@ -385,12 +463,14 @@ impl FromHigher for ScExpression {
                 sym: lazy.unwrap_sym(),
             },
             DsExpr::Underscore => ScExpr::Underscore,
-            DsExpr::FunDefinition { .. } => ScExpr::Poisoned {
-                diag: Some(feature_todo! {
-                    feature: "local function definition",
-                    label: "fundefs inside an expression isn't supported",
-                    loc: node.loc.clone().unwrap(),
-                }),
+            DsExpr::FunDefinition {
+                args,
+                rettypexpr,
+                body,
+            } => ScExpr::FunDefinition {
+                args: lower(args),
+                rettypexpr: lower(rettypexpr),
+                body: lower(body),
             },
             DsExpr::FunDeclaration { .. } => ScExpr::Poisoned {
                 diag: Some(
@@ -409,6 +489,22 @@ impl FromHigher for ScExpression {
                 args: lower(args),
                 ret: lower(ret),
             },
+            DsExpr::Struct { fields } => ScExpr::Struct {
+                fields: lower(fields),
+                index: None,
+            },
+            DsExpr::StructLit { typexpr, fields } => ScExpr::StructLit {
+                typexpr: lower(typexpr),
+                fields: lower(fields),
+            },
+            DsExpr::Match { expr, arms } => ScExpr::Match {
+                expr: lower(expr),
+                arms: lower(arms),
+            },
+            DsExpr::Enum { variants } => ScExpr::Enum {
+                variants: lower(variants),
+                index: None,
+            },
             DsExpr::Poisoned { diag: _ } => {
                 // NOTE: didn't used `opt_unreachable`, i didn't wanted to
                 // ensure it was truly unreachable
@@ -432,7 +528,7 @@ pub enum ScExpr {
     /// See [`DsExpr::IntLit`]
     ///
     /// [`DsExpr::IntLit`]: lunc_dsir::DsExpr::IntLit
-    IntLit(u128),
+    IntLit(u128, Option<IntSuffix>),
     /// See [`DsExpr::BoolLit`]
     ///
     /// [`DsExpr::BoolLit`]: lunc_dsir::DsExpr::BoolLit
@@ -448,7 +544,11 @@ pub enum ScExpr {
     /// See [`DsExpr::FloatLit`]
     ///
     /// [`DsExpr::FloatLit`]: lunc_dsir::DsExpr::FloatLit
-    FloatLit(f64),
+    FloatLit(f64, Option<FloatSuffix>),
+    /// See [`DsExpr::Tuple`]
+    ///
+    /// [`DsExpr::Tuple`]: lunc_dsir::DsExpr::Tuple
+    Tuple(Vec<ScExpression>),
     /// See [`DsExpr::Ident`]
     ///
     /// [`DsExpr::Ident`]: lunc_dsir::DsExpr::Ident
@@ -468,6 +568,13 @@ pub enum ScExpr {
         op: UnaryOp,
         expr: Box<ScExpression>,
     },
+    /// See [`DsExpr::Cast`]
+    ///
+    /// [`DsExpr::Cast`]: lunc_dsir::DsExpr::Cast
+    Cast {
+        expr: Box<ScExpression>,
+        typexpr: Box<ScExpression>,
+    },
     /// See [`DsExpr::Borrow`]
     ///
     /// [`DsExpr::Borrow`]: lunc_dsir::DsExpr::Borrow
@@ -475,12 +582,23 @@ pub enum ScExpr {
         mutable: bool,
         expr: Box<ScExpression>,
     },
+    /// See [`DsExpr::Range`]
+    ///
+    /// [`DsExpr::Range`]: lunc_dsir::DsExpr::Range
+    Range {
+        start: Option<Box<ScExpression>>,
+        end: Option<Box<ScExpression>>,
+        inclusive: bool,
+    },
     /// See [`DsExpr::FunCall`]
     ///
     /// [`DsExpr::FunCall`]: lunc_dsir::DsExpr::FunCall
     FunCall {
         callee: Box<ScExpression>,
         args: Vec<ScExpression>,
+        /// the named arguments of the call, after checking they are moved to
+        /// their position in `args` and this is empty.
+        named_args: Vec<ScNamedArg>,
     },
     /// See [`DsExpr::If`]
     ///
@@ -520,6 +638,10 @@ pub enum ScExpr {
         expr: Option<Box<ScExpression>>,
         /// label index after checking MUST be `Some(..)`
         index: Option<usize>,
+        /// see [`DsExpr::Break`]
+        ///
+        /// [`DsExpr::Break`]: lunc_dsir::DsExpr::Break
+        desugared: bool,
     },
     /// See [`DsExpr::Continue`]
     ///
@@ -533,6 +655,13 @@ pub enum ScExpr {
     ///
     /// [`DsExpr::Null`]: lunc_dsir::DsExpr::Null
     Null,
+    /// See [`DsExpr::Comptime`]
+    ///
+    /// After checking, the expression is replaced by the literal it evaluates
+    /// to.
+    ///
+    /// [`DsExpr::Comptime`]: lunc_dsir::DsExpr::Comptime
+    Comptime(Box<ScExpression>),
     /// See [`DsExpr::MemberAccess`]
     ///
     /// After the name resolution, member access of modules are converted to [`EffectivePath`]
@@ -543,6 +672,20 @@ pub enum ScExpr {
         expr: Box<ScExpression>,
         member: String,
     },
+    /// See [`DsExpr::StructLit`]
+    ///
+    /// [`DsExpr::StructLit`]: lunc_dsir::DsExpr::StructLit
+    StructLit {
+        typexpr: Box<ScExpression>,
+        fields: Vec<ScFieldValue>,
+    },
+    /// See [`DsExpr::Match`]
+    ///
+    /// [`DsExpr::Match`]: lunc_dsir::DsExpr::Match
+    Match {
+        expr: Box<ScExpression>,
+        arms: Vec<ScMatchArm>,
+    },
     /// Constructed from member access, eg:
     ///
     /// `orb.driver.run` are member accesses and it refers to a function "run",
@@ -553,6 +696,20 @@ pub enum ScExpr {
         /// the symbol we are referring to
         sym: Symbol,
     },
+    /// See [`DsExpr::FunDefinition`]
+    ///
+    /// # Note
+    ///
+    /// Function definitions of global definitions are lowered to a
+    /// [`ScItem::FunDefinition`], this node is only used for functions used as
+    /// values.
+    ///
+    /// [`DsExpr::FunDefinition`]: lunc_dsir::DsExpr::FunDefinition
+    FunDefinition {
+        args: Vec<ScArg>,
+        rettypexpr: Option<Box<ScExpression>>,
+        body: ScBlock,
+    },
     /// Constructed from the lazy ident `_`, but only in certain cases, like
     /// when it's part of an assignment like so: `_ = expr`
     Underscore,
@@ -570,6 +727,24 @@ pub enum ScExpr {
         args: Vec<ScExpression>,
         ret: Option<Box<ScExpression>>,
     },
+    /// See [`DsExpr::Struct`]
+    ///
+    /// [`DsExpr::Struct`]: lunc_dsir::DsExpr::Struct
+    Struct {
+        fields: Vec<ScStructField>,
+        /// index of the struct in the struct table, after checking MUST be
+        /// `Some(..)`
+        index: Option<usize>,
+    },
+    /// See [`DsExpr::Enum`]
+    ///
+    /// [`DsExpr::Enum`]: lunc_dsir::DsExpr::Enum
+    Enum {
+        variants: Vec<ScEnumVariant>,
+        /// index of the enum in the enum table, after checking MUST be
+        /// `Some(..)`
+        index: Option<usize>,
+    },
     /// See [`DsExpr::Poisoned`]
     ///
     /// # Note
@@ -586,9 +761,11 @@ pub enum ScExpr {
 /// [`DsArg`]: lunc_dsir::DsArg
 #[derive(Debug, Clone)]
 pub struct ScArg {
+    pub comptime: bool,
     pub name: String,
     pub name_loc: OSpan,
     pub typexpr: ScExpression,
+    pub default: Option<ScExpression>,
     pub loc: OSpan,
     pub sym: Symbol,
 }
@@ -598,23 +775,175 @@ impl FromHigher for ScArg {
 
     fn lower(node: Self::Higher) -> Self {
         let DsArg {
+            comptime,
             name,
             name_loc,
             typexpr,
+            default,
             loc,
             sym: lazy,
         } = node;
 
         ScArg {
+            comptime,
             name,
             name_loc,
             typexpr: lower(typexpr),
+            default: lower(default),
             loc,
             sym: lazy.unwrap_sym(),
         }
     }
 }
 
+/// A semantic checked named argument, see the dsir version [`DsNamedArg`]
+///
+/// [`DsNamedArg`]: lunc_dsir::DsNamedArg
+#[derive(Debug, Clone)]
+pub struct ScNamedArg {
+    pub name: String,
+    pub name_loc: OSpan,
+    pub value: ScExpression,
+    pub loc: OSpan,
+}
+
+impl FromHigher for ScNamedArg {
+    type Higher = DsNamedArg;
+
+    fn lower(node: Self::Higher) -> Self {
+        let DsNamedArg {
+            name,
+            name_loc,
+            value,
+            loc,
+        } = node;
+
+        ScNamedArg {
+            name,
+            name_loc,
+            value: lower(value),
+            loc,
+        }
+    }
+}
+
+/// A semantic checked struct field, see the dsir version [`DsStructField`]
+///
+/// [`DsStructField`]: lunc_dsir::DsStructField
+#[derive(Debug, Clone)]
+pub struct ScStructField {
+    pub name: String,
+    pub name_loc: OSpan,
+    pub typexpr: ScExpression,
+    pub loc: OSpan,
+}
+
+impl FromHigher for ScStructField {
+    type Higher = DsStructField;
+
+    fn lower(node: Self::Higher) -> Self {
+        let DsStructField {
+            name,
+            name_loc,
+            typexpr,
+            loc,
+        } = node;
+
+        ScStructField {
+            name,
+            name_loc,
+            typexpr: lower(typexpr),
+            loc,
+        }
+    }
+}
+
+/// A semantic checked enum variant, see the dsir version [`DsEnumVariant`]
+///
+/// [`DsEnumVariant`]: lunc_dsir::DsEnumVariant
+#[derive(Debug, Clone)]
+pub struct ScEnumVariant {
+    pub name: String,
+    pub name_loc: OSpan,
+    pub value: Option<ScExpression>,
+    pub loc: OSpan,
+}
+
+impl FromHigher for ScEnumVariant {
+    type Higher = DsEnumVariant;
+
+    fn lower(node: Self::Higher) -> Self {
+        let DsEnumVariant {
+            name,
+            name_loc,
+            value,
+            loc,
+        } = node;
+
+        ScEnumVariant {
+            name,
+            name_loc,
+            value: lower(value),
+            loc,
+        }
+    }
+}
+
+/// A semantic checked match arm, see the dsir version [`DsMatchArm`]
+///
+/// [`DsMatchArm`]: lunc_dsir::DsMatchArm
+#[derive(Debug, Clone)]
+pub struct ScMatchArm {
+    pub pats: Vec<ScExpression>,
+    pub body: ScExpression,
+    pub loc: OSpan,
+}
+
+impl FromHigher for ScMatchArm {
+    type Higher = DsMatchArm;
+
+    fn lower(node: Self::Higher) -> Self {
+        let DsMatchArm { pats, body, loc } = node;
+
+        ScMatchArm {
+            pats: lower(pats),
+            body: lower(body),
+            loc,
+        }
+    }
+}
+
+/// A semantic checked field value, see the dsir version [`DsFieldValue`]
+///
+/// [`DsFieldValue`]: lunc_dsir::DsFieldValue
+#[derive(Debug, Clone)]
+pub struct ScFieldValue {
+    pub name: String,
+    pub name_loc: OSpan,
+    pub value: ScExpression,
+    pub loc: OSpan,
+}
+
+impl FromHigher for ScFieldValue {
+    type Higher = DsFieldValue;
+
+    fn lower(node: Self::Higher) -> Self {
+        let DsFieldValue {
+            name,
+            name_loc,
+            value,
+            loc,
+        } = node;
+
+        ScFieldValue {
+            name,
+            name_loc,
+            value: lower(value),
+            loc,
+        }
+    }
+}
+
 /// A semantic checked block, see the dsir version [`DsBlock`]
 ///
 /// [`DsBlock`]: lunc_dsir::DsBlock
@@ -676,6 +1005,7 @@ impl FromHigher for ScStatement {
             },
             DsStmt::Defer { expr } => ScStmt::Defer { expr: lower(expr) },
             DsStmt::Expression(expr) => ScStmt::Expression(lower(expr)),
+            DsStmt::BoundsCheck(expr) => ScStmt::BoundsCheck(lower(expr)),
         };
 
         ScStatement {
@@ -706,6 +1036,10 @@ pub enum ScStmt {
     ///
     /// [`DsStmt::Expression`]: lunc_dsir::DsStmt::Expression
     Expression(ScExpression),
+    /// See [`DsStmt::BoundsCheck`]
+    ///
+    /// [`DsStmt::BoundsCheck`]: lunc_dsir::DsStmt::BoundsCheck
+    BoundsCheck(ScExpression),
 }
 
 /// The thing that contains the items
@@ -725,12 +1059,38 @@ pub struct SemaChecker {
     fun_retty: Type,
     /// location where the return type was defined
     fun_retty_loc: OSpan,
+    /// location of the defer statement we are currently checking the
+    /// expression of, `None` if we are not inside of a defer
+    defer_loc: OSpan,
+    /// index of the first label defined inside of the defer statement we are
+    /// currently checking, the labels before it are outside of the defer
+    defer_first_label: usize,
     /// it is used to check the types and correctness of label using expression
     label_stack: LabelStack,
     /// the target we are compiling to
     target: TargetTriplet,
     /// container of the item currently being checked
     container: ItemContainer,
+    /// path of the module currently being checked
+    module_path: EffectivePath,
+    /// table of all the struct types, [`Type::Struct`] refers to a struct by
+    /// its index in this table
+    structs: Vec<StructInfo>,
+    /// table of all the enum types, [`Type::Enum`] refers to an enum by its
+    /// index in this table
+    enums: Vec<EnumInfo>,
+    /// the items defined in impl blocks, associated to their type
+    assoc_items: Vec<AssocItem>,
+    /// the generic functions, with their instances
+    generics: Vec<GenericFun>,
+    /// amount of instances of generic functions being checked, see
+    /// [`generics::MAX_INSTANTIATION_DEPTH`]
+    instantiation_depth: usize,
+    /// the generic impl blocks, with their instances
+    generic_impls: Vec<GenericImpl>,
+    /// the parameters of the function definitions, used to resolve the named
+    /// arguments and the default values of a call
+    fun_params: Vec<FunParams>,
 }
 
 impl SemaChecker {
@@ -739,21 +1099,49 @@ impl SemaChecker {
             sink,
             fun_retty: Type::Unknown,
             fun_retty_loc: None,
+            defer_loc: None,
+            defer_first_label: 0,
             label_stack: LabelStack::new(),
             target,
             container: ItemContainer::Module,
+            module_path: EffectivePath::with_root_member("orb"),
+            structs: Vec::new(),
+            enums: Vec::new(),
+            assoc_items: Vec::new(),
+            generics: Vec::new(),
+            instantiation_depth: 0,
+            generic_impls: Vec::new(),
+            fun_params: Vec::new(),
         }
     }
 
     pub fn produce(&mut self, dsir: DsModule) -> Option<ScModule> {
-        let mut root = lower(dsir);
+        let mut root: ScModule = lower(dsir);
+
+        // we bind the struct and enum types defined in global definitions, so
+        // that they can be referred to before their definition and by
+        // themselves
+        self.bind_struct_defs(&mut root.items);
+
+        // we register the generic functions, they are instantiated when they
+        // are called
+        self.bind_generic_funs(&mut root.items);
 
         // we pre check the modules to compute the types of the global definitions
         self.pre_ck_module(&mut root);
 
-        // we check all of the SCIR
+        // we check the global definitions, then the rest of the SCIR
+        self.ck_global_defs(&mut root.items);
         self.ck_mod(&mut root);
 
+        // all the fields of the structs are known, we check that none of them
+        // contains itself
+        self.ck_recursive_structs();
+
+        // we insert the checked instances of the generic functions and impl
+        // blocks next to their definition
+        self.insert_instances(&mut root.items);
+
         // check the safety of the SCIR, we check if there is no integer literal overflow, float literal overflow..
         self.safety_ck_mod(&root);
 
@@ -771,36 +1159,17 @@ impl SemaChecker {
         &mut self,
         expr: &ScExpression,
     ) -> Result<ValueExpr, (Span, Option<String>)> {
-        let expr_loc = expr.loc.clone().unwrap();
+        // expressions generated during the desugaring don't have a location
+        let expr_loc = expr.loc.clone().unwrap_or(Span::ZERO);
 
         match &expr.expr {
-            ScExpr::IntLit(i) => match expr.typ {
-                Type::I8 => Ok(ValueExpr::I8(*i as i8)),
-                Type::I16 => Ok(ValueExpr::I16(*i as i16)),
-                Type::I32 => Ok(ValueExpr::I32(*i as i32)),
-                Type::I64 => Ok(ValueExpr::I64(*i as i64)),
-                Type::I128 => Ok(ValueExpr::I128(*i as i128)),
-                Type::Isz => match self.target.ptr_width() {
-                    PtrWidth::Ptr16 => Ok(ValueExpr::I16(*i as i16)),
-                    PtrWidth::Ptr32 => Ok(ValueExpr::I32(*i as i32)),
-                    PtrWidth::Ptr64 => Ok(ValueExpr::I64(*i as i64)),
-                },
-                Type::U8 => Ok(ValueExpr::U8(*i as u8)),
-                Type::U16 => Ok(ValueExpr::U16(*i as u16)),
-                Type::U32 => Ok(ValueExpr::U32(*i as u32)),
-                Type::U64 => Ok(ValueExpr::U64(*i as u64)),
-                Type::U128 => Ok(ValueExpr::U128(*i /* as u128 */)),
-                Type::Usz => match self.target.ptr_width() {
-                    PtrWidth::Ptr16 => Ok(ValueExpr::U16(*i as u16)),
-                    PtrWidth::Ptr32 => Ok(ValueExpr::U32(*i as u32)),
-                    PtrWidth::Ptr64 => Ok(ValueExpr::U64(*i as u64)),
-                },
-                _ => Ok(ValueExpr::I32(*i as i32)),
-            },
+            ScExpr::IntLit(i, _) => Ok(self.int_value(*i, &expr.typ)),
             ScExpr::BoolLit(b) => Ok(ValueExpr::Boolean(*b)),
             ScExpr::StringLit(str) => Ok(ValueExpr::Str(str.clone())),
+            // a character literal coerced to an integer is its codepoint
+            ScExpr::CharLit(c) if expr.typ.is_int() => Ok(self.int_value(*c as u128, &expr.typ)),
             ScExpr::CharLit(c) => Ok(ValueExpr::Char(*c)),
-            ScExpr::FloatLit(f) => match expr.typ {
+            ScExpr::FloatLit(f, _) => match expr.typ {
                 Type::F16 | Type::F128 => {
                     self.sink.emit(feature_todo! {
                         feature: "f16 / f128 compile-time evaluation",
@@ -814,27 +1183,145 @@ impl SemaChecker {
                 _ => Ok(ValueExpr::F32(*f as f32)),
             },
             ScExpr::Ident(sym) if sym.is_comptime_known() => sym.value().ok_or((expr_loc, None)),
+            ScExpr::Binary {
+                lhs,
+                op: op @ (BinOp::LogicalAnd | BinOp::LogicalOr),
+                rhs,
+            } => {
+                let lhs_val = self.evaluate_expr(lhs)?;
+                let lhs_bool = lhs_val
+                    .as_bool()
+                    .ok_or((lhs.loc.clone().unwrap_or(expr_loc.clone()), None))?;
+
+                // short-circuit evaluation, like at runtime
+                match (op, lhs_bool) {
+                    (BinOp::LogicalAnd, false) => Ok(ValueExpr::Boolean(false)),
+                    (BinOp::LogicalOr, true) => Ok(ValueExpr::Boolean(true)),
+                    _ => self.evaluate_expr(rhs),
+                }
+            }
             ScExpr::Binary { lhs, op, rhs } => {
                 let lhs_val = self.evaluate_expr(lhs)?;
                 let rhs_val = self.evaluate_expr(rhs)?;
 
+                let cmp = |pred: fn(Ordering) -> bool| {
+                    lhs_val
+                        .compare(&rhs_val)
+                        .map(|ord| ValueExpr::Boolean(pred(ord)))
+                        .ok_or((expr_loc.clone(), None))
+                };
+
                 match op {
                     BinOp::Add => Ok(lhs_val.add(&rhs_val).map_err(|note| (expr_loc, note))?),
                     BinOp::Sub => Ok(lhs_val.sub(&rhs_val).map_err(|note| (expr_loc, note))?),
                     BinOp::Mul => Ok(lhs_val.mul(&rhs_val).map_err(|note| (expr_loc, note))?),
                     BinOp::Div => Ok(lhs_val.div(&rhs_val).map_err(|note| (expr_loc, note))?),
                     BinOp::Rem => Ok(lhs_val.rem(&rhs_val).map_err(|note| (expr_loc, note))?),
+                    BinOp::BitwiseAnd => {
+                        Ok(lhs_val.bitand(&rhs_val).map_err(|note| (expr_loc, note))?)
+                    }
+                    BinOp::BitwiseOr => {
+                        Ok(lhs_val.bitor(&rhs_val).map_err(|note| (expr_loc, note))?)
+                    }
+                    BinOp::BitwiseXor => {
+                        Ok(lhs_val.bitxor(&rhs_val).map_err(|note| (expr_loc, note))?)
+                    }
+                    BinOp::Shl => Ok(lhs_val.shl(&rhs_val).map_err(|note| (expr_loc, note))?),
+                    BinOp::Shr => Ok(lhs_val.shr(&rhs_val).map_err(|note| (expr_loc, note))?),
+                    BinOp::CompLT => cmp(Ordering::is_lt),
+                    BinOp::CompLE => cmp(Ordering::is_le),
+                    BinOp::CompGT => cmp(Ordering::is_gt),
+                    BinOp::CompGE => cmp(Ordering::is_ge),
+                    BinOp::CompEq => cmp(Ordering::is_eq),
+                    BinOp::CompNe => cmp(Ordering::is_ne),
                     _ => Err((expr_loc, None)),
                 }
             }
+            // the literal of a negated literal can be one past the maximum of
+            // its type, like `-128i8`, it wraps to the minimum
+            ScExpr::Unary {
+                op: UnaryOp::Negation,
+                expr: exp,
+            } if matches!(exp.expr, ScExpr::IntLit(..))
+                && matches!(exp.typ.signedness(), Some(Signedness::Signed)) =>
+            {
+                let ScExpr::IntLit(i, _) = exp.expr else {
+                    opt_unreachable!()
+                };
+
+                Ok(self.int_value(i.wrapping_neg(), &exp.typ))
+            }
+            ScExpr::Unary {
+                op: op @ (UnaryOp::Negation | UnaryOp::Not),
+                expr: exp,
+            } => {
+                let val = self.evaluate_expr(exp)?;
+
+                match op {
+                    UnaryOp::Negation => val.neg(),
+                    _ => val.not(),
+                }
+                .map_err(|note| (expr_loc, note))
+            }
+            ScExpr::Cast { expr: exp, .. } => {
+                let val = self.evaluate_expr(exp)?;
+
+                self.cast_value(val, &expr.typ).ok_or((expr_loc, None))
+            }
+            ScExpr::If {
+                cond,
+                then_br,
+                else_br,
+            } => {
+                let cond_val = self.evaluate_expr(cond)?;
+
+                match (cond_val.as_bool(), else_br) {
+                    (None, _) => Err((cond.loc.clone().unwrap_or(expr_loc.clone()), None)),
+                    (Some(true), _) => self.evaluate_expr(then_br),
+                    (Some(false), Some(else_br)) => self.evaluate_expr(else_br),
+                    (Some(false), None) => Ok(ValueExpr::Void),
+                }
+            }
             ScExpr::Block {
                 label: _,
                 block,
                 index: _,
-            } if block.stmts.is_empty() && block.last_expr.is_none() => {
-                // minimal support for blocks evaluation
-                Ok(ValueExpr::Void)
+            } => {
+                for stmt in &block.stmts {
+                    match &stmt.stmt {
+                        ScStmt::VariableDef {
+                            mutable: false,
+                            value,
+                            sym,
+                            ..
+                        } => {
+                            let val = self.evaluate_expr(value)?;
+                            sym.clone().set_value(Some(val));
+                        }
+                        ScStmt::VariableDef { mutable: true, .. } => {
+                            return Err((
+                                stmt.loc.clone().unwrap_or(expr_loc.clone()),
+                                Some(
+                                    "mutable variables can't be evaluated at compile-time"
+                                        .to_string(),
+                                ),
+                            ));
+                        }
+                        ScStmt::Expression(expr) | ScStmt::BoundsCheck(expr) => {
+                            self.evaluate_expr(expr)?;
+                        }
+                        ScStmt::Defer { .. } => {
+                            return Err((stmt.loc.clone().unwrap_or(expr_loc.clone()), None));
+                        }
+                    }
+                }
+
+                match &block.last_expr {
+                    Some(last) => self.evaluate_expr(last),
+                    None => Ok(ValueExpr::Void),
+                }
             }
+            ScExpr::Comptime(exp) => self.evaluate_expr(exp),
             ScExpr::PointerType { mutable, typexpr } => {
                 let typ = self.evaluate_expr(typexpr)?.as_type().unwrap_or(Type::Void);
                 // NOTE: we do not emit a diagnostic because we already did in
@@ -910,9 +1397,322 @@ impl SemaChecker {
                     ret: Box::new(ret_typ),
                 }))
             }
+            // a call to an instance of a generic function with only `comptime`
+            // parameters, like `Vec(i32)`
+            ScExpr::FunCall { callee, args, .. } if args.is_empty() => {
+                let (ScExpr::Ident(sym) | ScExpr::QualifiedPath { sym, .. }) = &callee.expr else {
+                    return Err((expr_loc, None));
+                };
+
+                let Some(body) = self.instance_body(sym) else {
+                    return Err((
+                        expr_loc,
+                        Some(
+                            "only instances of functions with `comptime` parameters can be called at compile-time"
+                                .to_string(),
+                        ),
+                    ));
+                };
+
+                let value = self.evaluate_expr(&ScExpression {
+                    expr: ScExpr::Block {
+                        label: None,
+                        block: body,
+                        index: None,
+                    },
+                    typ: expr.typ.clone(),
+                    loc: expr.loc.clone(),
+                })?;
+
+                // an anonymous struct returned by an instance is named after it
+                if let ValueExpr::Type(Type::Struct { name: None, index }) = value {
+                    self.structs[index].name = Some(sym.name());
+
+                    return Ok(ValueExpr::Type(self.struct_type(index)));
+                }
+
+                Ok(value)
+            }
+            ScExpr::Struct {
+                fields: _,
+                index: Some(index),
+            } => Ok(ValueExpr::Type(self.struct_type(*index))),
+            ScExpr::Enum {
+                variants: _,
+                index: Some(index),
+            } => Ok(ValueExpr::Type(self.enum_type(*index))),
+            // a variant of an enum, `Color.Red`, evaluates to its discriminant
+            ScExpr::MemberAccess { expr: exp, member } if exp.typ == Type::Type => {
+                let discriminant = self
+                    .enum_info(&expr.typ)
+                    .and_then(|info| info.variant(member))
+                    .ok_or((expr_loc, None))?;
+
+                Ok(self.int_value(discriminant as u128, &Type::Isz))
+            }
+            ScExpr::Tuple(exprs) if exprs.is_empty() => Ok(ValueExpr::Void),
+            // a tuple of types is a tuple type, `(i32, bool)`
+            ScExpr::Tuple(exprs) if expr.typ == Type::Type => {
+                let mut typs = Vec::new();
+
+                for exp in exprs {
+                    match self.evaluate_expr(exp)?.as_type() {
+                        Some(typ) => typs.push(typ),
+                        None => return Err((exp.loc.clone().unwrap_or(expr_loc), None)),
+                    }
+                }
+
+                Ok(ValueExpr::Type(Type::Tuple(typs)))
+            }
             _ => Err((expr_loc, None)),
         }
     }
+
+    /// Converts the integer `i` to the value expression of the integer type
+    /// `typ`, defaults to `i32` if it is not an integer type.
+    fn int_value(&self, i: u128, typ: &Type) -> ValueExpr {
+        match typ {
+            Type::I8 => ValueExpr::I8(i as i8),
+            Type::I16 => ValueExpr::I16(i as i16),
+            Type::I32 => ValueExpr::I32(i as i32),
+            Type::I64 => ValueExpr::I64(i as i64),
+            Type::I128 => ValueExpr::I128(i as i128),
+            Type::Isz => match self.target.ptr_width() {
+                PtrWidth::Ptr16 => ValueExpr::I16(i as i16),
+                PtrWidth::Ptr32 => ValueExpr::I32(i as i32),
+                PtrWidth::Ptr64 => ValueExpr::I64(i as i64),
+            },
+            Type::U8 => ValueExpr::U8(i as u8),
+            Type::U16 => ValueExpr::U16(i as u16),
+            Type::U32 => ValueExpr::U32(i as u32),
+            Type::U64 => ValueExpr::U64(i as u64),
+            Type::U128 => ValueExpr::U128(i /* as u128 */),
+            Type::Usz => match self.target.ptr_width() {
+                PtrWidth::Ptr16 => ValueExpr::U16(i as u16),
+                PtrWidth::Ptr32 => ValueExpr::U32(i as u32),
+                PtrWidth::Ptr64 => ValueExpr::U64(i as u64),
+            },
+            _ => ValueExpr::I32(i as i32),
+        }
+    }
+
+    /// Converts the value `val` to the type `typ` of a cast, integers are
+    /// truncated or extended and floats are rounded toward zero. Returns `None`
+    /// if the value can't be cast at compile-time.
+    fn cast_value(&self, val: ValueExpr, typ: &Type) -> Option<ValueExpr> {
+        if typ.is_int() {
+            let i = match val {
+                ValueExpr::Boolean(b) => b as u128,
+                ValueExpr::Char(c) => c as u128,
+                ValueExpr::F32(f) => f as i128 as u128,
+                ValueExpr::F64(f) => f as i128 as u128,
+                ValueExpr::U128(u) => u,
+                _ => val.as_i128()? as u128,
+            };
+
+            Some(self.int_value(i, typ))
+        } else if typ.is_float() {
+            let f = match val {
+                ValueExpr::F32(f) => f as f64,
+                ValueExpr::F64(f) => f,
+                ValueExpr::U128(u) => u as f64,
+                _ => val.as_i128()? as f64,
+            };
+
+            match typ {
+                Type::F32 => Some(ValueExpr::F32(f as f32)),
+                Type::F64 => Some(ValueExpr::F64(f)),
+                _ => None,
+            }
+        } else if *typ == Type::Char {
+            match val {
+                ValueExpr::Char(c) => Some(ValueExpr::Char(c)),
+                ValueExpr::U8(b) => Some(ValueExpr::Char(char::from(b))),
+                _ => None,
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Defines a new struct without any field in the struct table and returns
+    /// its index.
+    pub fn define_struct(&mut self, name: Option<String>, loc: OSpan) -> usize {
+        self.structs.push(StructInfo {
+            name,
+            fields: Vec::new(),
+            loc,
+        });
+
+        self.structs.len() - 1
+    }
+
+    /// Returns the type of the struct at `index` in the struct table.
+    pub fn struct_type(&self, index: usize) -> Type {
+        Type::Struct {
+            name: self.structs[index].name.clone(),
+            index,
+        }
+    }
+
+    /// Returns the struct info of the struct type, if it is one.
+    pub fn struct_info(&self, typ: &Type) -> Option<&StructInfo> {
+        match typ {
+            Type::Struct { name: _, index } => self.structs.get(*index),
+            _ => None,
+        }
+    }
+
+    /// Defines a new enum without any variant in the enum table and returns its
+    /// index.
+    pub fn define_enum(&mut self, name: Option<String>, loc: OSpan) -> usize {
+        self.enums.push(EnumInfo {
+            name,
+            variants: Vec::new(),
+            loc,
+        });
+
+        self.enums.len() - 1
+    }
+
+    /// Returns the type of the enum at `index` in the enum table.
+    pub fn enum_type(&self, index: usize) -> Type {
+        Type::Enum {
+            name: self.enums[index].name.clone(),
+            index,
+        }
+    }
+
+    /// Returns the enum info of the enum type, if it is one.
+    pub fn enum_info(&self, typ: &Type) -> Option<&EnumInfo> {
+        match typ {
+            Type::Enum { name: _, index } => self.enums.get(*index),
+            _ => None,
+        }
+    }
+
+    /// Returns the item named `name` associated to the type `typ`, if any.
+    pub fn assoc_item(&self, typ: &Type, name: &str) -> Option<&AssocItem> {
+        self.assoc_items
+            .iter()
+            .find(|item| item.typ == *typ && item.name == name)
+    }
+
+    /// Returns the symbol of the method named `name` of the type `typ`, an
+    /// associated item is a method if it is a function.
+    pub fn method(&self, typ: &Type, name: &str) -> Option<Symbol> {
+        self.assoc_item(typ, name)
+            .filter(|item| item.sym.typ().is_fun_ptr())
+            .map(|item| item.sym.clone())
+    }
+}
+
+/// An item defined in an impl block, stored in the associated items table of
+/// the [`SemaChecker`].
+#[derive(Debug, Clone)]
+pub struct AssocItem {
+    /// the type the item is associated to
+    pub typ: Type,
+    /// name of the item
+    pub name: String,
+    /// symbol of the item
+    pub sym: Symbol,
+    /// visibility of the item, a private item can only be used inside of the
+    /// module of its impl block and its submodules
+    pub vis: Vis,
+    /// path of the module of the impl block
+    pub module: EffectivePath,
+    /// location of the name of the item
+    pub loc: OSpan,
+}
+
+/// The parameters of a function definition, stored in the function parameters
+/// table of the [`SemaChecker`].
+#[derive(Debug, Clone)]
+pub struct FunParams {
+    /// symbol of the function
+    pub sym: Symbol,
+    /// name of the parameters and their checked default value, in the order
+    /// of definition
+    pub params: Vec<(String, Option<ScExpression>)>,
+    /// are the default values checked?
+    pub checked: bool,
+}
+
+impl FunParams {
+    /// Returns the position of the parameter named `name`, if any.
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.params.iter().position(|(param, _)| param == name)
+    }
+}
+
+/// Information about a struct type, stored in the struct table of the
+/// [`SemaChecker`].
+#[derive(Debug, Clone)]
+pub struct StructInfo {
+    /// name of the struct, `None` if the struct is anonymous
+    pub name: Option<String>,
+    /// the fields of the struct, in the order of definition
+    pub fields: Vec<(String, Type)>,
+    /// location of the struct type expression
+    pub loc: OSpan,
+}
+
+impl StructInfo {
+    /// Returns the type of the field named `name`, if any.
+    pub fn field(&self, name: &str) -> Option<&Type> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, typ)| typ)
+    }
+
+    /// Returns the name of the field closest to `name`, used to suggest a
+    /// field when `name` is not a field of the struct.
+    pub fn suggest_field(&self, name: &str) -> Option<String> {
+        let names: Vec<&str> = self
+            .fields
+            .iter()
+            .map(|(field, _)| field.as_str())
+            .collect();
+
+        suggest(name, &names, DEFAULT_MAX_LEVENSHTEIN_DISTANCE).map(ToString::to_string)
+    }
+}
+
+/// Information about an enum type, stored in the enum table of the
+/// [`SemaChecker`].
+#[derive(Debug, Clone)]
+pub struct EnumInfo {
+    /// name of the enum, `None` if the enum is anonymous
+    pub name: Option<String>,
+    /// the variants of the enum and their discriminant, in the order of
+    /// definition
+    pub variants: Vec<(String, i128)>,
+    /// location of the enum type expression
+    pub loc: OSpan,
+}
+
+impl EnumInfo {
+    /// Returns the discriminant of the variant named `name`, if any.
+    pub fn variant(&self, name: &str) -> Option<i128> {
+        self.variants
+            .iter()
+            .find(|(variant, _)| variant == name)
+            .map(|(_, discriminant)| *discriminant)
+    }
+
+    /// Returns the name of the variant closest to `name`, used to suggest a
+    /// variant when `name` is not a variant of the enum.
+    pub fn suggest_variant(&self, name: &str) -> Option<String> {
+        let names: Vec<&str> = self
+            .variants
+            .iter()
+            .map(|(variant, _)| variant.as_str())
+            .collect();
+
+        suggest(name, &names, DEFAULT_MAX_LEVENSHTEIN_DISTANCE).map(ToString::to_string)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -942,11 +1742,6 @@ impl LabelKind {
     pub fn is_loop(&self) -> bool {
         !matches!(self, LabelKind::Block)
     }
-
-    /// Can this label kind have a value in `break` expressions?
-    pub fn can_have_val(&self) -> bool {
-        matches!(self, LabelKind::Block | LabelKind::InfiniteLoop)
-    }
 }
 
 #[derive(Debug, Clone)]
@@ -958,6 +1753,8 @@ pub struct LabelInfo {
     pub index: usize,
     /// expected type of the loop
     pub typ: Type,
+    /// location of the first `break` that set the type of the label
+    pub typ_loc: OSpan,
     /// what kind of label it is.
     pub kind: LabelKind,
     /// **For loop**'s label, if set to `true` it indicates that we `break`'d
@@ -1001,6 +1798,7 @@ impl LabelStack {
             name,
             index,
             typ: Type::Unknown,
+            typ_loc: None,
             kind,
             break_out: false,
         });
@@ -1008,6 +1806,11 @@ impl LabelStack {
         index
     }
 
+    /// Returns the index the next defined label will have.
+    pub fn next_index(&self) -> usize {
+        self.last
+    }
+
     /// Return the last label of the loop
     pub fn last(&self) -> Option<&LabelInfo> {
         self.labels.last()
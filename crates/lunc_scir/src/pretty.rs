@@ -7,7 +7,10 @@ use lunc_utils::{
     pretty::{PrettyCtxt, PrettyDump},
 };
 
-use crate::{ScArg, ScBlock, ScExpr, ScExpression, ScItem, ScModule, ScStatement, ScStmt};
+use crate::{
+    ScArg, ScBlock, ScEnumVariant, ScExpr, ScExpression, ScFieldValue, ScImplDef, ScItem,
+    ScMatchArm, ScModule, ScNamedArg, ScStatement, ScStmt, ScStructField,
+};
 
 impl PrettyDump for ScModule {
     fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
@@ -127,12 +130,41 @@ impl PrettyDump for ScItem {
 
                 ctx.print_loc(loc)?;
 
+                Ok(())
+            }
+            ScItem::Impl {
+                params,
+                target,
+                defs,
+                loc,
+            } => {
+                ctx.pretty_struct("Impl")
+                    .field("params", params.as_slice())
+                    .field("target", target)
+                    .field("defs", defs.as_slice())
+                    .finish()?;
+
+                ctx.print_loc(loc)?;
+
                 Ok(())
             }
         }
     }
 }
 
+impl PrettyDump for ScImplDef {
+    fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
+        let ScImplDef { vis, item } = self;
+
+        ctx.pretty_struct("ImplDef")
+            .field("vis", vis)
+            .field("item", item)
+            .finish()?;
+
+        Ok(())
+    }
+}
+
 impl PrettyDump for ScExpression {
     fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
         let ScExpression { expr, typ, loc } = self;
@@ -152,11 +184,13 @@ impl PrettyDump for ScExpr {
         let out = &mut ctx.out;
 
         match self {
-            ScExpr::IntLit(i) => write!(out, "integer {i}"),
+            ScExpr::IntLit(i, None) => write!(out, "integer {i}"),
+            ScExpr::IntLit(i, Some(suffix)) => write!(out, "integer {i}{suffix}"),
             ScExpr::BoolLit(b) => write!(out, "boolean {b}"),
             ScExpr::StringLit(s) => write!(out, "string {s:?}"),
             ScExpr::CharLit(c) => write!(out, "character {c:?}"),
-            ScExpr::FloatLit(f) => write!(out, "float {f:.}"),
+            ScExpr::FloatLit(f, None) => write!(out, "float {f:.}"),
+            ScExpr::FloatLit(f, Some(suffix)) => write!(out, "float {f:.}{suffix}"),
             ScExpr::Ident(sym) => sym.try_dump(ctx),
             ScExpr::Binary { lhs, op, rhs } => {
                 ctx.pretty_struct("Binary")
@@ -175,6 +209,14 @@ impl PrettyDump for ScExpr {
 
                 Ok(())
             }
+            ScExpr::Cast { expr, typexpr } => {
+                ctx.pretty_struct("Cast")
+                    .field("expr", expr)
+                    .field("typexpr", typexpr)
+                    .finish()?;
+
+                Ok(())
+            }
             ScExpr::Borrow { mutable, expr } => {
                 ctx.pretty_struct("Borrow")
                     .field("mutable", mutable)
@@ -183,10 +225,35 @@ impl PrettyDump for ScExpr {
 
                 Ok(())
             }
-            ScExpr::FunCall { callee, args } => {
+            ScExpr::Range {
+                start,
+                end,
+                inclusive,
+            } => {
+                ctx.pretty_struct("Range")
+                    .field("start", start)
+                    .field("end", end)
+                    .field("inclusive", inclusive)
+                    .finish()?;
+
+                Ok(())
+            }
+            ScExpr::Tuple(exprs) => {
+                ctx.pretty_struct("Tuple")
+                    .field("exprs", exprs.as_slice())
+                    .finish()?;
+
+                Ok(())
+            }
+            ScExpr::FunCall {
+                callee,
+                args,
+                named_args,
+            } => {
                 ctx.pretty_struct("FunCall")
                     .field("callee", callee)
                     .field("args", args.as_slice())
+                    .field("named_args", named_args.as_slice())
                     .finish()?;
 
                 Ok(())
@@ -242,11 +309,17 @@ impl PrettyDump for ScExpr {
                 ctx.pretty_struct("Return").field("expr", expr).finish()?;
                 Ok(())
             }
-            ScExpr::Break { label, expr, index } => {
+            ScExpr::Break {
+                label,
+                expr,
+                index,
+                desugared,
+            } => {
                 ctx.pretty_struct("Break")
                     .field("label", label)
                     .field("expr", expr)
                     .field("index", index)
+                    .field("desugared", desugared)
                     .finish()?;
 
                 Ok(())
@@ -262,6 +335,11 @@ impl PrettyDump for ScExpr {
             ScExpr::Null => {
                 write!(ctx.out, "Null")
             }
+            ScExpr::Comptime(expr) => {
+                ctx.pretty_struct("Comptime").field("expr", expr).finish()?;
+
+                Ok(())
+            }
             ScExpr::MemberAccess { expr, member } => {
                 ctx.pretty_struct("MemberAccess")
                     .field("expr", expr)
@@ -270,6 +348,22 @@ impl PrettyDump for ScExpr {
 
                 Ok(())
             }
+            ScExpr::StructLit { typexpr, fields } => {
+                ctx.pretty_struct("StructLit")
+                    .field("typexpr", typexpr)
+                    .field("fields", fields.as_slice())
+                    .finish()?;
+
+                Ok(())
+            }
+            ScExpr::Match { expr, arms } => {
+                ctx.pretty_struct("Match")
+                    .field("expr", expr)
+                    .field("arms", arms.as_slice())
+                    .finish()?;
+
+                Ok(())
+            }
             ScExpr::QualifiedPath { path, sym } => {
                 ctx.pretty_struct("QualifiedPath")
                     .field("path", path)
@@ -287,6 +381,19 @@ impl PrettyDump for ScExpr {
 
                 Ok(())
             }
+            ScExpr::FunDefinition {
+                args,
+                rettypexpr,
+                body,
+            } => {
+                ctx.pretty_struct("FunDefinition")
+                    .field("args", args.as_slice())
+                    .field("rettypexpr", rettypexpr)
+                    .field("body", body)
+                    .finish()?;
+
+                Ok(())
+            }
             ScExpr::FunPtrType { args, ret } => {
                 ctx.pretty_struct("FunPtrType")
                     .field("args", args.as_slice())
@@ -295,6 +402,22 @@ impl PrettyDump for ScExpr {
 
                 Ok(())
             }
+            ScExpr::Struct { fields, index } => {
+                ctx.pretty_struct("Struct")
+                    .field("fields", fields.as_slice())
+                    .field("index", index)
+                    .finish()?;
+
+                Ok(())
+            }
+            ScExpr::Enum { variants, index } => {
+                ctx.pretty_struct("Enum")
+                    .field("variants", variants.as_slice())
+                    .field("index", index)
+                    .finish()?;
+
+                Ok(())
+            }
             ScExpr::Poisoned { diag } => {
                 write!(ctx.out, "POISONED: {diag:#?}")
             }
@@ -302,19 +425,98 @@ impl PrettyDump for ScExpr {
     }
 }
 
+impl PrettyDump for ScStructField {
+    fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
+        let ScStructField {
+            name,
+            name_loc,
+            typexpr,
+            loc,
+        } = self;
+
+        ctx.pretty_struct("StructField")
+            .field("name", (name, name_loc))
+            .field("typexpr", typexpr)
+            .finish()?;
+
+        ctx.print_loc(loc)?;
+
+        Ok(())
+    }
+}
+
+impl PrettyDump for ScEnumVariant {
+    fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
+        let ScEnumVariant {
+            name,
+            name_loc,
+            value,
+            loc,
+        } = self;
+
+        ctx.pretty_struct("EnumVariant")
+            .field("name", (name, name_loc))
+            .field("value", value)
+            .finish()?;
+
+        ctx.print_loc(loc)?;
+
+        Ok(())
+    }
+}
+
+impl PrettyDump for ScMatchArm {
+    fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
+        let ScMatchArm { pats, body, loc } = self;
+
+        ctx.pretty_struct("MatchArm")
+            .field("pats", pats.as_slice())
+            .field("body", body)
+            .finish()?;
+
+        ctx.print_loc(loc)?;
+
+        Ok(())
+    }
+}
+
+impl PrettyDump for ScFieldValue {
+    fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
+        let ScFieldValue {
+            name,
+            name_loc,
+            value,
+            loc,
+        } = self;
+
+        ctx.pretty_struct("FieldValue")
+            .field("name", (name, name_loc))
+            .field("value", value)
+            .finish()?;
+
+        ctx.print_loc(loc)?;
+
+        Ok(())
+    }
+}
+
 impl PrettyDump for ScArg {
     fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
         let ScArg {
+            comptime,
             name,
             name_loc,
             typexpr,
+            default,
             loc,
             sym,
         } = self;
 
         ctx.pretty_struct("Arg")
+            .field("comptime", comptime)
             .field("name", (name, name_loc))
             .field("typexpr", typexpr)
+            .field("default", default)
             .field("sym", sym)
             .finish()?;
 
@@ -324,6 +526,26 @@ impl PrettyDump for ScArg {
     }
 }
 
+impl PrettyDump for ScNamedArg {
+    fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
+        let ScNamedArg {
+            name,
+            name_loc,
+            value,
+            loc,
+        } = self;
+
+        ctx.pretty_struct("NamedArg")
+            .field("name", (name, name_loc))
+            .field("value", value)
+            .finish()?;
+
+        ctx.print_loc(loc)?;
+
+        Ok(())
+    }
+}
+
 impl PrettyDump for ScBlock {
     fn try_dump(&self, ctx: &mut PrettyCtxt) -> io::Result<()> {
         let ScBlock {
@@ -383,6 +605,13 @@ impl PrettyDump for ScStmt {
                 Ok(())
             }
             ScStmt::Expression(expr) => expr.try_dump(ctx),
+            ScStmt::BoundsCheck(expr) => {
+                ctx.pretty_struct("BoundsCheck")
+                    .field("expr", expr)
+                    .finish()?;
+
+                Ok(())
+            }
         }
     }
 }
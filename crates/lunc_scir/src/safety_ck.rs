@@ -50,6 +50,10 @@ impl SemaChecker {
 
                 Ok(())
             }
+            ScItem::FunDefinition { sym, .. } if self.is_generic(sym) => {
+                // NOTE: only the instances of a generic function are checked.
+                Ok(())
+            }
             ScItem::FunDefinition {
                 name: _,
                 name_loc: _,
@@ -124,49 +128,101 @@ impl SemaChecker {
             } => {
                 self.safety_ck_items(items);
 
+                Ok(())
+            }
+            ScItem::Impl {
+                params,
+                target: _,
+                defs,
+                loc: _,
+            } => {
+                // NOTE: only the instances of a generic impl block are checked
+                if params.is_empty() {
+                    for ScImplDef { item: def, .. } in defs {
+                        match self.safety_ck_item(def) {
+                            Ok(()) => {}
+                            Err(d) => self.sink.emit(d),
+                        }
+                    }
+                }
+
                 Ok(())
             }
         }
     }
 
-    pub fn safety_ck_expr(&mut self, expr: &ScExpression) -> Result<(), Diagnostic> {
-        match &expr.expr {
-            ScExpr::IntLit(int) => {
-                if expr.typ == Type::U128 {
-                    return Ok(());
-                }
+    /// Checks that the integer literal `int` of the expression fits in the
+    /// type of the expression, `negated` is true if the literal is the operand
+    /// of a negation, `-128i8`.
+    fn safety_ck_int_lit(
+        &mut self,
+        int: u128,
+        negated: bool,
+        expr: &ScExpression,
+    ) -> Result<(), Diagnostic> {
+        if expr.typ == Type::U128 {
+            return Ok(());
+        }
 
-                let range = expr.typ.integer_range(&self.target).unwrap();
+        // NOTE: the literal was not typed because the checking of the
+        // expression around it failed, an error was already emitted
+        let Some(range) = expr.typ.integer_range(&self.target) else {
+            return Ok(());
+        };
+
+        let int: i128 = match i128::try_from(int) {
+            Ok(i) if negated => -i,
+            Ok(i) => i,
+            Err(_) if negated && int == i128::MIN.unsigned_abs() => i128::MIN,
+            Err(_) => {
+                let range = Idk128::I128(*range.start())..=Idk128::I128(*range.end());
+
+                return Err(OverflowingLiteral {
+                    integer: Idk128::U128(int),
+                    typ: expr.typ.clone(),
+                    range,
+                    loc: expr.loc.clone().unwrap(),
+                }
+                .into_diag());
+            }
+        };
 
-                let int: i128 = match (*int).try_into() {
-                    Ok(i) => i,
-                    Err(_) => {
-                        let range = Idk128::I128(*range.start())..=Idk128::I128(*range.end());
+        if !range.contains(&int) {
+            let range = Idk128::I128(*range.start())..=Idk128::I128(*range.end());
 
-                        return Err(OverflowingLiteral {
-                            integer: Idk128::U128(*int),
-                            typ: expr.typ.clone(),
-                            range,
-                            loc: expr.loc.clone().unwrap(),
-                        }
-                        .into_diag());
-                    }
-                };
+            self.sink.emit(OverflowingLiteral {
+                integer: Idk128::I128(int),
+                typ: expr.typ.clone(),
+                range: range.clone(),
+                loc: expr.loc.clone().unwrap(),
+            });
+        }
 
-                if !range.contains(&int) {
-                    let range = Idk128::I128(*range.start())..=Idk128::I128(*range.end());
+        Ok(())
+    }
 
-                    self.sink.emit(OverflowingLiteral {
-                        integer: Idk128::I128(int),
-                        typ: expr.typ.clone(),
-                        range: range.clone(),
-                        loc: expr.loc.clone().unwrap(),
-                    });
-                }
+    pub fn safety_ck_expr(&mut self, expr: &ScExpression) -> Result<(), Diagnostic> {
+        match &expr.expr {
+            ScExpr::IntLit(int, _) => self.safety_ck_int_lit(*int, false, expr),
+            // a negated literal of a signed type can be one past its maximum,
+            // like `-128i8`
+            ScExpr::Unary {
+                op: UnaryOp::Negation,
+                expr: exp,
+            } if matches!(exp.expr, ScExpr::IntLit(..))
+                && matches!(exp.typ.signedness(), Some(Signedness::Signed)) =>
+            {
+                let ScExpr::IntLit(int, _) = exp.expr else {
+                    opt_unreachable!()
+                };
 
-                Ok(())
+                self.safety_ck_int_lit(int, true, exp)
             }
-            ScExpr::FloatLit(float) => {
+            // a character literal coerced to an integer must fit in it
+            ScExpr::CharLit(c) if expr.typ.is_int() => {
+                self.safety_ck_int_lit(*c as u128, false, expr)
+            }
+            ScExpr::FloatLit(float, _) => {
                 let range = expr.typ.float_range().unwrap();
 
                 if expr.typ == Type::F16 || expr.typ == Type::F128 {
@@ -204,13 +260,49 @@ impl SemaChecker {
 
                 Ok(())
             }
-            ScExpr::FunCall { callee, args } => {
+            ScExpr::Cast { expr, typexpr } => {
+                self.safety_ck_expr(expr)?;
+                self.safety_ck_expr(typexpr)?;
+
+                Ok(())
+            }
+            ScExpr::Range {
+                start,
+                end,
+                inclusive: _,
+            } => {
+                if let Some(start) = start {
+                    self.safety_ck_expr(start)?;
+                }
+
+                if let Some(end) = end {
+                    self.safety_ck_expr(end)?;
+                }
+
+                Ok(())
+            }
+            ScExpr::Tuple(exprs) => {
+                for exp in exprs {
+                    self.safety_ck_expr(exp)?;
+                }
+
+                Ok(())
+            }
+            ScExpr::FunCall {
+                callee,
+                args,
+                named_args,
+            } => {
                 self.safety_ck_expr(callee)?;
 
                 for arg in args {
                     self.safety_ck_expr(arg)?;
                 }
 
+                for arg in named_args {
+                    self.safety_ck_expr(&arg.value)?;
+                }
+
                 Ok(())
             }
             ScExpr::If {
@@ -246,6 +338,7 @@ impl SemaChecker {
                 label: _,
                 expr,
                 index: _,
+                desugared: _,
             } => {
                 if let Some(expr) = expr {
                     self.safety_ck_expr(expr)?;
@@ -254,12 +347,64 @@ impl SemaChecker {
                 Ok(())
             }
             ScExpr::Continue { label: _, index: _ } | ScExpr::Null => Ok(()),
+            ScExpr::Comptime(expr) => self.safety_ck_expr(expr),
             ScExpr::MemberAccess { expr, member: _ } => {
                 self.safety_ck_expr(expr)?;
 
                 Ok(())
             }
+            ScExpr::StructLit { typexpr, fields } => {
+                self.safety_ck_expr(typexpr)?;
+
+                for field in fields {
+                    match self.safety_ck_expr(&field.value) {
+                        Ok(()) => {}
+                        Err(d) => self.sink.emit(d),
+                    }
+                }
+
+                Ok(())
+            }
+            ScExpr::Match { expr, arms } => {
+                self.safety_ck_expr(expr)?;
+
+                for arm in arms {
+                    for pat in &arm.pats {
+                        match self.safety_ck_expr(pat) {
+                            Ok(()) => {}
+                            Err(d) => self.sink.emit(d),
+                        }
+                    }
+
+                    match self.safety_ck_expr(&arm.body) {
+                        Ok(()) => {}
+                        Err(d) => self.sink.emit(d),
+                    }
+                }
+
+                Ok(())
+            }
             ScExpr::QualifiedPath { path: _, sym: _ } | ScExpr::Underscore => Ok(()),
+            ScExpr::FunDefinition {
+                args,
+                rettypexpr,
+                body,
+            } => {
+                for arg in args {
+                    match self.safety_ck_arg(arg) {
+                        Ok(()) => {}
+                        Err(d) => self.sink.emit(d),
+                    }
+                }
+
+                if let Some(rettyexpr) = rettypexpr {
+                    self.safety_ck_expr(rettyexpr)?;
+                }
+
+                self.safety_ck_block(body);
+
+                Ok(())
+            }
             ScExpr::PointerType {
                 mutable: _,
                 typexpr,
@@ -282,6 +427,28 @@ impl SemaChecker {
 
                 Ok(())
             }
+            ScExpr::Struct { fields, index: _ } => {
+                for field in fields {
+                    match self.safety_ck_expr(&field.typexpr) {
+                        Ok(()) => {}
+                        Err(d) => self.sink.emit(d),
+                    }
+                }
+
+                Ok(())
+            }
+            ScExpr::Enum { variants, index: _ } => {
+                for variant in variants {
+                    if let Some(value) = &variant.value {
+                        match self.safety_ck_expr(value) {
+                            Ok(()) => {}
+                            Err(d) => self.sink.emit(d),
+                        }
+                    }
+                }
+
+                Ok(())
+            }
             ScExpr::Poisoned { diag: _ } => Ok(()),
         }
     }
@@ -319,7 +486,7 @@ impl SemaChecker {
 
                 Ok(())
             }
-            ScStmt::Defer { expr } | ScStmt::Expression(expr) => {
+            ScStmt::Defer { expr } | ScStmt::Expression(expr) | ScStmt::BoundsCheck(expr) => {
                 self.safety_ck_expr(expr)?;
 
                 Ok(())
@@ -329,15 +496,21 @@ impl SemaChecker {
 
     pub fn safety_ck_arg(&mut self, arg: &ScArg) -> Result<(), Diagnostic> {
         let ScArg {
+            comptime: _,
             name: _,
             name_loc: _,
             typexpr,
+            default,
             loc: _,
             sym: _,
         } = arg;
 
         self.safety_ck_expr(typexpr)?;
 
+        if let Some(default) = default {
+            self.safety_ck_expr(default)?;
+        }
+
         Ok(())
     }
 }
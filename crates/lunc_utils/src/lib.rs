@@ -382,8 +382,8 @@ pub fn levenshtein_distance(a: &str, b: &str) -> usize {
     for (i, item) in dp.iter_mut().enumerate().take(n + 1) {
         item[0] = i;
     }
-    for j in 0..=m {
-        dp[0][j] = j;
+    for (j, item) in dp[0].iter_mut().enumerate() {
+        *item = j;
     }
 
     for i in 1..=n {
@@ -69,6 +69,23 @@ pub enum Type {
     Str,
     /// Unicode code point, AKA character
     Char,
+    /// Range of integers, the type of a range expression like `0..<10`, the
+    /// type it contains is the type of its bounds.
+    Range(Box<Type>),
+    /// Struct type, the fields of the struct are stored by the semantic
+    /// checker at `index`, this way a struct can contain a pointer to itself.
+    ///
+    /// Two struct types are the same only if they come from the same struct
+    /// type expression.
+    Struct { name: Option<String>, index: usize },
+    /// Enum type, the variants of the enum are stored by the semantic checker
+    /// at `index`, like [`Type::Struct`].
+    Enum { name: Option<String>, index: usize },
+    /// Type of the `null` literal, it coerces to any pointer type.
+    Null,
+    /// Tuple type, like `(i32, bool)`, it always has at least one element, the
+    /// empty tuple `()` is the value of type [`Type::Void`].
+    Tuple(Vec<Type>),
     /// Type, it is the "type" of a type, because types in Lun are first class
     /// citizens.
     ///
@@ -187,6 +204,26 @@ impl Type {
         matches!(self, Type::F16 | Type::F32 | Type::F64 | Type::F128)
     }
 
+    /// Can a value of this type (self) be cast to the type (other) with a cast
+    /// expression `expr as T`?
+    ///
+    /// Integers and floats can be cast to any integer or float type, booleans
+    /// and characters to integers, bytes (`u8`) to characters and pointers to
+    /// other pointers.
+    pub fn can_cast(&self, other: &Type) -> bool {
+        let is_num = |typ: &Type| typ.is_int() || typ.is_float();
+
+        match (self, other) {
+            _ if self == other => true,
+            (from, to) if is_num(from) && is_num(to) => true,
+            (Type::Bool, to) if to.is_int() => true,
+            (Type::Char, to) if to.is_int() => true,
+            (Type::U8, Type::Char) => true,
+            (Type::Ptr { .. }, Type::Ptr { .. }) => true,
+            _ => false,
+        }
+    }
+
     /// Is this type a mutable pointer type? `*mut T`?
     pub fn is_mut_ptr(&self) -> bool {
         matches!(
@@ -198,6 +235,11 @@ impl Type {
         )
     }
 
+    /// Is this type a function pointer type? `*fun(..) -> T`?
+    pub fn is_fun_ptr(&self) -> bool {
+        matches!(self, Type::FunPtr { args: _, ret: _ })
+    }
+
     /// Returns the signedness of an integer type or `None` if it's not an
     /// integer
     pub fn signedness(&self) -> Option<Signedness> {
@@ -254,7 +296,7 @@ impl Type {
             | Type::U128
             | Type::Usz => other.is_int(),
             Type::F16 | Type::F32 | Type::F64 | Type::F128 => other.is_float(),
-            Type::Bool => other.is_int(),
+            Type::Bool | Type::Char => other.is_int(),
             Type::Void | Type::FunPtr { .. } => false,
             Type::Ptr { mutable, typ } => matches!(
                 other,
@@ -265,7 +307,15 @@ impl Type {
             ),
             // NOTE: noreturn can coerce to everything.
             Type::Noreturn => true,
-            Type::Str | Type::Char | Type::Type => false,
+            Type::Range(typ) => matches!(other, Type::Range(other_ty) if typ.can_coerce(other_ty)),
+            Type::Struct { .. } | Type::Enum { .. } => false,
+            Type::Null => matches!(other, Type::Ptr { .. }),
+            Type::Tuple(typs) => matches!(
+                other,
+                Type::Tuple(other_typs) if typs.len() == other_typs.len()
+                    && typs.iter().zip(other_typs).all(|(typ, other_ty)| typ.can_coerce(other_ty))
+            ),
+            Type::Str | Type::Type => false,
         }
     }
 
@@ -467,6 +517,35 @@ impl Display for Type {
             Type::Noreturn => write!(f, "noreturn"),
             Type::Str => write!(f, "str"),
             Type::Char => write!(f, "char"),
+            Type::Range(typ) => write!(f, "range({typ})"),
+            Type::Struct {
+                name: Some(name),
+                index: _,
+            } => write!(f, "{name}"),
+            Type::Struct { name: None, index } => write!(f, "struct#{index}"),
+            Type::Enum {
+                name: Some(name),
+                index: _,
+            } => write!(f, "{name}"),
+            Type::Enum { name: None, index } => write!(f, "enum#{index}"),
+            Type::Null => write!(f, "null"),
+            Type::Tuple(typs) => {
+                write!(f, "(")?;
+
+                for (i, typ) in typs.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+
+                    typ.fmt(f)?;
+                }
+
+                if typs.len() == 1 {
+                    write!(f, ",")?;
+                }
+
+                write!(f, ")")
+            }
             Type::Type => write!(f, "type"),
         }
     }
@@ -721,7 +800,8 @@ impl Symbol {
     // NOTE: this is probably fine for now but if we allow interior mutability,
     // it can lead to bugs.
     /// Returns true if the symbol is known at compile time, like immutable
-    /// local variables or immutable global variables or functions
+    /// local variables or immutable global variables or functions, or the
+    /// `comptime` arguments of an instantiated function, they have a value.
     pub fn is_comptime_known(&self) -> bool {
         self.inspect(|sym| {
             matches!(
@@ -729,7 +809,7 @@ impl Symbol {
                 SymKind::Local { mutable: false }
                     | SymKind::Global { mutable: false }
                     | SymKind::Function
-            )
+            ) || (sym.kind == SymKind::Arg && sym.value.is_some())
         })
     }
 }
@@ -982,6 +1062,71 @@ macro_rules! value_expr_impl_op {
     };
 }
 
+macro_rules! value_expr_impl_bitwise_op {
+    {name = $name:ident, int_fn = $int_fn:path,} => {
+        /// Tries to perform a bitwise operation on two value expressions,
+        /// returns an error if they are not integers.
+        ///
+        /// # Note
+        ///
+        /// This operation only works if both values have the same type.
+        pub fn $name(&self, other: &ValueExpr) -> Result<Self, Option<String>> {
+            use ValueExpr::*;
+
+            match (self, other) {
+                // signed integers
+                (I8(lhs), I8(rhs)) => Ok(I8($int_fn(*lhs, *rhs))),
+                (I16(lhs), I16(rhs)) => Ok(I16($int_fn(*lhs, *rhs))),
+                (I32(lhs), I32(rhs)) => Ok(I32($int_fn(*lhs, *rhs))),
+                (I64(lhs), I64(rhs)) => Ok(I64($int_fn(*lhs, *rhs))),
+                (I128(lhs), I128(rhs)) => Ok(I128($int_fn(*lhs, *rhs))),
+
+                // unsigned integers
+                (U8(lhs), U8(rhs)) => Ok(U8($int_fn(*lhs, *rhs))),
+                (U16(lhs), U16(rhs)) => Ok(U16($int_fn(*lhs, *rhs))),
+                (U32(lhs), U32(rhs)) => Ok(U32($int_fn(*lhs, *rhs))),
+                (U64(lhs), U64(rhs)) => Ok(U64($int_fn(*lhs, *rhs))),
+                (U128(lhs), U128(rhs)) => Ok(U128($int_fn(*lhs, *rhs))),
+                _ => Err(None),
+            }
+        }
+    };
+}
+
+macro_rules! value_expr_impl_shift_op {
+    {name = $name:ident, int_fn = $int_fn:ident,} => {
+        /// Tries to shift an integer value expression by `amount` bits,
+        /// returns an error with a note if the amount is greater or equal to
+        /// the bit width of the integer.
+        ///
+        /// # Note
+        ///
+        /// Unlike the other operations, `amount` can be an integer of any type.
+        pub fn $name(&self, amount: &ValueExpr) -> Result<Self, Option<String>> {
+            use ValueExpr::*;
+            let err = Some("attempt to shift with overflow".to_string());
+            let amount = amount.as_shift_amount().ok_or(err.clone())?;
+
+            match self {
+                // signed integers
+                I8(i) => Ok(I8(i.$int_fn(amount).ok_or(err)?)),
+                I16(i) => Ok(I16(i.$int_fn(amount).ok_or(err)?)),
+                I32(i) => Ok(I32(i.$int_fn(amount).ok_or(err)?)),
+                I64(i) => Ok(I64(i.$int_fn(amount).ok_or(err)?)),
+                I128(i) => Ok(I128(i.$int_fn(amount).ok_or(err)?)),
+
+                // unsigned integers
+                U8(i) => Ok(U8(i.$int_fn(amount).ok_or(err)?)),
+                U16(i) => Ok(U16(i.$int_fn(amount).ok_or(err)?)),
+                U32(i) => Ok(U32(i.$int_fn(amount).ok_or(err)?)),
+                U64(i) => Ok(U64(i.$int_fn(amount).ok_or(err)?)),
+                U128(i) => Ok(U128(i.$int_fn(amount).ok_or(err)?)),
+                _ => Err(None),
+            }
+        }
+    };
+}
+
 /// A value of an expression evaluated at compile time, during constant folding
 /// of SCIR or types are also ValueExprs.
 ///
@@ -1011,7 +1156,8 @@ pub enum ValueExpr {
     Void,
 }
 
-use std::ops::{Add, Div, Mul, Rem, Sub};
+use std::cmp::Ordering;
+use std::ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Rem, Sub};
 
 impl ValueExpr {
     /// Tries to convert this value to a type.
@@ -1056,6 +1202,171 @@ impl ValueExpr {
         int_fn = checked_rem,
         float_fn = Rem::rem,
     }
+
+    value_expr_impl_bitwise_op! {
+        name = bitand,
+        int_fn = BitAnd::bitand,
+    }
+
+    value_expr_impl_bitwise_op! {
+        name = bitor,
+        int_fn = BitOr::bitor,
+    }
+
+    value_expr_impl_bitwise_op! {
+        name = bitxor,
+        int_fn = BitXor::bitxor,
+    }
+
+    value_expr_impl_shift_op! {
+        name = shl,
+        int_fn = checked_shl,
+    }
+
+    value_expr_impl_shift_op! {
+        name = shr,
+        int_fn = checked_shr,
+    }
+
+    /// Converts an integer value to the amount of bits of a shift, returns
+    /// `None` if it is not an integer or if it doesn't fit in an `u32`.
+    fn as_shift_amount(&self) -> Option<u32> {
+        use ValueExpr::*;
+
+        match *self {
+            I8(i) => u32::try_from(i).ok(),
+            I16(i) => u32::try_from(i).ok(),
+            I32(i) => u32::try_from(i).ok(),
+            I64(i) => u32::try_from(i).ok(),
+            I128(i) => u32::try_from(i).ok(),
+            U8(i) => Some(i.into()),
+            U16(i) => Some(i.into()),
+            U32(i) => Some(i),
+            U64(i) => u32::try_from(i).ok(),
+            U128(i) => u32::try_from(i).ok(),
+            _ => None,
+        }
+    }
+
+    /// Tries to convert an integer value to an `i128`, returns `None` if it is
+    /// not an integer or if it doesn't fit in an `i128`.
+    pub fn as_i128(&self) -> Option<i128> {
+        use ValueExpr::*;
+
+        match *self {
+            I8(i) => Some(i.into()),
+            I16(i) => Some(i.into()),
+            I32(i) => Some(i.into()),
+            I64(i) => Some(i.into()),
+            I128(i) => Some(i),
+            U8(i) => Some(i.into()),
+            U16(i) => Some(i.into()),
+            U32(i) => Some(i.into()),
+            U64(i) => Some(i.into()),
+            U128(i) => i128::try_from(i).ok(),
+            _ => None,
+        }
+    }
+
+    /// Tries to convert this value to a boolean.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            ValueExpr::Boolean(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Compares two value expressions, returns `None` if the values can't be
+    /// compared.
+    ///
+    /// # Note
+    ///
+    /// This operation only works if both values have the same type.
+    pub fn compare(&self, other: &ValueExpr) -> Option<Ordering> {
+        use ValueExpr::*;
+
+        match (self, other) {
+            (Boolean(lhs), Boolean(rhs)) => lhs.partial_cmp(rhs),
+            (I8(lhs), I8(rhs)) => lhs.partial_cmp(rhs),
+            (I16(lhs), I16(rhs)) => lhs.partial_cmp(rhs),
+            (I32(lhs), I32(rhs)) => lhs.partial_cmp(rhs),
+            (I64(lhs), I64(rhs)) => lhs.partial_cmp(rhs),
+            (I128(lhs), I128(rhs)) => lhs.partial_cmp(rhs),
+            (U8(lhs), U8(rhs)) => lhs.partial_cmp(rhs),
+            (U16(lhs), U16(rhs)) => lhs.partial_cmp(rhs),
+            (U32(lhs), U32(rhs)) => lhs.partial_cmp(rhs),
+            (U64(lhs), U64(rhs)) => lhs.partial_cmp(rhs),
+            (U128(lhs), U128(rhs)) => lhs.partial_cmp(rhs),
+            (Str(lhs), Str(rhs)) => lhs.partial_cmp(rhs),
+            (Char(lhs), Char(rhs)) => lhs.partial_cmp(rhs),
+            (F32(lhs), F32(rhs)) => lhs.partial_cmp(rhs),
+            (F64(lhs), F64(rhs)) => lhs.partial_cmp(rhs),
+            _ => None,
+        }
+    }
+
+    /// Tries to negate the value, works on signed integers and floats.
+    pub fn neg(&self) -> Result<Self, Option<String>> {
+        use ValueExpr::*;
+        let err = Some("integer overflow".to_string());
+
+        match self {
+            I8(i) => Ok(I8(i.checked_neg().ok_or(err)?)),
+            I16(i) => Ok(I16(i.checked_neg().ok_or(err)?)),
+            I32(i) => Ok(I32(i.checked_neg().ok_or(err)?)),
+            I64(i) => Ok(I64(i.checked_neg().ok_or(err)?)),
+            I128(i) => Ok(I128(i.checked_neg().ok_or(err)?)),
+            F32(f) => Ok(F32(-f)),
+            F64(f) => Ok(F64(-f)),
+            _ => Err(None),
+        }
+    }
+
+    /// Tries to perform a not on the value, it is a logical not on booleans
+    /// and a bitwise not on integers.
+    pub fn not(&self) -> Result<Self, Option<String>> {
+        use ValueExpr::*;
+
+        match self {
+            Boolean(b) => Ok(Boolean(!b)),
+            I8(i) => Ok(I8(!i)),
+            I16(i) => Ok(I16(!i)),
+            I32(i) => Ok(I32(!i)),
+            I64(i) => Ok(I64(!i)),
+            I128(i) => Ok(I128(!i)),
+            U8(i) => Ok(U8(!i)),
+            U16(i) => Ok(U16(!i)),
+            U32(i) => Ok(U32(!i)),
+            U64(i) => Ok(U64(!i)),
+            U128(i) => Ok(U128(!i)),
+            _ => Err(None),
+        }
+    }
+}
+
+impl Display for ValueExpr {
+    /// Formats the value like it would be written in the source code.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueExpr::Type(typ) => write!(f, "{typ}"),
+            ValueExpr::Boolean(b) => write!(f, "{b}"),
+            ValueExpr::I8(i) => write!(f, "{i}"),
+            ValueExpr::I16(i) => write!(f, "{i}"),
+            ValueExpr::I32(i) => write!(f, "{i}"),
+            ValueExpr::I64(i) => write!(f, "{i}"),
+            ValueExpr::I128(i) => write!(f, "{i}"),
+            ValueExpr::U8(i) => write!(f, "{i}"),
+            ValueExpr::U16(i) => write!(f, "{i}"),
+            ValueExpr::U32(i) => write!(f, "{i}"),
+            ValueExpr::U64(i) => write!(f, "{i}"),
+            ValueExpr::U128(i) => write!(f, "{i}"),
+            ValueExpr::Str(s) => write!(f, "{s:?}"),
+            ValueExpr::Char(c) => write!(f, "{c:?}"),
+            ValueExpr::F32(x) => write!(f, "{x}"),
+            ValueExpr::F64(x) => write!(f, "{x}"),
+            ValueExpr::Void => write!(f, "()"),
+        }
+    }
 }
 
 impl PrettyDump for ValueExpr {
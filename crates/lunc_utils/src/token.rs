@@ -234,7 +234,8 @@ pub enum TokenType {
     CharLit(char),
     /// float literal
     FloatLit(f64),
-    /// specialized string literal
+    /// specialized string literal, escape sequences are not processed, e.g:
+    /// the raw string `r"C:\path"`
     SpecializedStringLit { specialization: String, str: String },
     /// specialized char literal
     SpecializedCharLit { specialization: String, char: char },
@@ -291,6 +292,8 @@ pub enum Keyword {
     Defer,
     /// else
     Else,
+    /// enum
+    Enum,
     /// extern
     Extern,
     /// false
@@ -309,6 +312,8 @@ pub enum Keyword {
     Let,
     /// loop
     Loop,
+    /// match
+    Match,
     /// mut
     Mut,
     /// null
@@ -328,6 +333,8 @@ pub enum Keyword {
     /// here the name of this keyword is `SelfVal` because we can't name it
     /// `Self` because it's a keyword and neither `r#Self`.
     SelfVal,
+    /// struct
+    Struct,
     /// then
     Then,
     /// trait
@@ -360,6 +367,9 @@ impl Keyword {
     /// `else` keyword.
     pub const ELSE: &str = "else";
 
+    /// `enum` keyword.
+    pub const ENUM: &str = "enum";
+
     /// `extern` keyword.
     pub const EXTERN: &str = "extern";
 
@@ -387,6 +397,9 @@ impl Keyword {
     /// `loop` keyword.
     pub const LOOP: &str = "loop";
 
+    /// `match` keyword.
+    pub const MATCH: &str = "match";
+
     /// `mut` keyword
     pub const MUT: &str = "mut";
 
@@ -408,6 +421,9 @@ impl Keyword {
     /// `self` keyword.
     pub const SELF: &str = "self";
 
+    /// `struct` keyword.
+    pub const STRUCT: &str = "struct";
+
     /// `then` keyword.
     pub const THEN: &str = "then";
 
@@ -431,6 +447,7 @@ impl Display for Keyword {
             Keyword::Continue => f.write_str(Keyword::CONTINUE),
             Keyword::Defer => f.write_str(Keyword::DEFER),
             Keyword::Else => f.write_str(Keyword::ELSE),
+            Keyword::Enum => f.write_str(Keyword::ENUM),
             Keyword::Extern => f.write_str(Keyword::EXTERN),
             Keyword::False => f.write_str(Keyword::FALSE),
             Keyword::For => f.write_str(Keyword::FOR),
@@ -440,6 +457,7 @@ impl Display for Keyword {
             Keyword::In => f.write_str(Keyword::IN),
             Keyword::Let => f.write_str(Keyword::LET),
             Keyword::Loop => f.write_str(Keyword::LOOP),
+            Keyword::Match => f.write_str(Keyword::MATCH),
             Keyword::Mut => f.write_str(Keyword::MUT),
             Keyword::Null => f.write_str(Keyword::NULL),
             Keyword::Or => f.write_str(Keyword::OR),
@@ -447,6 +465,7 @@ impl Display for Keyword {
             Keyword::Pub => f.write_str(Keyword::PUB),
             Keyword::Return => f.write_str(Keyword::RETURN),
             Keyword::SelfVal => f.write_str(Keyword::SELF),
+            Keyword::Struct => f.write_str(Keyword::STRUCT),
             Keyword::Then => f.write_str(Keyword::THEN),
             Keyword::Trait => f.write_str(Keyword::TRAIT),
             Keyword::True => f.write_str(Keyword::TRUE),
@@ -485,6 +504,8 @@ pub enum Punctuation {
     Equal,
     /// ==
     Equal2,
+    /// =>
+    EqualGt,
     /// !=
     BangEqual,
     /// !
@@ -517,6 +538,12 @@ pub enum Punctuation {
     Dot,
     /// .*
     DotStar,
+    /// ..
+    Dot2,
+    /// ..<
+    Dot2Lt,
+    /// ..=
+    Dot2Equal,
     /// #
     Hashtag,
 }
@@ -539,6 +566,7 @@ impl Display for Punctuation {
             Comma => f.write_str(","),
             Equal => f.write_str("="),
             Equal2 => f.write_str("=="),
+            EqualGt => f.write_str("=>"),
             BangEqual => f.write_str("!="),
             Bang => f.write_str("!"),
             LtEqual => f.write_str("<="),
@@ -555,6 +583,9 @@ impl Display for Punctuation {
             Percent => f.write_str("%"),
             Dot => f.write_str("."),
             DotStar => f.write_str(".*"),
+            Dot2 => f.write_str(".."),
+            Dot2Lt => f.write_str("..<"),
+            Dot2Equal => f.write_str("..="),
             Hashtag => f.write_str("#"),
         }
     }
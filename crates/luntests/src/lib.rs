@@ -19,6 +19,19 @@ use serde::{Deserialize, Serialize};
 use termcolor::{Color, ColorSpec, StandardStream, WriteColor};
 use thiserror::Error;
 
+/// Entry points of the multifile tests, the other files of `tests/multifile/`
+/// are modules loaded by those.
+pub const MULTIFILE_TESTS: &[&str] = &[
+    "lib",
+    "main",
+    "E052",
+    "E052_impl",
+    "E052_import",
+    "E058",
+    "E059",
+    "E060",
+];
+
 #[derive(Debug, Error)]
 pub enum TestError {
     #[error(transparent)]
@@ -66,7 +79,7 @@ impl TestContext {
 
                 if test_path.starts_with("./tests/multifile/") {
                     // NOTE: we are not really skipping this test, we just add
-                    // him after but just the `multifile/lib` one
+                    // after the entry points listed in `MULTIFILE_TESTS`
                     continue;
                 }
 
@@ -86,11 +99,13 @@ impl TestContext {
             }
         }
 
-        self.tests.push(Test {
-            name: String::from("multifile/lib"),
-            path: PathBuf::from("./tests/multifile/lib.lun"),
-            stage: TestStage::Multifile,
-        });
+        for name in MULTIFILE_TESTS {
+            self.tests.push(Test {
+                name: format!("multifile/{name}"),
+                path: PathBuf::from(format!("./tests/multifile/{name}.lun")),
+                stage: TestStage::Multifile,
+            });
+        }
 
         self.tests.sort_by(|a, b| a.name.cmp(&b.name));
 
@@ -284,6 +299,7 @@ pub enum TestStage {
     None,
     Lexer,
     Parser,
+    Format,
     Dsir,
     Scir,
     Multifile,
@@ -295,6 +311,7 @@ impl TestStage {
             TestStage::None => &[],
             TestStage::Lexer => &["-Dhalt-at=lexer", "-Dprint=tokenstream"],
             TestStage::Parser => &["-Dhalt-at=parser", "-Dprint=ast"],
+            TestStage::Format => &["-Dhalt-at=parser", "-Dprint=ast-source"],
             TestStage::Dsir => &["-Dhalt-at=dsir", "-Dprint=dsir-tree"],
             TestStage::Scir => &["-Dhalt-at=scir", "-Dprint=scir-tree"],
             // NOTE: we print the dsir tree but we do not halt like the Dsir
@@ -312,6 +329,8 @@ impl FromStr for TestStage {
             Ok(TestStage::Lexer)
         } else if s.starts_with("parser/") {
             Ok(TestStage::Parser)
+        } else if s.starts_with("format/") {
+            Ok(TestStage::Format)
         } else if s.starts_with("desugaring/") {
             Ok(TestStage::Dsir)
         } else if s.starts_with("scir/") {
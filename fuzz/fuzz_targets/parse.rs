@@ -0,0 +1,11 @@
+//! Fuzzes the parser entry point, any input must produce either a module or
+//! diagnostics, but never a panic.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(source) = std::str::from_utf8(data) {
+        _ = lunc_parser::parse(source);
+    }
+});